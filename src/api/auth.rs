@@ -0,0 +1,237 @@
+// Copyright 2025 Lablup Inc. and Jeongkyu Shin
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Bearer-token authentication for the API server
+//!
+//! Accepted tokens are read from a file, one per line. The file is watched
+//! for changes so tokens can be rotated without restarting the server; when a
+//! reload fails the previously loaded tokens stay in effect.
+
+use std::io;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, SystemTime};
+
+use axum::extract::{Request, State};
+use axum::http::{header, HeaderValue, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+
+/// How often the token file is checked for modifications
+const TOKEN_FILE_POLL_SECS: u64 = 5;
+
+pub struct TokenStore {
+    path: PathBuf,
+    tokens: RwLock<Vec<String>>,
+    modified: Mutex<Option<SystemTime>>,
+}
+
+impl TokenStore {
+    /// Load tokens from `path`. Fails if the file cannot be read.
+    pub fn load(path: impl Into<PathBuf>) -> io::Result<Self> {
+        let store = Self {
+            path: path.into(),
+            tokens: RwLock::new(Vec::new()),
+            modified: Mutex::new(None),
+        };
+        store.reload()?;
+        Ok(store)
+    }
+
+    /// Number of tokens currently accepted
+    pub fn token_count(&self) -> usize {
+        self.tokens.read().unwrap().len()
+    }
+
+    /// Re-read the token file, replacing the accepted tokens
+    pub fn reload(&self) -> io::Result<usize> {
+        let modified = std::fs::metadata(&self.path)?.modified().ok();
+        let tokens = parse_tokens(&std::fs::read_to_string(&self.path)?);
+        let count = tokens.len();
+        if count == 0 {
+            tracing::warn!(
+                "Token file {} contains no tokens; all requests will be rejected",
+                self.path.display()
+            );
+        }
+        *self.tokens.write().unwrap() = tokens;
+        *self.modified.lock().unwrap() = modified;
+        Ok(count)
+    }
+
+    /// Reload the token file if its modification time changed
+    pub fn reload_if_changed(&self) {
+        let modified = std::fs::metadata(&self.path)
+            .and_then(|m| m.modified())
+            .ok();
+        if modified.is_none() || modified == *self.modified.lock().unwrap() {
+            return;
+        }
+        match self.reload() {
+            Ok(count) => {
+                tracing::info!("Reloaded {count} API token(s) from {}", self.path.display())
+            }
+            Err(e) => tracing::warn!(
+                "Failed to reload API tokens from {}: {e}; keeping the previous tokens",
+                self.path.display()
+            ),
+        }
+    }
+
+    /// Check a presented token against every accepted token
+    pub fn is_valid(&self, presented: &str) -> bool {
+        let tokens = self.tokens.read().unwrap();
+        // Compare against all tokens so timing does not reveal which one matched
+        tokens.iter().fold(false, |found, token| {
+            constant_time_eq(token.as_bytes(), presented.as_bytes()) | found
+        })
+    }
+}
+
+/// Parse a token file: one token per line, blank lines and `#` comments ignored
+pub fn parse_tokens(content: &str) -> Vec<String> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_string)
+        .collect()
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// Poll the token file and reload it when it changes
+pub async fn watch_token_file(store: Arc<TokenStore>) {
+    let mut interval = tokio::time::interval(Duration::from_secs(TOKEN_FILE_POLL_SECS));
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    loop {
+        interval.tick().await;
+        store.reload_if_changed();
+    }
+}
+
+/// The token of an `Authorization` header value. The scheme name is
+/// case-insensitive (RFC 7235).
+fn bearer_token(value: &str) -> Option<&str> {
+    let (scheme, token) = value.trim_start().split_once(' ')?;
+    scheme.eq_ignore_ascii_case("Bearer").then(|| token.trim())
+}
+
+/// Middleware rejecting requests without a valid `Authorization: Bearer` token
+pub async fn require_bearer_token(
+    State(store): State<Arc<TokenStore>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let token = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(bearer_token);
+
+    match token {
+        Some(token) if store.is_valid(token) => next.run(request).await,
+        _ => {
+            let mut response = (StatusCode::UNAUTHORIZED, "Unauthorized\n").into_response();
+            response.headers_mut().insert(
+                header::WWW_AUTHENTICATE,
+                HeaderValue::from_static("Bearer realm=\"all-smi\""),
+            );
+            response
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::routing::get;
+    use axum::{middleware, Router};
+
+    #[test]
+    fn test_parse_tokens() {
+        let tokens = parse_tokens("# comment\nalpha\n\n  beta  \n#gamma\n");
+        assert_eq!(tokens, vec!["alpha", "beta"]);
+    }
+
+    #[test]
+    fn test_bearer_token() {
+        assert_eq!(bearer_token("Bearer secret"), Some("secret"));
+        assert_eq!(bearer_token("bearer secret"), Some("secret"));
+        assert_eq!(bearer_token("BEARER  secret "), Some("secret"));
+        assert_eq!(bearer_token("Basic c2VjcmV0"), None);
+        assert_eq!(bearer_token("Bearer"), None);
+        assert_eq!(bearer_token("Bearersecret"), None);
+    }
+
+    #[test]
+    fn test_token_store_reload() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("tokens");
+        std::fs::write(&path, "alpha\nbeta\n").unwrap();
+
+        let store = TokenStore::load(&path).unwrap();
+        assert_eq!(store.token_count(), 2);
+        assert!(store.is_valid("alpha"));
+        assert!(store.is_valid("beta"));
+        assert!(!store.is_valid("alph"));
+        assert!(!store.is_valid(""));
+
+        std::fs::write(&path, "gamma\n").unwrap();
+        store.reload().unwrap();
+        assert!(!store.is_valid("alpha"));
+        assert!(store.is_valid("gamma"));
+
+        // A failed reload keeps the previous tokens
+        std::fs::remove_file(&path).unwrap();
+        assert!(store.reload().is_err());
+        store.reload_if_changed();
+        assert!(store.is_valid("gamma"));
+    }
+
+    #[tokio::test]
+    async fn test_require_bearer_token() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("tokens");
+        std::fs::write(&path, "secret\n").unwrap();
+        let store = Arc::new(TokenStore::load(&path).unwrap());
+
+        let app = Router::new()
+            .route("/metrics", get(|| async { "ok" }))
+            .layer(middleware::from_fn_with_state(store, require_bearer_token));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+
+        let client = reqwest::Client::new();
+        let url = format!("http://{addr}/metrics");
+
+        let response = client.get(&url).send().await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert!(response.headers().contains_key(header::WWW_AUTHENTICATE));
+
+        let response = client.get(&url).bearer_auth("wrong").send().await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let response = client.get(&url).bearer_auth("secret").send().await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+}
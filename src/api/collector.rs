@@ -0,0 +1,438 @@
+// Copyright 2025 Lablup Inc. and Jeongkyu Shin
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Device collection for API mode with a deadline per reader
+//!
+//! Every reader call runs on the blocking pool, concurrently with the other
+//! readers. A reader that misses the deadline keeps serving its last good
+//! data, marked stale, while the call finishes in the background. No new call
+//! is started for that reader until the stuck one returns, so a hung vendor
+//! tool ties up at most one thread; the late result is published in the
+//! first cycle after it arrives.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use futures_util::future::join_all;
+use tokio::task::JoinHandle;
+
+use crate::app_state::ReaderStatus;
+use crate::common::config::{ReaderSettings, Settings};
+use crate::device::{
+    feed_gpu_power, get_chassis_readers, get_cpu_readers, get_memory_readers, get_new_gpu_readers,
+    ChassisInfo, ChassisReader, CpuInfo, CpuReader, GpuInfo, GpuReader, MemoryInfo, MemoryReader,
+    ProcessInfo,
+};
+
+/// Data returned by one reader call
+pub trait ReaderData: Clone + Default + Send + 'static {
+    fn device_count(&self) -> usize;
+}
+
+impl<T: Clone + Send + 'static> ReaderData for Vec<T> {
+    fn device_count(&self) -> usize {
+        self.len()
+    }
+}
+
+/// GPU readers report their devices together with the processes using them
+type GpuData = (Vec<GpuInfo>, Vec<ProcessInfo>);
+
+impl ReaderData for GpuData {
+    fn device_count(&self) -> usize {
+        self.0.len()
+    }
+}
+
+type ReadFn<R, T> = dyn Fn(&R) -> T + Send + Sync;
+
+/// A reader together with the last data it returned in time
+pub struct ReaderTask<R: ?Sized, T> {
+    kind: &'static str,
+    name: &'static str,
+    reader: Arc<R>,
+    read: Arc<ReadFn<R, T>>,
+    in_flight: Option<JoinHandle<T>>,
+    data: T,
+    read_at: Option<Instant>,
+    stale: bool,
+    timeouts: u64,
+    call_duration: Option<Duration>,
+}
+
+impl<R, T> ReaderTask<R, T>
+where
+    R: ?Sized + Send + Sync + 'static,
+    T: ReaderData,
+{
+    pub fn new(
+        kind: &'static str,
+        name: &'static str,
+        reader: Arc<R>,
+        read: impl Fn(&R) -> T + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            kind,
+            name,
+            reader,
+            read: Arc::new(read),
+            in_flight: None,
+            data: T::default(),
+            read_at: None,
+            stale: false,
+            timeouts: 0,
+            call_duration: None,
+        }
+    }
+
+    pub fn reader(&self) -> &R {
+        &self.reader
+    }
+
+    /// Read with a deadline and return the data to publish: the fresh result,
+    /// or the last good data if the reader overran or is still stuck
+    pub async fn run(&mut self, deadline: Duration) -> T {
+        let (mut handle, late) = match self.in_flight.take() {
+            Some(handle) if !handle.is_finished() => {
+                self.in_flight = Some(handle);
+                self.call_duration = None;
+                return self.data.clone();
+            }
+            // A call that came back after its deadline provides this cycle's
+            // data, so a reader slower than the deadline still publishes
+            Some(handle) => (handle, true),
+            None => {
+                let reader = self.reader.clone();
+                let read = self.read.clone();
+                (tokio::task::spawn_blocking(move || read(&reader)), false)
+            }
+        };
+
+        let started = Instant::now();
+        let result = tokio::time::timeout(deadline, &mut handle).await;
+        // The duration of a late call was not observed
+        self.call_duration = (!late).then(|| started.elapsed());
+        match result {
+            Ok(Ok(data)) => {
+                self.data = data;
+                self.read_at = Some(Instant::now());
+                self.stale = false;
+            }
+            Ok(Err(e)) => {
+                tracing::warn!("{} reader {} failed: {e}", self.kind, self.name);
+                self.stale = true;
+            }
+            Err(_) => {
+                tracing::warn!(
+                    "{} reader {} did not return within {}s; serving its last data",
+                    self.kind,
+                    self.name,
+                    deadline.as_secs_f64()
+                );
+                self.timeouts += 1;
+                self.stale = true;
+                self.in_flight = Some(handle);
+            }
+        }
+        self.data.clone()
+    }
+
+    pub fn status(&self) -> ReaderStatus {
+        ReaderStatus {
+            kind: self.kind,
+            name: self.name,
+            device_count: self.data.device_count(),
+            stale: self.stale,
+            data_age: self.read_at.map(|at| at.elapsed()),
+            timeouts_total: self.timeouts,
+            call_duration: self.call_duration,
+        }
+    }
+}
+
+/// Results of one collection cycle, in reader order
+#[derive(Default)]
+pub struct Cycle {
+    pub gpu_info: Vec<GpuInfo>,
+    pub process_info: Vec<ProcessInfo>,
+    pub cpu_info: Vec<CpuInfo>,
+    pub memory_info: Vec<MemoryInfo>,
+    pub chassis_info: Vec<ChassisInfo>,
+}
+
+/// All platform readers of API mode
+pub struct Collector {
+    deadline: Duration,
+    processes: Arc<AtomicBool>,
+    /// GPU reader tasks, keyed by the name `readers.disabled` uses for them
+    gpu: Vec<(&'static str, ReaderTask<dyn GpuReader, GpuData>)>,
+    cpu: Vec<ReaderTask<dyn CpuReader, Vec<CpuInfo>>>,
+    memory: Vec<ReaderTask<dyn MemoryReader, Vec<MemoryInfo>>>,
+    chassis: Vec<ReaderTask<dyn ChassisReader, Vec<ChassisInfo>>>,
+}
+
+impl Collector {
+    /// Create the platform readers. Process lists are only read when
+    /// `processes` is set.
+    pub fn new(processes: bool, deadline: Duration) -> Self {
+        let processes = Arc::new(AtomicBool::new(processes));
+        let gpu = gpu_tasks(&processes, &[]);
+        let cpu = get_cpu_readers()
+            .into_iter()
+            .map(|reader| {
+                let reader: Arc<dyn CpuReader> = Arc::from(reader);
+                ReaderTask::new("cpu", reader.name(), reader, |reader| reader.get_cpu_info())
+            })
+            .collect();
+        let memory = get_memory_readers()
+            .into_iter()
+            .map(|reader| {
+                let reader: Arc<dyn MemoryReader> = Arc::from(reader);
+                ReaderTask::new("memory", reader.name(), reader, |reader| {
+                    reader.get_memory_info()
+                })
+            })
+            .collect();
+        let chassis = get_chassis_readers()
+            .into_iter()
+            .map(|reader| {
+                let reader: Arc<dyn ChassisReader> = Arc::from(reader);
+                ReaderTask::new("chassis", reader.name(), reader, |reader| {
+                    reader.get_chassis_info().into_iter().collect()
+                })
+            })
+            .collect();
+
+        Self {
+            deadline,
+            processes,
+            gpu,
+            cpu,
+            memory,
+            chassis,
+        }
+    }
+
+    /// Apply a reloaded configuration: the process list setting, and the set
+    /// of GPU readers, which follows the enabled readers in [`Settings`].
+    /// Readers that stay keep their task, with its last data and any call in
+    /// flight; only newly enabled readers are created.
+    pub fn reload(&mut self, processes: bool) {
+        self.processes.store(processes, Ordering::Relaxed);
+        remove_disabled(&mut self.gpu, &Settings::current().readers);
+        let running: Vec<&str> = self.gpu.iter().map(|(name, _)| *name).collect();
+        for (name, task) in gpu_tasks(&self.processes, &running) {
+            tracing::info!("Enabled {} reader {}", task.kind, task.name);
+            self.gpu.push((name, task));
+        }
+    }
+
+    /// Run every reader once. GPU, CPU and memory readers run concurrently;
+    /// chassis readers follow because they aggregate this cycle's GPU power.
+    pub async fn collect(&mut self) -> Cycle {
+        let deadline = self.deadline;
+        let (gpu, cpu, memory) = tokio::join!(
+            join_all(self.gpu.iter_mut().map(|(_, task)| task.run(deadline))),
+            join_all(self.cpu.iter_mut().map(|task| task.run(deadline))),
+            join_all(self.memory.iter_mut().map(|task| task.run(deadline))),
+        );
+
+        let mut cycle = Cycle::default();
+        for (gpu_info, process_info) in gpu {
+            cycle.gpu_info.extend(gpu_info);
+            cycle.process_info.extend(process_info);
+        }
+        cycle.cpu_info = cpu.into_iter().flatten().collect();
+        cycle.memory_info = memory.into_iter().flatten().collect();
+
+        feed_gpu_power(
+            self.chassis.iter().map(|task| task.reader()),
+            &cycle.gpu_info,
+        );
+        cycle.chassis_info = join_all(self.chassis.iter_mut().map(|task| task.run(deadline)))
+            .await
+            .into_iter()
+            .flatten()
+            .collect();
+        cycle
+    }
+
+    pub fn status(&self) -> Vec<ReaderStatus> {
+        let gpu = self.gpu.iter().map(|(_, task)| task.status());
+        let cpu = self.cpu.iter().map(ReaderTask::status);
+        let memory = self.memory.iter().map(ReaderTask::status);
+        let chassis = self.chassis.iter().map(ReaderTask::status);
+        gpu.chain(cpu).chain(memory).chain(chassis).collect()
+    }
+}
+
+/// Drop the tasks of GPU readers the settings no longer enable
+fn remove_disabled<R: ?Sized, T>(
+    tasks: &mut Vec<(&'static str, ReaderTask<R, T>)>,
+    readers: &ReaderSettings,
+) {
+    tasks.retain(|(name, task)| {
+        let enabled = !readers.skip_gpu_detection && readers.is_enabled(name);
+        if !enabled {
+            tracing::info!("Disabled {} reader {}", task.kind, task.name);
+        }
+        enabled
+    });
+}
+
+fn gpu_tasks(
+    processes: &Arc<AtomicBool>,
+    running: &[&str],
+) -> Vec<(&'static str, ReaderTask<dyn GpuReader, GpuData>)> {
+    get_new_gpu_readers(running)
+        .into_iter()
+        .map(|(name, reader)| {
+            let reader: Arc<dyn GpuReader> = Arc::from(reader);
+            let processes = processes.clone();
+            let task = ReaderTask::new("gpu", reader.name(), reader, move |reader| {
+                let processes = if processes.load(Ordering::Relaxed) {
+                    reader.get_process_info()
+                } else {
+                    Vec::new()
+                };
+                (reader.get_gpu_info(), processes)
+            });
+            (name, task)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+
+    /// Reader returning the number of calls so far, after a configurable delay
+    #[derive(Default)]
+    struct FakeReader {
+        delay_ms: AtomicU64,
+        calls: AtomicUsize,
+    }
+
+    fn task(reader: Arc<FakeReader>) -> ReaderTask<FakeReader, Vec<usize>> {
+        ReaderTask::new("gpu", "FakeReader", reader, |reader| {
+            std::thread::sleep(Duration::from_millis(
+                reader.delay_ms.load(Ordering::SeqCst),
+            ));
+            vec![reader.calls.fetch_add(1, Ordering::SeqCst) + 1]
+        })
+    }
+
+    #[tokio::test]
+    async fn test_overrunning_reader_serves_last_data() {
+        let deadline = Duration::from_millis(100);
+        let reader = Arc::new(FakeReader::default());
+        let mut task = task(reader.clone());
+
+        assert_eq!(task.run(deadline).await, vec![1]);
+        let status = task.status();
+        assert!(!status.stale);
+        assert_eq!(status.device_count, 1);
+        assert_eq!(status.timeouts_total, 0);
+
+        // The reader hangs: the previous data is served and marked stale
+        reader.delay_ms.store(400, Ordering::SeqCst);
+        assert_eq!(task.run(deadline).await, vec![1]);
+        let status = task.status();
+        assert!(status.stale);
+        assert_eq!(status.timeouts_total, 1);
+        assert!(status.data_age.is_some());
+
+        // While the call is stuck no second call is started
+        reader.delay_ms.store(0, Ordering::SeqCst);
+        assert_eq!(task.run(deadline).await, vec![1]);
+        assert_eq!(reader.calls.load(Ordering::SeqCst), 1);
+        let status = task.status();
+        assert_eq!(status.timeouts_total, 1);
+        assert!(status.call_duration.is_none());
+
+        // Once the stuck call returns its result is published on the next
+        // cycle without another call
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        assert_eq!(task.run(deadline).await, vec![2]);
+        assert_eq!(reader.calls.load(Ordering::SeqCst), 2);
+        let status = task.status();
+        assert!(!status.stale);
+        assert_eq!(status.timeouts_total, 1);
+        assert!(status.call_duration.is_none());
+
+        // After that the reader is called as usual
+        assert_eq!(task.run(deadline).await, vec![3]);
+        assert!(task.status().call_duration.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_reader_slower_than_deadline_still_publishes() {
+        let deadline = Duration::from_millis(50);
+        let reader = Arc::new(FakeReader::default());
+        reader.delay_ms.store(120, Ordering::SeqCst);
+        let mut task = task(reader.clone());
+
+        let mut published = Vec::new();
+        for _ in 0..6 {
+            published.extend(task.run(deadline).await);
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        // The first call overran its deadline, yet its result was published
+        assert!(published.contains(&1), "{published:?}");
+    }
+
+    #[tokio::test]
+    async fn test_no_data_before_first_read() {
+        let reader = Arc::new(FakeReader::default());
+        reader.delay_ms.store(300, Ordering::SeqCst);
+        let mut task = task(reader);
+
+        assert!(task.run(Duration::from_millis(50)).await.is_empty());
+        let status = task.status();
+        assert!(status.stale);
+        assert_eq!(status.device_count, 0);
+        assert!(status.data_age.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_reload_removes_only_disabled_readers() {
+        let deadline = Duration::from_millis(100);
+        let mut kept = task(Arc::new(FakeReader::default()));
+        kept.run(deadline).await;
+        let dropped = ReaderTask::new("gpu", "Dropped", Arc::new(FakeReader::default()), |_| {
+            vec![0]
+        });
+        let mut tasks = vec![("nvidia", kept), ("furiosa", dropped)];
+
+        let readers = ReaderSettings {
+            disabled: vec!["furiosa".to_string()],
+            ..Default::default()
+        };
+        remove_disabled(&mut tasks, &readers);
+        let names: Vec<_> = tasks.iter().map(|(name, _)| *name).collect();
+        assert_eq!(names, ["nvidia"]);
+        // The running reader kept its data instead of starting over
+        assert_eq!(tasks[0].1.status().device_count, 1);
+        assert!(tasks[0].1.status().data_age.is_some());
+
+        let readers = ReaderSettings {
+            skip_gpu_detection: true,
+            ..Default::default()
+        };
+        remove_disabled(&mut tasks, &readers);
+        assert!(tasks.is_empty());
+    }
+}
@@ -0,0 +1,468 @@
+// Copyright 2025 Lablup Inc. and Jeongkyu Shin
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Pre-rendered `/metrics` responses
+//!
+//! The collection loop renders the exposition once per cycle in both text
+//! formats and stores it with the cycle's `data_version`. Scrapes are served
+//! from that copy without touching the application state; gzip and zstd
+//! encodings are produced on first use and shared by later scrapes of the
+//! same cycle. The ETag identifies the cycle, so a client that already has it
+//! gets `304 Not Modified`.
+//!
+//! Self-metrics move between scrapes of a cycle, so each response appends a
+//! tail rendered on request. The cached gzip stream ends on a byte boundary
+//! without its trailer, letting the tail's deflate blocks and the trailer
+//! complete a single gzip member; zstd carries the tail as a second frame.
+//! The tail is not part of the ETag.
+
+use std::io::Write;
+use std::sync::{Arc, OnceLock, RwLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use all_smi::traits::ExportFormat;
+use axum::body::Bytes;
+use axum::http::header::{CONTENT_ENCODING, CONTENT_TYPE, ETAG, VARY};
+use axum::http::{HeaderMap, HeaderValue, StatusCode};
+use axum::response::{IntoResponse, Response};
+use flate2::write::DeflateEncoder;
+use flate2::{Compression, Crc};
+
+use super::metrics::openmetrics::{
+    OPENMETRICS_CONTENT_TYPE, OPENMETRICS_EOF, PROMETHEUS_CONTENT_TYPE,
+};
+use super::metrics::MetricBuilder;
+
+pub type SharedExposition = Arc<ExpositionCache>;
+
+/// zstd level; metrics text compresses well at low levels
+const ZSTD_LEVEL: i32 = 3;
+
+/// Header of a gzip member with no file name, mtime or flags
+const GZIP_HEADER: [u8; 10] = [0x1f, 0x8b, 8, 0, 0, 0, 0, 0, 0, 0xff];
+
+/// Content codings supported for `/metrics`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Encoding {
+    Identity,
+    Gzip,
+    Zstd,
+}
+
+impl Encoding {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Identity => "identity",
+            Self::Gzip => "gzip",
+            Self::Zstd => "zstd",
+        }
+    }
+
+    /// Pick the coding from an `Accept-Encoding` header, preferring zstd
+    /// over gzip when both are acceptable. `*` covers only the codings the
+    /// header does not name.
+    pub fn negotiate(accept_encoding: Option<&str>) -> Self {
+        let Some(header) = accept_encoding else {
+            return Self::Identity;
+        };
+        let mut gzip = None;
+        let mut zstd = None;
+        let mut any = None;
+        for item in header.split(',') {
+            let mut parts = item.split(';').map(str::trim);
+            let coding = parts.next().unwrap_or_default().to_ascii_lowercase();
+            let accepted = parts
+                .filter_map(|param| param.strip_prefix("q="))
+                .all(|q| q.parse::<f32>().is_ok_and(|q| q > 0.0));
+            match coding.as_str() {
+                "zstd" => zstd = Some(accepted),
+                "gzip" | "x-gzip" => gzip = Some(accepted),
+                "*" => any = Some(accepted),
+                _ => {}
+            }
+        }
+        let any = any.unwrap_or(false);
+        if zstd.unwrap_or(any) {
+            Self::Zstd
+        } else if gzip.unwrap_or(any) {
+            Self::Gzip
+        } else {
+            Self::Identity
+        }
+    }
+}
+
+/// One rendered body and its compressed forms. The gzip form is left open
+/// so that a tail can still be appended (see `finish_gzip`).
+pub struct RenderedBody {
+    content_type: &'static str,
+    identity: Bytes,
+    gzip: OnceLock<Bytes>,
+    zstd: OnceLock<Bytes>,
+}
+
+impl RenderedBody {
+    pub fn new(content_type: &'static str, text: String) -> Self {
+        Self {
+            content_type,
+            identity: Bytes::from(text),
+            gzip: OnceLock::new(),
+            zstd: OnceLock::new(),
+        }
+    }
+
+    /// The compressed body as cached, compressing it on first use
+    fn compressed(&self, encoding: Encoding) -> std::io::Result<Bytes> {
+        let cell = match encoding {
+            Encoding::Identity => return Ok(self.identity.clone()),
+            Encoding::Gzip => &self.gzip,
+            Encoding::Zstd => &self.zstd,
+        };
+        if let Some(bytes) = cell.get() {
+            return Ok(bytes.clone());
+        }
+        let bytes = compress(&self.identity, encoding)?;
+        Ok(cell.get_or_init(|| bytes).clone())
+    }
+
+    /// The body followed by `tail` in the given coding; falls back to
+    /// identity if compression fails
+    fn encoded(&self, encoding: Encoding, tail: &[u8]) -> (Encoding, Bytes) {
+        let encoded = self.compressed(encoding).and_then(|body| match encoding {
+            Encoding::Identity if tail.is_empty() => Ok(body),
+            Encoding::Identity => Ok(concat(&body, tail)),
+            Encoding::Gzip => Ok(concat(&body, &finish_gzip(&self.identity, tail)?)),
+            Encoding::Zstd if tail.is_empty() => Ok(body),
+            Encoding::Zstd => Ok(concat(&body, &zstd::encode_all(tail, ZSTD_LEVEL)?)),
+        });
+        match encoded {
+            Ok(bytes) => (encoding, bytes),
+            Err(e) => {
+                tracing::warn!("Failed to {} /metrics response: {e}", encoding.as_str());
+                (Encoding::Identity, concat(&self.identity, tail))
+            }
+        }
+    }
+
+    /// Build the response, honouring `If-None-Match` when an ETag is given.
+    /// `tail` is sent after the body but does not change the ETag.
+    pub fn respond(&self, headers: &HeaderMap, etag: Option<&str>, tail: &[u8]) -> Response {
+        let encoding = Encoding::negotiate(
+            headers
+                .get(axum::http::header::ACCEPT_ENCODING)
+                .and_then(|v| v.to_str().ok()),
+        );
+        let etag = etag.map(|tag| format!("\"{tag}-{}\"", encoding.as_str()));
+
+        let mut response_headers = HeaderMap::new();
+        response_headers.insert(VARY, HeaderValue::from_static("Accept, Accept-Encoding"));
+        if let Some(etag) = etag
+            .as_deref()
+            .and_then(|tag| HeaderValue::from_str(tag).ok())
+        {
+            response_headers.insert(ETAG, etag);
+        }
+
+        let not_modified = etag.as_deref().is_some_and(|etag| {
+            headers
+                .get(axum::http::header::IF_NONE_MATCH)
+                .and_then(|v| v.to_str().ok())
+                .is_some_and(|value| etag_matches(value, etag))
+        });
+        if not_modified {
+            return (StatusCode::NOT_MODIFIED, response_headers).into_response();
+        }
+
+        let (encoding, body) = self.encoded(encoding, tail);
+        response_headers.insert(CONTENT_TYPE, HeaderValue::from_static(self.content_type));
+        if encoding != Encoding::Identity {
+            response_headers.insert(
+                CONTENT_ENCODING,
+                HeaderValue::from_static(encoding.as_str()),
+            );
+        }
+        (response_headers, body).into_response()
+    }
+}
+
+fn compress(data: &[u8], encoding: Encoding) -> std::io::Result<Bytes> {
+    let compressed = match encoding {
+        Encoding::Identity => data.to_vec(),
+        Encoding::Gzip => {
+            // A sync flush ends the stream on a byte boundary without a final
+            // block. The output is taken out first, so the final block the
+            // encoder writes when dropped is discarded.
+            let mut encoder = DeflateEncoder::new(GZIP_HEADER.to_vec(), Compression::default());
+            encoder.write_all(data)?;
+            encoder.flush()?;
+            std::mem::take(encoder.get_mut())
+        }
+        Encoding::Zstd => zstd::encode_all(data, ZSTD_LEVEL)?,
+    };
+    Ok(Bytes::from(compressed))
+}
+
+/// The final deflate blocks and trailer that complete a gzip member started
+/// by `compress(body, Encoding::Gzip)` with `tail`
+fn finish_gzip(body: &[u8], tail: &[u8]) -> std::io::Result<Vec<u8>> {
+    let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(tail)?;
+    let mut rest = encoder.finish()?;
+
+    let mut crc = Crc::new();
+    crc.update(body);
+    crc.update(tail);
+    rest.extend_from_slice(&crc.sum().to_le_bytes());
+    rest.extend_from_slice(&crc.amount().to_le_bytes());
+    Ok(rest)
+}
+
+fn concat(head: &[u8], tail: &[u8]) -> Bytes {
+    let mut bytes = Vec::with_capacity(head.len() + tail.len());
+    bytes.extend_from_slice(head);
+    bytes.extend_from_slice(tail);
+    Bytes::from(bytes)
+}
+
+/// Whether an `If-None-Match` value matches the current ETag. Weak
+/// comparison is used, as RFC 9110 requires for this header.
+fn etag_matches(if_none_match: &str, etag: &str) -> bool {
+    if_none_match.split(',').map(str::trim).any(|candidate| {
+        candidate == "*" || candidate.strip_prefix("W/").unwrap_or(candidate) == etag
+    })
+}
+
+/// The exposition of one collection cycle in both text formats. The
+/// OpenMetrics body is kept without its `# EOF`, which the tail supplies.
+pub struct Exposition {
+    etag: String,
+    prometheus: RenderedBody,
+    openmetrics: RenderedBody,
+}
+
+impl Exposition {
+    /// Render `builder` for the cycle identified by `data_version`
+    pub fn render(builder: MetricBuilder, data_version: u64, epoch: &str) -> Self {
+        let mut openmetrics = builder.clone().build_openmetrics();
+        openmetrics.truncate(openmetrics.len() - OPENMETRICS_EOF.len());
+        Self {
+            etag: format!("{epoch}-{data_version}"),
+            prometheus: RenderedBody::new(PROMETHEUS_CONTENT_TYPE, builder.build()),
+            openmetrics: RenderedBody::new(OPENMETRICS_CONTENT_TYPE, openmetrics),
+        }
+    }
+
+    /// Respond with the cached body followed by `tail`, rendered on request
+    pub fn respond(
+        &self,
+        format: &ExportFormat,
+        headers: &HeaderMap,
+        tail: MetricBuilder,
+    ) -> Response {
+        match format {
+            ExportFormat::OpenMetrics => self.openmetrics.respond(
+                headers,
+                Some(&format!("{}-om", self.etag)),
+                tail.build_openmetrics().as_bytes(),
+            ),
+            _ => self.prometheus.respond(
+                headers,
+                Some(&format!("{}-prom", self.etag)),
+                tail.build().as_bytes(),
+            ),
+        }
+    }
+}
+
+/// Latest rendered exposition, replaced after every collection cycle
+pub struct ExpositionCache {
+    /// Distinguishes this process's ETags from those of an earlier run,
+    /// whose `data_version` counted from the same start
+    epoch: String,
+    current: RwLock<Option<Arc<Exposition>>>,
+}
+
+impl Default for ExpositionCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ExpositionCache {
+    pub fn new() -> Self {
+        let started = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        Self {
+            epoch: format!("{:x}", started.as_micros()),
+            current: RwLock::new(None),
+        }
+    }
+
+    pub fn update(&self, builder: MetricBuilder, data_version: u64) {
+        let exposition = Exposition::render(builder, data_version, &self.epoch);
+        *self.current.write().unwrap() = Some(Arc::new(exposition));
+    }
+
+    /// The exposition of the latest cycle, `None` before the first one
+    pub fn current(&self) -> Option<Arc<Exposition>> {
+        self.current.read().unwrap().clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::to_bytes;
+    use axum::http::header::{ACCEPT_ENCODING, IF_NONE_MATCH};
+    use flate2::read::GzDecoder;
+    use std::io::Read;
+
+    fn cache() -> ExpositionCache {
+        let mut builder = MetricBuilder::new();
+        builder
+            .help("all_smi_gpu_utilization", "GPU utilization percentage")
+            .type_("all_smi_gpu_utilization", "gauge")
+            .metric("all_smi_gpu_utilization", &[("gpu", "0")], 42);
+        let cache = ExpositionCache::new();
+        cache.update(builder, 7);
+        cache
+    }
+
+    fn headers(pairs: &[(axum::http::HeaderName, &str)]) -> HeaderMap {
+        pairs
+            .iter()
+            .map(|(name, value)| (name.clone(), HeaderValue::from_str(value).unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn test_negotiate_encoding() {
+        assert_eq!(Encoding::negotiate(None), Encoding::Identity);
+        assert_eq!(Encoding::negotiate(Some("gzip")), Encoding::Gzip);
+        assert_eq!(Encoding::negotiate(Some("gzip, zstd")), Encoding::Zstd);
+        assert_eq!(Encoding::negotiate(Some("zstd;q=0, gzip")), Encoding::Gzip);
+        assert_eq!(Encoding::negotiate(Some("br, deflate")), Encoding::Identity);
+        assert_eq!(Encoding::negotiate(Some("*")), Encoding::Zstd);
+        // `*` does not override a coding the header refuses by name
+        assert_eq!(Encoding::negotiate(Some("gzip;q=0, *")), Encoding::Zstd);
+        assert_eq!(
+            Encoding::negotiate(Some("zstd;q=0, *, gzip;q=0")),
+            Encoding::Identity
+        );
+        assert_eq!(Encoding::negotiate(Some("*;q=0, gzip")), Encoding::Gzip);
+    }
+
+    fn tail(scrapes: u64) -> MetricBuilder {
+        let mut builder = MetricBuilder::new();
+        builder
+            .type_("all_smi_collector_scrapes_total", "counter")
+            .metric("all_smi_collector_scrapes_total", &[], scrapes);
+        builder
+    }
+
+    #[tokio::test]
+    async fn test_compressed_responses() {
+        let exposition = cache().current().unwrap();
+        let plain = exposition.respond(&ExportFormat::Prometheus, &HeaderMap::new(), tail(1));
+        assert!(plain.headers().get(CONTENT_ENCODING).is_none());
+        let plain = to_bytes(plain.into_body(), usize::MAX).await.unwrap();
+        let text = String::from_utf8_lossy(&plain);
+        assert!(text.starts_with("# HELP all_smi_gpu_utilization"));
+        assert!(text.ends_with("all_smi_collector_scrapes_total 1\n"));
+
+        // The tail completes a single gzip member, so a decoder that stops
+        // after the first member still sees it
+        let response = exposition.respond(
+            &ExportFormat::Prometheus,
+            &headers(&[(ACCEPT_ENCODING, "gzip")]),
+            tail(1),
+        );
+        assert_eq!(response.headers()[CONTENT_ENCODING], "gzip");
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let mut decoded = Vec::new();
+        GzDecoder::new(&body[..]).read_to_end(&mut decoded).unwrap();
+        assert_eq!(decoded, plain);
+
+        let response = exposition.respond(
+            &ExportFormat::Prometheus,
+            &headers(&[(ACCEPT_ENCODING, "zstd")]),
+            tail(1),
+        );
+        assert_eq!(response.headers()[CONTENT_ENCODING], "zstd");
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(zstd::decode_all(&body[..]).unwrap(), plain);
+
+        // The cached gzip stream is reused with a different tail
+        let response = exposition.respond(
+            &ExportFormat::Prometheus,
+            &headers(&[(ACCEPT_ENCODING, "gzip")]),
+            tail(2),
+        );
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let mut decoded = String::new();
+        GzDecoder::new(&body[..])
+            .read_to_string(&mut decoded)
+            .unwrap();
+        assert!(decoded.ends_with("all_smi_collector_scrapes_total 2\n"));
+    }
+
+    #[tokio::test]
+    async fn test_openmetrics_tail_before_eof() {
+        let exposition = cache().current().unwrap();
+        let response = exposition.respond(&ExportFormat::OpenMetrics, &HeaderMap::new(), tail(1));
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let text = String::from_utf8_lossy(&body);
+        assert_eq!(text.matches(OPENMETRICS_EOF).count(), 1);
+        assert!(text.ends_with("all_smi_collector_scrapes_total 1\n# EOF\n"));
+    }
+
+    #[tokio::test]
+    async fn test_etag_revalidation() {
+        let cache = cache();
+        let exposition = cache.current().unwrap();
+        let response = exposition.respond(&ExportFormat::OpenMetrics, &HeaderMap::new(), tail(1));
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[CONTENT_TYPE], OPENMETRICS_CONTENT_TYPE);
+        let etag = response.headers()[ETAG].to_str().unwrap().to_string();
+        assert!(etag.contains("-7-om-identity"));
+
+        // The tail is not part of the ETag
+        let response = exposition.respond(
+            &ExportFormat::OpenMetrics,
+            &headers(&[(IF_NONE_MATCH, &format!("\"other\", W/{etag}"))]),
+            tail(2),
+        );
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+        assert!(to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap()
+            .is_empty());
+
+        // The other format and the next cycle have different tags
+        let response = exposition.respond(
+            &ExportFormat::Prometheus,
+            &headers(&[(IF_NONE_MATCH, &etag)]),
+            tail(3),
+        );
+        assert_eq!(response.status(), StatusCode::OK);
+        cache.update(MetricBuilder::new(), 8);
+        let response = cache.current().unwrap().respond(
+            &ExportFormat::OpenMetrics,
+            &headers(&[(IF_NONE_MATCH, &etag)]),
+            tail(3),
+        );
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+}
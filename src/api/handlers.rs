@@ -12,68 +12,189 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use axum::extract::State;
+use all_smi::traits::ExportFormat;
+use axum::extract::{FromRef, RawQuery, State};
+use axum::http::header::ACCEPT;
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use std::time::Instant;
+use tokio::sync::{watch, RwLock};
 
 use crate::app_state::AppState;
 
+use super::exposition::{RenderedBody, SharedExposition};
+use super::health::SharedHealth;
+use super::self_metrics::SharedSelfMetrics;
+
 use super::metrics::{
-    chassis::ChassisMetricExporter, cpu::CpuMetricExporter, disk::DiskMetricExporter,
-    gpu::GpuMetricExporter, memory::MemoryMetricExporter, npu::NpuMetricExporter,
-    process::ProcessMetricExporter, runtime::RuntimeMetricExporter, MetricExporter,
+    chassis::ChassisMetricExporter,
+    cpu::CpuMetricExporter,
+    disk::DiskMetricExporter,
+    filter::{ExporterGroup, MetricFilter},
+    gpu::GpuMetricExporter,
+    memory::MemoryMetricExporter,
+    npu::NpuMetricExporter,
+    openmetrics::{negotiate_format, OPENMETRICS_CONTENT_TYPE, PROMETHEUS_CONTENT_TYPE},
+    process::ProcessMetricExporter,
+    reader::ReaderMetricExporter,
+    runtime::RuntimeMetricExporter,
+    ExportOptions, MetricBuilder, MetricExporter, SharedExportOptions,
 };
 
 pub type SharedState = Arc<RwLock<AppState>>;
 
-pub async fn metrics_handler(State(state): State<SharedState>) -> String {
-    let state = state.read().await;
-    let mut all_metrics = String::new();
+/// Router state for API mode.
+/// Handlers that only need the cached data keep extracting `State<SharedState>`.
+#[derive(Clone)]
+pub struct ApiState {
+    pub app_state: SharedState,
+    /// Carries the `data_version` of the last completed collection cycle
+    pub updates: watch::Receiver<u64>,
+    /// Per-reader collection status for the probe endpoints
+    pub health: SharedHealth,
+    /// Collection and scrape statistics of the exporter itself
+    pub self_metrics: SharedSelfMetrics,
+    /// `/metrics` rendered after the last collection cycle
+    pub exposition: SharedExposition,
+    /// Exporter settings for scrapes rendered on request
+    pub export_options: SharedExportOptions,
+}
+
+impl FromRef<ApiState> for SharedState {
+    fn from_ref(state: &ApiState) -> Self {
+        state.app_state.clone()
+    }
+}
+
+impl FromRef<ApiState> for SharedExposition {
+    fn from_ref(state: &ApiState) -> Self {
+        state.exposition.clone()
+    }
+}
+
+impl FromRef<ApiState> for SharedExportOptions {
+    fn from_ref(state: &ApiState) -> Self {
+        state.export_options.clone()
+    }
+}
+
+impl FromRef<ApiState> for SharedSelfMetrics {
+    fn from_ref(state: &ApiState) -> Self {
+        state.self_metrics.clone()
+    }
+}
+
+pub async fn metrics_handler(
+    State(state): State<SharedState>,
+    State(self_metrics): State<SharedSelfMetrics>,
+    State(exposition): State<SharedExposition>,
+    State(options): State<SharedExportOptions>,
+    RawQuery(query): RawQuery,
+    headers: HeaderMap,
+) -> Response {
+    let started = Instant::now();
+    let filter = match MetricFilter::from_query(query.as_deref().unwrap_or_default()) {
+        Ok(filter) => filter,
+        Err(e) => return (StatusCode::BAD_REQUEST, format!("{e}\n")).into_response(),
+    };
+    let format = negotiate_format(headers.get(ACCEPT).and_then(|v| v.to_str().ok()));
+
+    let response = match exposition.current() {
+        // Full scrapes are served from the copy rendered after the last cycle,
+        // followed by the self-metrics as they stand now
+        Some(current) if filter.selects_everything() => {
+            let mut tail = MetricBuilder::new();
+            tail.const_labels(&state.read().await.node_labels);
+            self_metrics.export_to(&mut tail);
+            current.respond(&format, &headers, tail)
+        }
+        _ => {
+            let mut builder =
+                collect_filtered_metrics(&*state.read().await, &*options.read().await, &filter);
+            if filter.wants(ExporterGroup::Collector) {
+                self_metrics.export_to(&mut builder);
+            }
+            if filter.has_name_patterns() {
+                builder.retain_families(|name| filter.allows(name));
+            }
+            let body = match format {
+                ExportFormat::OpenMetrics => {
+                    RenderedBody::new(OPENMETRICS_CONTENT_TYPE, builder.build_openmetrics())
+                }
+                _ => RenderedBody::new(PROMETHEUS_CONTENT_TYPE, builder.build()),
+            };
+            body.respond(&headers, None, &[])
+        }
+    };
+    self_metrics.record_scrape(started.elapsed());
+    response
+}
+
+/// Run every exporter over the cached data into a single family registry
+pub fn collect_metrics(state: &AppState, options: &ExportOptions) -> MetricBuilder {
+    collect_filtered_metrics(state, options, &MetricFilter::default())
+}
+
+/// Run the exporters of the groups selected by `filter`. Name patterns are
+/// applied by the caller once all families are registered.
+pub fn collect_filtered_metrics(
+    state: &AppState,
+    options: &ExportOptions,
+    filter: &MetricFilter,
+) -> MetricBuilder {
+    let mut builder = MetricBuilder::new();
+    builder.const_labels(&state.node_labels);
 
     // Export GPU/NPU metrics
     if !state.gpu_info.is_empty() {
-        // Export GPU/NPU metrics together since the exporters handle filtering
-        let gpu_exporter = GpuMetricExporter::new(&state.gpu_info);
-        all_metrics.push_str(&gpu_exporter.export_metrics());
-
-        let npu_exporter = NpuMetricExporter::new(&state.gpu_info);
-        all_metrics.push_str(&npu_exporter.export_metrics());
+        // The GPU and NPU exporters each pick their own devices
+        if filter.wants(ExporterGroup::Gpu) {
+            GpuMetricExporter::new(&state.gpu_info).export_to(&mut builder);
+        }
+        if filter.wants(ExporterGroup::Npu) {
+            NpuMetricExporter::new(&state.gpu_info).export_to(&mut builder);
+        }
     }
 
     // Export process metrics
-    if !state.process_info.is_empty() {
-        let process_exporter = ProcessMetricExporter::new(&state.process_info);
-        all_metrics.push_str(&process_exporter.export_metrics());
+    if !state.process_info.is_empty() && filter.wants(ExporterGroup::Process) {
+        ProcessMetricExporter::new(&state.process_info, &options.process).export_to(&mut builder);
     }
 
     // Export CPU metrics
-    if !state.cpu_info.is_empty() {
-        let cpu_exporter = CpuMetricExporter::new(&state.cpu_info);
-        all_metrics.push_str(&cpu_exporter.export_metrics());
+    if !state.cpu_info.is_empty() && filter.wants(ExporterGroup::Cpu) {
+        CpuMetricExporter::new(&state.cpu_info).export_to(&mut builder);
     }
 
     // Export memory metrics
-    if !state.memory_info.is_empty() {
-        let memory_exporter = MemoryMetricExporter::new(&state.memory_info);
-        all_metrics.push_str(&memory_exporter.export_metrics());
+    if !state.memory_info.is_empty() && filter.wants(ExporterGroup::Memory) {
+        MemoryMetricExporter::new(&state.memory_info).export_to(&mut builder);
     }
 
     // Export disk metrics from cached storage_info
     // This uses pre-collected data from the background task instead of collecting on each request
-    if !state.storage_info.is_empty() {
-        let disk_exporter = DiskMetricExporter::new(&state.storage_info);
-        all_metrics.push_str(&disk_exporter.export_metrics());
+    if !state.storage_info.is_empty() && filter.wants(ExporterGroup::Disk) {
+        DiskMetricExporter::new(&state.storage_info).export_to(&mut builder);
     }
 
     // Export runtime environment metrics
-    let runtime_exporter = RuntimeMetricExporter::new(&state.runtime_environment);
-    all_metrics.push_str(&runtime_exporter.export_metrics());
+    if filter.wants(ExporterGroup::Runtime) {
+        RuntimeMetricExporter::new(&state.runtime_environment).export_to(&mut builder);
+    }
 
     // Export chassis metrics
-    if !state.chassis_info.is_empty() {
-        let chassis_exporter = ChassisMetricExporter::new(&state.chassis_info);
-        all_metrics.push_str(&chassis_exporter.export_metrics());
+    if !state.chassis_info.is_empty() && filter.wants(ExporterGroup::Chassis) {
+        ChassisMetricExporter::new(&state.chassis_info).export_to(&mut builder);
     }
 
-    all_metrics
+    // Export per-reader collection status
+    if filter.wants(ExporterGroup::Collector) {
+        ReaderMetricExporter::new(&state.reader_status).export_to(&mut builder);
+    }
+
+    builder
 }
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,720 @@
+// Copyright 2025 Lablup Inc. and Jeongkyu Shin
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Exposition conformance tests for `metrics_handler`.
+//!
+//! The full handler output is run through a strict parser that enforces the
+//! rules promtool and the Prometheus/OpenMetrics parsers apply: one HELP/TYPE
+//! per family, contiguous families, valid names, escapes and values, and no
+//! duplicate series.
+
+use super::*;
+use crate::app_state::ReaderStatus;
+use crate::device::{ChassisInfo, CpuInfo, CpuPlatformType, FanInfo, GpuInfo, MemoryInfo};
+use crate::device::{CoreType, CoreUtilization, CpuSocketInfo, ProcessInfo};
+use crate::storage::info::StorageInfo;
+use axum::body::to_bytes;
+use axum::http::header::{CONTENT_TYPE, ETAG};
+use axum::http::HeaderValue;
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+
+const DETAIL_KEYS: &[&str] = &[
+    "aiclk_mhz",
+    "arc0_health",
+    "arc3_health",
+    "arc_fw_version",
+    "arcclk_mhz",
+    "asic_temperature",
+    "axiclk_mhz",
+    "board_type",
+    "chip_name",
+    "clock_graphics_max",
+    "clock_memory_max",
+    "collection_method",
+    "combined_power_mw",
+    "compute_utilization",
+    "ddr_fw_version",
+    "ddr_status",
+    "device_name",
+    "dram_speed",
+    "driver_version",
+    "eth_fw_version",
+    "eth_status0",
+    "eth_status1",
+    "fan_rpm",
+    "fan_speed",
+    "faults",
+    "firmware",
+    "firmware_version",
+    "fw_date",
+    "heartbeat",
+    "inlet_temperature",
+    "kmd_version",
+    "lib_version",
+    "memory_clock_mhz",
+    "memory_total",
+    "memory_used",
+    "outlet_temperature1",
+    "outlet_temperature2",
+    "pcie_address",
+    "pcie_device_id",
+    "pcie_gen_current",
+    "pcie_link_gen",
+    "pcie_link_width",
+    "pcie_status",
+    "pcie_vendor_id",
+    "pcie_width_current",
+    "power_draw",
+    "power_limit_current",
+    "power_limit_max",
+    "power_limit_tdc",
+    "power_limit_tdp",
+    "ready",
+    "serial_id",
+    "spibootrom_fw_version",
+    "status",
+    "tdc_limit",
+    "tdp_limit",
+    "thermal_limit",
+    "throttler",
+    "voltage",
+    "vreg_temperature",
+];
+
+fn device(name: &str, device_type: &str, index: usize) -> GpuInfo {
+    let mut detail: HashMap<String, String> = DETAIL_KEYS
+        .iter()
+        .map(|k| (k.to_string(), "1".to_string()))
+        .collect();
+    detail.insert("performance_state".to_string(), "P0".to_string());
+    detail.insert("thermal_pressure".to_string(), "Nominal".to_string());
+    detail.insert("index".to_string(), index.to_string());
+    detail.insert("lib_name".to_string(), "CUDA \"12\"\\x".to_string());
+
+    GpuInfo {
+        uuid: format!("{name}-{index}"),
+        time: "2025-01-01 00:00:00".to_string(),
+        name: name.to_string(),
+        device_type: device_type.to_string(),
+        host_id: "node-1".to_string(),
+        hostname: "node-1".to_string(),
+        instance: "node-1".to_string(),
+        utilization: 50.0,
+        ane_utilization: 0.0,
+        dla_utilization: Some(1.0),
+        tensorcore_utilization: Some(2.0),
+        temperature: 40,
+        used_memory: 1024,
+        total_memory: 4096,
+        frequency: 1500,
+        power_consumption: 200.0,
+        gpu_core_count: Some(10),
+        detail,
+    }
+}
+
+fn cpu(index: usize) -> CpuInfo {
+    CpuInfo {
+        host_id: "node-1".to_string(),
+        hostname: "node-1".to_string(),
+        instance: "node-1".to_string(),
+        cpu_model: format!("Test CPU {index}"),
+        architecture: "x86_64".to_string(),
+        platform_type: CpuPlatformType::Intel,
+        socket_count: 2,
+        total_cores: 4,
+        total_threads: 8,
+        base_frequency_mhz: 2000,
+        max_frequency_mhz: 3000,
+        cache_size_mb: 32,
+        utilization: 10.0,
+        temperature: Some(50),
+        power_consumption: Some(80.0),
+        per_socket_info: (0..2)
+            .map(|socket_id| CpuSocketInfo {
+                socket_id,
+                utilization: 10.0,
+                cores: 2,
+                threads: 4,
+                temperature: Some(50),
+                frequency_mhz: 2500,
+            })
+            .collect(),
+        apple_silicon_info: None,
+        per_core_utilization: (0..4)
+            .map(|core_id| CoreUtilization {
+                core_id,
+                core_type: CoreType::Standard,
+                utilization: 5.0,
+            })
+            .collect(),
+        time: "2025-01-01 00:00:00".to_string(),
+    }
+}
+
+fn memory() -> MemoryInfo {
+    MemoryInfo {
+        host_id: "node-1".to_string(),
+        hostname: "node-1".to_string(),
+        instance: "node-1".to_string(),
+        total_bytes: 100,
+        used_bytes: 50,
+        available_bytes: 50,
+        free_bytes: 40,
+        buffers_bytes: 5,
+        cached_bytes: 5,
+        swap_total_bytes: 10,
+        swap_used_bytes: 1,
+        swap_free_bytes: 9,
+        utilization: 50.0,
+        time: "2025-01-01 00:00:00".to_string(),
+    }
+}
+
+fn process(pid: u32, device_id: usize) -> ProcessInfo {
+    ProcessInfo {
+        host_id: String::new(),
+        device_id,
+        device_uuid: format!("NVIDIA H100-{device_id}"),
+        pid,
+        process_name: "python".to_string(),
+        used_memory: 1024,
+        cpu_percent: 1.0,
+        memory_percent: 1.0,
+        memory_rss: 1024,
+        memory_vms: 2048,
+        user: "user".to_string(),
+        state: "R".to_string(),
+        start_time: "00:00".to_string(),
+        cpu_time: 1,
+        command: "python train.py".to_string(),
+        ppid: 1,
+        threads: 4,
+        uses_gpu: true,
+        priority: 20,
+        nice_value: 0,
+        gpu_utilization: 10.0,
+    }
+}
+
+fn populated_state() -> SharedState {
+    let mut state = AppState::new();
+    let names = [
+        ("NVIDIA H100", "GPU"),
+        ("Apple M2 Max", "GPU"),
+        ("Tenstorrent Wormhole n300", "NPU"),
+        ("Rebellions ATOM", "NPU"),
+        ("Furiosa RNGD", "NPU"),
+        ("Intel Gaudi 3 HL-325L", "NPU"),
+        ("Google TPU v5e", "TPU"),
+    ];
+    for (name, device_type) in names {
+        for _ in 0..2 {
+            let index = state.gpu_info.len();
+            state.gpu_info.push(device(name, device_type, index));
+        }
+    }
+    state.cpu_info = vec![cpu(0), cpu(1)];
+    state.memory_info = vec![memory()];
+    state.process_info = vec![process(100, 0), process(101, 1)];
+    state.storage_info = (0..2)
+        .map(|index| StorageInfo {
+            mount_point: format!("/mnt/{index}"),
+            total_bytes: 100,
+            available_bytes: 50,
+            host_id: "node-1".to_string(),
+            hostname: "node-1".to_string(),
+            index,
+        })
+        .collect();
+    state.chassis_info = vec![ChassisInfo {
+        host_id: "node-1".to_string(),
+        hostname: "node-1".to_string(),
+        instance: "node-1".to_string(),
+        total_power_watts: Some(500.0),
+        inlet_temperature: Some(20.0),
+        outlet_temperature: Some(30.0),
+        thermal_pressure: Some("Nominal".to_string()),
+        fan_speeds: (0..2)
+            .map(|id| FanInfo {
+                id,
+                name: format!("fan{id}"),
+                speed_rpm: 1000,
+                max_rpm: 2000,
+            })
+            .collect(),
+        detail: [
+            ("cpu_power_watts", "100"),
+            ("gpu_power_watts", "300"),
+            ("ane_power_watts", "1"),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect(),
+        ..Default::default()
+    }];
+    state.reader_status = reader_status();
+    Arc::new(RwLock::new(state))
+}
+
+fn reader_status() -> Vec<ReaderStatus> {
+    [
+        ("gpu", "NvidiaGpuReader", 8, true),
+        ("cpu", "LinuxCpuReader", 1, false),
+    ]
+    .into_iter()
+    .map(|(kind, name, device_count, stale)| ReaderStatus {
+        kind,
+        name,
+        device_count,
+        stale,
+        data_age: Some(Duration::from_millis(1500)),
+        timeouts_total: u64::from(stale),
+        call_duration: Some(Duration::from_millis(40)),
+    })
+    .collect()
+}
+
+fn self_metrics() -> SharedSelfMetrics {
+    let self_metrics = SharedSelfMetrics::default();
+    self_metrics.record_cycle(&reader_status());
+    self_metrics.record_scrape(Duration::from_millis(3));
+    self_metrics
+}
+
+async fn scrape(state: SharedState, accept: Option<&str>) -> (String, String) {
+    scrape_query(state, accept, None).await
+}
+
+async fn scrape_query(
+    state: SharedState,
+    accept: Option<&str>,
+    query: Option<&str>,
+) -> (String, String) {
+    let mut headers = HeaderMap::new();
+    if let Some(accept) = accept {
+        headers.insert(ACCEPT, HeaderValue::from_str(accept).unwrap());
+    }
+    let query = RawQuery(query.map(str::to_string));
+    let response = metrics_handler(
+        State(state),
+        State(self_metrics()),
+        State(SharedExposition::default()),
+        State(SharedExportOptions::default()),
+        query,
+        headers,
+    )
+    .await;
+    let content_type = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default()
+        .to_string();
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    (content_type, String::from_utf8(body.to_vec()).unwrap())
+}
+
+fn is_metric_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_' || c == ':')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == ':')
+}
+
+fn is_label_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && !name.starts_with("__")
+}
+
+fn is_value(value: &str) -> bool {
+    matches!(value, "+Inf" | "-Inf" | "NaN")
+        || (value
+            .chars()
+            .all(|c| c.is_ascii_digit() || matches!(c, '.' | '-' | '+' | 'e' | 'E'))
+            && value.parse::<f64>().is_ok())
+}
+
+/// Allowed sample name suffixes for a family type
+fn sample_suffixes(metric_type: &str, openmetrics: bool) -> &'static [&'static str] {
+    match (metric_type, openmetrics) {
+        ("counter", true) => &["_total", "_created"],
+        ("counter", false) => &["", "_total"],
+        ("histogram", _) => &["_bucket", "_count", "_sum", "_created"],
+        ("summary", _) => &["", "_count", "_sum", "_created"],
+        _ => &[""],
+    }
+}
+
+struct ParsedSample {
+    name: String,
+    labels: Vec<(String, String)>,
+}
+
+fn parse_sample_line(line: &str, openmetrics: bool) -> Result<ParsedSample, String> {
+    let name_end = line
+        .find(['{', ' '])
+        .ok_or_else(|| format!("no value in sample: {line}"))?;
+    let name = &line[..name_end];
+    if !is_metric_name(name) {
+        return Err(format!("invalid metric name: {name}"));
+    }
+
+    let mut rest = &line[name_end..];
+    let mut labels = Vec::new();
+
+    if let Some(label_str) = rest.strip_prefix('{') {
+        let mut chars = label_str.char_indices().peekable();
+        loop {
+            // label name
+            let mut key = String::new();
+            while let Some(&(_, c)) = chars.peek() {
+                if c == '=' || c == '}' {
+                    break;
+                }
+                key.push(c);
+                chars.next();
+            }
+            if let Some(&(i, '}')) = chars.peek() {
+                if !key.trim().is_empty() {
+                    return Err(format!("label without value in: {line}"));
+                }
+                rest = &label_str[i + 1..];
+                break;
+            }
+            let key = if openmetrics {
+                key
+            } else {
+                key.trim().to_string()
+            };
+            if !is_label_name(&key) {
+                return Err(format!("invalid label name '{key}' in: {line}"));
+            }
+            if chars.next().map(|(_, c)| c) != Some('=')
+                || chars.next().map(|(_, c)| c) != Some('"')
+            {
+                return Err(format!("malformed label '{key}' in: {line}"));
+            }
+
+            // label value
+            let mut value = String::new();
+            loop {
+                match chars.next() {
+                    Some((_, '\\')) => match chars.next() {
+                        Some((_, '\\')) => value.push('\\'),
+                        Some((_, '"')) => value.push('"'),
+                        Some((_, 'n')) => value.push('\n'),
+                        other => return Err(format!("invalid escape {other:?} in: {line}")),
+                    },
+                    Some((_, '"')) => break,
+                    Some((_, '\n')) | None => {
+                        return Err(format!("unterminated label value in: {line}"))
+                    }
+                    Some((_, c)) => value.push(c),
+                }
+            }
+            if labels.iter().any(|(k, _)| k == &key) {
+                return Err(format!("duplicate label '{key}' in: {line}"));
+            }
+            labels.push((key, value));
+
+            match chars.next() {
+                Some((i, '}')) => {
+                    rest = &label_str[i + 1..];
+                    break;
+                }
+                Some((_, ',')) => {
+                    if !openmetrics {
+                        while chars.peek().is_some_and(|(_, c)| *c == ' ') {
+                            chars.next();
+                        }
+                    }
+                }
+                other => return Err(format!("unexpected {other:?} after label in: {line}")),
+            }
+        }
+    }
+
+    let value = rest
+        .strip_prefix(' ')
+        .ok_or_else(|| format!("missing space before value in: {line}"))?;
+    if value.contains(' ') || !is_value(value) {
+        return Err(format!("invalid sample value '{value}' in: {line}"));
+    }
+
+    Ok(ParsedSample {
+        name: name.to_string(),
+        labels,
+    })
+}
+
+/// Validate an exposition and return the number of samples
+fn validate_exposition(text: &str, openmetrics: bool) -> Result<usize, String> {
+    let mut types: HashMap<String, String> = HashMap::new();
+    let mut helps: HashSet<String> = HashSet::new();
+    let mut units: HashSet<String> = HashSet::new();
+    let mut closed: HashSet<String> = HashSet::new();
+    let mut with_samples: HashSet<String> = HashSet::new();
+    let mut series: HashSet<String> = HashSet::new();
+    let mut current: Option<String> = None;
+    let mut samples = 0;
+    let mut saw_eof = false;
+
+    let mut enter = |family: &str, current: &mut Option<String>| -> Result<(), String> {
+        if current.as_deref() != Some(family) {
+            if closed.contains(family) {
+                return Err(format!("family {family} is not contiguous"));
+            }
+            if let Some(previous) = current.take() {
+                closed.insert(previous);
+            }
+            *current = Some(family.to_string());
+        }
+        Ok(())
+    };
+
+    for line in text.split_terminator('\n') {
+        if saw_eof {
+            return Err("content after # EOF".to_string());
+        }
+        if line.is_empty() {
+            if openmetrics {
+                return Err("empty line in OpenMetrics output".to_string());
+            }
+            continue;
+        }
+
+        if let Some(comment) = line.strip_prefix('#') {
+            if openmetrics && comment == " EOF" {
+                saw_eof = true;
+                continue;
+            }
+            let mut parts = comment.trim_start().splitn(3, ' ');
+            let keyword = parts.next().unwrap_or_default();
+            let family = parts.next().unwrap_or_default();
+            let rest = parts.next().unwrap_or_default();
+            match keyword {
+                "HELP" | "TYPE" | "UNIT" => {
+                    if !is_metric_name(family) {
+                        return Err(format!("invalid family name in: {line}"));
+                    }
+                    if with_samples.contains(family) {
+                        return Err(format!("{keyword} after samples for {family}"));
+                    }
+                    enter(family, &mut current)?;
+                }
+                _ if openmetrics => return Err(format!("unknown comment: {line}")),
+                _ => continue,
+            }
+            match keyword {
+                "HELP" => {
+                    if !helps.insert(family.to_string()) {
+                        return Err(format!("duplicate HELP for {family}"));
+                    }
+                }
+                "TYPE" => {
+                    let allowed: &[&str] = if openmetrics {
+                        &[
+                            "counter",
+                            "gauge",
+                            "histogram",
+                            "gaugehistogram",
+                            "stateset",
+                            "info",
+                            "summary",
+                            "unknown",
+                        ]
+                    } else {
+                        &["counter", "gauge", "histogram", "summary", "untyped"]
+                    };
+                    if !allowed.contains(&rest) {
+                        return Err(format!("invalid TYPE in: {line}"));
+                    }
+                    if types.insert(family.to_string(), rest.to_string()).is_some() {
+                        return Err(format!("duplicate TYPE for {family}"));
+                    }
+                }
+                _ => {
+                    if !openmetrics {
+                        return Err(format!("UNIT in Prometheus text: {line}"));
+                    }
+                    if !family.ends_with(&format!("_{rest}")) || !units.insert(family.into()) {
+                        return Err(format!("invalid UNIT in: {line}"));
+                    }
+                }
+            }
+            continue;
+        }
+
+        let sample = parse_sample_line(line, openmetrics)?;
+
+        // Resolve the family this sample belongs to
+        let family = match current.as_deref() {
+            Some(family)
+                if sample_suffixes(
+                    types.get(family).map(String::as_str).unwrap_or("unknown"),
+                    openmetrics,
+                )
+                .iter()
+                .any(|suffix| sample.name == format!("{family}{suffix}")) =>
+            {
+                family.to_string()
+            }
+            _ => {
+                if types.contains_key(&sample.name) || helps.contains(&sample.name) {
+                    return Err(format!("sample {} outside its family block", sample.name));
+                }
+                if openmetrics {
+                    return Err(format!("sample {} without TYPE", sample.name));
+                }
+                sample.name.clone()
+            }
+        };
+        enter(&family, &mut current)?;
+        with_samples.insert(family);
+
+        let mut labels = sample.labels.clone();
+        labels.sort();
+        if !series.insert(format!("{}{labels:?}", sample.name)) {
+            return Err(format!("duplicate series: {line}"));
+        }
+        samples += 1;
+    }
+
+    if openmetrics && !saw_eof {
+        return Err("missing # EOF".to_string());
+    }
+    Ok(samples)
+}
+
+#[tokio::test]
+async fn test_prometheus_exposition_conformance() {
+    let (content_type, body) = scrape(populated_state(), None).await;
+    assert_eq!(content_type, PROMETHEUS_CONTENT_TYPE);
+
+    let samples = validate_exposition(&body, false).unwrap_or_else(|e| panic!("{e}\n{body}"));
+    assert!(samples > 100, "expected a full exposition, got {samples}");
+    assert_eq!(body.matches("# HELP all_smi_gpu_utilization ").count(), 1);
+    assert_eq!(body.matches("# TYPE all_smi_gpu_utilization ").count(), 1);
+}
+
+#[tokio::test]
+async fn test_openmetrics_exposition_conformance() {
+    let (content_type, body) =
+        scrape(populated_state(), Some("application/openmetrics-text")).await;
+    assert_eq!(content_type, OPENMETRICS_CONTENT_TYPE);
+
+    let samples = validate_exposition(&body, true).unwrap_or_else(|e| panic!("{e}\n{body}"));
+    assert!(samples > 100, "expected a full exposition, got {samples}");
+}
+
+#[tokio::test]
+async fn test_collect_groups_and_name_patterns() {
+    let (_, body) =
+        scrape_query(populated_state(), None, Some("collect[]=gpu&collect[]=cpu")).await;
+    validate_exposition(&body, false).unwrap_or_else(|e| panic!("{e}\n{body}"));
+    assert!(body.contains("all_smi_gpu_utilization{"));
+    assert!(body.contains("all_smi_cpu_utilization{"));
+    assert!(!body.contains("all_smi_memory_"));
+    assert!(!body.contains("all_smi_gpu_process_"));
+    assert!(!body.contains("all_smi_tenstorrent_"));
+    assert!(!body.contains("all_smi_collector_"));
+
+    let query = "include[]=all_smi_(gpu|cpu)_.*&exclude[]=.*_info";
+    let (_, body) = scrape_query(populated_state(), None, Some(query)).await;
+    validate_exposition(&body, false).unwrap_or_else(|e| panic!("{e}\n{body}"));
+    assert!(body.contains("all_smi_gpu_utilization{"));
+    assert!(!body.contains("all_smi_gpu_info{"));
+    assert!(!body.contains("all_smi_memory_"));
+}
+
+#[tokio::test]
+async fn test_invalid_filter_is_rejected() {
+    for query in ["collect[]=gpus", "include[]=("] {
+        let response = metrics_handler(
+            State(populated_state()),
+            State(self_metrics()),
+            State(SharedExposition::default()),
+            State(SharedExportOptions::default()),
+            RawQuery(Some(query.to_string())),
+            HeaderMap::new(),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+}
+
+#[tokio::test]
+async fn test_full_scrape_served_from_exposition() {
+    let state = populated_state();
+    let exposition = SharedExposition::default();
+    exposition.update(
+        collect_metrics(&*state.read().await, &ExportOptions::default()),
+        3,
+    );
+
+    let scrape = |query: Option<&str>| {
+        metrics_handler(
+            State(state.clone()),
+            State(self_metrics()),
+            State(exposition.clone()),
+            State(SharedExportOptions::default()),
+            RawQuery(query.map(str::to_string)),
+            HeaderMap::new(),
+        )
+    };
+
+    let response = scrape(None).await;
+    assert!(response.headers().contains_key(ETAG));
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let body = String::from_utf8_lossy(&body);
+    assert!(body.contains("all_smi_gpu_utilization{"));
+    assert!(body.contains("all_smi_reader_stale{"));
+    // Self-metrics are appended to the cached copy on every scrape
+    assert!(body.contains("all_smi_collector_scrapes_total{"));
+    validate_exposition(&body, false).unwrap_or_else(|e| panic!("{e}\n{body}"));
+
+    // Filtered scrapes are rendered per request and carry no ETag
+    let response = scrape(Some("collect[]=cpu")).await;
+    assert!(!response.headers().contains_key(ETAG));
+
+    let response = scrape(Some("collect[]=collector")).await;
+    assert!(!response.headers().contains_key(ETAG));
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let body = String::from_utf8_lossy(&body);
+    assert!(body.contains("all_smi_collector_scrapes_total{"));
+    assert!(body.contains("all_smi_reader_stale{"));
+}
+
+#[test]
+fn test_validator_rejects_repeated_metadata() {
+    let text = "\
+# HELP m help
+# TYPE m gauge
+m{a=\"1\"} 1
+# HELP m help
+# TYPE m gauge
+m{a=\"2\"} 1
+";
+    assert!(validate_exposition(text, false).is_err());
+}
+
+#[test]
+fn test_validator_rejects_bad_escape_and_duplicates() {
+    assert!(validate_exposition("m{a=\"\\x\"} 1\n", false).is_err());
+    assert!(validate_exposition("m{a=\"1\"} 1\nm{a=\"1\"} 2\n", false).is_err());
+    assert!(validate_exposition("m{a=\"1\"} inf\n", false).is_err());
+}
@@ -0,0 +1,382 @@
+// Copyright 2025 Lablup Inc. and Jeongkyu Shin
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Liveness and readiness probes
+//!
+//! The collection loop records, for every reader, how many devices it
+//! returned each cycle. `/healthz` fails when the loop itself has not
+//! completed a cycle within the staleness threshold; `/readyz` additionally
+//! waits for the first cycle and fails while any reader has gone without data
+//! for longer than the threshold.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tokio::sync::RwLock;
+
+use super::handlers::ApiState;
+use crate::app_state::ReaderStatus;
+
+pub type SharedHealth = Arc<RwLock<CollectorHealth>>;
+
+/// Smallest staleness threshold used when none is configured
+const MIN_STALE_AFTER_SECS: u64 = 30;
+
+/// Default staleness threshold: ten missed cycles, but at least 30 seconds
+pub fn default_stale_after(interval_secs: u64) -> Duration {
+    Duration::from_secs(interval_secs.saturating_mul(10).max(MIN_STALE_AFTER_SECS))
+}
+
+struct ReaderHealth {
+    kind: &'static str,
+    name: &'static str,
+    device_count: usize,
+    last_success: Option<(Instant, DateTime<Utc>)>,
+    last_error: Option<String>,
+}
+
+pub struct CollectorHealth {
+    stale_after: Duration,
+    started: Instant,
+    cycles: u64,
+    last_cycle: Option<(Instant, DateTime<Utc>)>,
+    readers: Vec<ReaderHealth>,
+}
+
+impl CollectorHealth {
+    pub fn new(stale_after: Duration) -> Self {
+        Self {
+            stale_after,
+            started: Instant::now(),
+            cycles: 0,
+            last_cycle: None,
+            readers: Vec::new(),
+        }
+    }
+
+    /// Record the outcome of one reader in the current cycle. A reader that
+    /// returns nothing or misses its deadline is counted as failing.
+    pub fn record(&mut self, status: &ReaderStatus) {
+        let idx = match self
+            .readers
+            .iter()
+            .position(|r| r.kind == status.kind && r.name == status.name)
+        {
+            Some(idx) => idx,
+            None => {
+                self.readers.push(ReaderHealth {
+                    kind: status.kind,
+                    name: status.name,
+                    device_count: 0,
+                    last_success: None,
+                    last_error: None,
+                });
+                self.readers.len() - 1
+            }
+        };
+
+        let reader = &mut self.readers[idx];
+        reader.device_count = status.device_count;
+        if status.stale {
+            reader.last_error = Some("reader missed its deadline".to_string());
+        } else if status.device_count > 0 {
+            reader.last_success = Some((Instant::now(), Utc::now()));
+            reader.last_error = None;
+        } else {
+            reader.last_error = Some("reader returned no data".to_string());
+        }
+    }
+
+    /// Forget readers missing from this cycle's status, such as those a
+    /// reload disabled, so they do not go stale and block readiness
+    pub fn retain_readers(&mut self, reader_status: &[ReaderStatus]) {
+        self.readers.retain(|reader| {
+            reader_status
+                .iter()
+                .any(|status| status.kind == reader.kind && status.name == reader.name)
+        });
+    }
+
+    /// Mark the end of a collection cycle
+    pub fn finish_cycle(&mut self) {
+        self.cycles += 1;
+        self.last_cycle = Some((Instant::now(), Utc::now()));
+    }
+
+    /// Whether the collection loop has gone longer than the threshold without
+    /// completing a cycle (counting from startup before the first one)
+    pub fn is_stale(&self, now: Instant) -> bool {
+        let since = self.last_cycle.map_or(self.started, |(at, _)| at);
+        now.saturating_duration_since(since) > self.stale_after
+    }
+
+    fn reader_is_stale(&self, reader: &ReaderHealth, now: Instant) -> bool {
+        let since = reader.last_success.map_or(self.started, |(at, _)| at);
+        now.saturating_duration_since(since) > self.stale_after
+    }
+
+    pub fn report(&self, now: Instant) -> HealthReport {
+        let stale = self.is_stale(now);
+        let readers: Vec<ReaderReport> = self
+            .readers
+            .iter()
+            .map(|reader| {
+                let status = if self.reader_is_stale(reader, now) {
+                    "stale"
+                } else if reader.last_error.is_some() {
+                    "degraded"
+                } else {
+                    "ok"
+                };
+                ReaderReport {
+                    kind: reader.kind,
+                    name: reader.name,
+                    status,
+                    device_count: reader.device_count,
+                    last_success: reader.last_success.map(|(_, at)| at.to_rfc3339()),
+                    last_error: reader.last_error.clone(),
+                }
+            })
+            .collect();
+
+        let live = !stale;
+        let ready = live && self.cycles > 0 && readers.iter().all(|r| r.status != "stale");
+        let status = if stale {
+            "stale"
+        } else if self.cycles == 0 {
+            "starting"
+        } else if !ready || readers.iter().any(|r| r.status != "ok") {
+            "degraded"
+        } else {
+            "ok"
+        };
+
+        HealthReport {
+            status,
+            live,
+            ready,
+            cycles: self.cycles,
+            last_cycle: self.last_cycle.map(|(_, at)| at.to_rfc3339()),
+            last_cycle_age_seconds: self
+                .last_cycle
+                .map(|(at, _)| now.saturating_duration_since(at).as_secs_f64()),
+            stale_after_seconds: self.stale_after.as_secs(),
+            readers,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct HealthReport {
+    pub status: &'static str,
+    pub live: bool,
+    pub ready: bool,
+    pub cycles: u64,
+    pub last_cycle: Option<String>,
+    pub last_cycle_age_seconds: Option<f64>,
+    pub stale_after_seconds: u64,
+    pub readers: Vec<ReaderReport>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReaderReport {
+    pub kind: &'static str,
+    pub name: &'static str,
+    pub status: &'static str,
+    pub device_count: usize,
+    pub last_success: Option<String>,
+    pub last_error: Option<String>,
+}
+
+fn probe_response(ok: bool, report: HealthReport) -> Response {
+    let status = if ok {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (status, Json(report)).into_response()
+}
+
+/// Liveness: fails only when the collection loop has stalled
+pub async fn healthz_handler(State(state): State<ApiState>) -> Response {
+    let report = state.health.read().await.report(Instant::now());
+    probe_response(report.live, report)
+}
+
+/// Readiness: requires a completed cycle and no stale readers
+pub async fn readyz_handler(State(state): State<ApiState>) -> Response {
+    let report = state.health.read().await.report(Instant::now());
+    probe_response(report.ready, report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status(kind: &'static str, name: &'static str, device_count: usize) -> ReaderStatus {
+        ReaderStatus {
+            kind,
+            name,
+            device_count,
+            stale: false,
+            data_age: Some(Duration::ZERO),
+            timeouts_total: 0,
+            call_duration: None,
+        }
+    }
+
+    #[test]
+    fn test_default_stale_after() {
+        assert_eq!(default_stale_after(1), Duration::from_secs(30));
+        assert_eq!(default_stale_after(5), Duration::from_secs(50));
+    }
+
+    #[test]
+    fn test_not_ready_before_first_cycle() {
+        let health = CollectorHealth::new(Duration::from_secs(30));
+        let report = health.report(Instant::now());
+        assert_eq!(report.status, "starting");
+        assert!(report.live);
+        assert!(!report.ready);
+    }
+
+    #[test]
+    fn test_ready_after_first_cycle() {
+        let mut health = CollectorHealth::new(Duration::from_secs(30));
+        health.record(&status("gpu", "NvidiaGpuReader", 8));
+        health.record(&status("cpu", "LinuxCpuReader", 1));
+        health.finish_cycle();
+
+        let report = health.report(Instant::now());
+        assert_eq!(report.status, "ok");
+        assert!(report.ready);
+        assert_eq!(report.cycles, 1);
+        assert_eq!(report.readers.len(), 2);
+        assert_eq!(report.readers[0].device_count, 8);
+        assert!(report.readers[0].last_success.is_some());
+        assert!(report.readers[0].last_error.is_none());
+    }
+
+    #[test]
+    fn test_failing_reader_degrades_then_goes_stale() {
+        let mut health = CollectorHealth::new(Duration::from_secs(30));
+        health.record(&status("gpu", "NvidiaGpuReader", 8));
+        health.finish_cycle();
+        health.record(&status("gpu", "NvidiaGpuReader", 0));
+        health.finish_cycle();
+
+        let report = health.report(Instant::now());
+        assert_eq!(report.status, "degraded");
+        assert!(report.ready);
+        assert_eq!(report.readers[0].status, "degraded");
+        assert!(report.readers[0].last_error.is_some());
+        // The device count of the failed cycle is reported, not the last good one
+        assert_eq!(report.readers[0].device_count, 0);
+
+        // Past the threshold the reader is stale and readiness fails, while
+        // the loop itself is still considered alive
+        let later = Instant::now() + Duration::from_secs(31);
+        health.readers[0].last_success = Some((Instant::now(), Utc::now()));
+        health.last_cycle = Some((later, Utc::now()));
+        let report = health.report(later);
+        assert!(report.live);
+        assert!(!report.ready);
+        assert_eq!(report.readers[0].status, "stale");
+    }
+
+    #[test]
+    fn test_timed_out_reader_degrades() {
+        let mut health = CollectorHealth::new(Duration::from_secs(30));
+        health.record(&status("gpu", "NvidiaGpuReader", 8));
+        health.finish_cycle();
+        // The last good data is still served, but the reader is failing
+        health.record(&ReaderStatus {
+            stale: true,
+            timeouts_total: 1,
+            ..status("gpu", "NvidiaGpuReader", 8)
+        });
+        health.finish_cycle();
+
+        let report = health.report(Instant::now());
+        assert_eq!(report.status, "degraded");
+        assert_eq!(report.readers[0].status, "degraded");
+        assert_eq!(report.readers[0].device_count, 8);
+        assert!(report.readers[0]
+            .last_error
+            .as_deref()
+            .unwrap()
+            .contains("deadline"));
+    }
+
+    #[tokio::test]
+    async fn test_reader_disabled_by_reload_keeps_readiness() {
+        use crate::api::exposition::SharedExposition;
+        use crate::api::self_metrics::{SelfMetrics, SharedSelfMetrics};
+        use crate::app_state::AppState;
+
+        let health = SharedHealth::new(RwLock::new(CollectorHealth::new(Duration::from_millis(
+            200,
+        ))));
+        let state = ApiState {
+            app_state: Arc::new(RwLock::new(AppState::new())),
+            updates: tokio::sync::watch::channel(0).1,
+            health: health.clone(),
+            self_metrics: SharedSelfMetrics::new(SelfMetrics::new()),
+            exposition: SharedExposition::default(),
+            export_options: Default::default(),
+        };
+        let cycle = |reader_status: Vec<ReaderStatus>| {
+            let health = health.clone();
+            async move {
+                let mut health = health.write().await;
+                for status in &reader_status {
+                    health.record(status);
+                }
+                health.retain_readers(&reader_status);
+                health.finish_cycle();
+            }
+        };
+
+        cycle(vec![
+            status("gpu", "NvidiaGpuReader", 8),
+            status("cpu", "LinuxCpuReader", 1),
+        ])
+        .await;
+        // A reload disables the GPU reader; it is not reported again
+        tokio::time::sleep(Duration::from_millis(250)).await;
+        cycle(vec![status("cpu", "LinuxCpuReader", 1)]).await;
+
+        let response = readyz_handler(State(state)).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(health.read().await.readers.len(), 1);
+    }
+
+    #[test]
+    fn test_stalled_loop_fails_liveness() {
+        let mut health = CollectorHealth::new(Duration::from_secs(30));
+        health.record(&status("cpu", "LinuxCpuReader", 1));
+        health.finish_cycle();
+
+        let report = health.report(Instant::now() + Duration::from_secs(31));
+        assert_eq!(report.status, "stale");
+        assert!(!report.live);
+        assert!(!report.ready);
+    }
+}
@@ -0,0 +1,129 @@
+// Copyright 2025 Lablup Inc. and Jeongkyu Shin
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Site labels attached to everything API mode exports
+//!
+//! Labels come from `--label key=value` and from a labels file with one
+//! `key=value` pair per line. The file is re-read with the rest of the
+//! configuration on SIGHUP. A `--label` wins over the file for the same key.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use crate::cli::ApiArgs;
+
+pub struct NodeLabels {
+    cli: Vec<(String, String)>,
+    file: Option<PathBuf>,
+}
+
+impl NodeLabels {
+    /// Parse the `--label` arguments; the file is read by `load`
+    pub fn from_args(args: &ApiArgs) -> Result<Self, String> {
+        let cli = args
+            .labels
+            .iter()
+            .map(|label| parse_label(label))
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
+            cli,
+            file: args.labels_file.as_ref().map(PathBuf::from),
+        })
+    }
+
+    /// Read the labels file and merge in the `--label` arguments
+    pub fn load(&self) -> Result<BTreeMap<String, String>, String> {
+        let mut labels = BTreeMap::new();
+        if let Some(path) = &self.file {
+            let content = std::fs::read_to_string(path)
+                .map_err(|e| format!("failed to read labels file {}: {e}", path.display()))?;
+            let file_labels = parse_labels_file(&content)
+                .map_err(|e| format!("invalid labels file {}: {e}", path.display()))?;
+            labels.extend(file_labels);
+        }
+        labels.extend(self.cli.iter().cloned());
+        Ok(labels)
+    }
+}
+
+/// Parse one `key=value` pair
+pub fn parse_label(label: &str) -> Result<(String, String), String> {
+    let (key, value) = label
+        .split_once('=')
+        .ok_or_else(|| format!("label '{label}' is not in key=value form"))?;
+    let key = key.trim();
+    if !is_valid_label_name(key) {
+        return Err(format!("invalid label name '{key}'"));
+    }
+    Ok((key.to_string(), value.trim().to_string()))
+}
+
+/// Parse a labels file: `key=value` lines, blank lines and `#` comments
+pub fn parse_labels_file(content: &str) -> Result<Vec<(String, String)>, String> {
+    content
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| (idx + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .map(|(number, line)| parse_label(line).map_err(|e| format!("line {number}: {e}")))
+        .collect()
+}
+
+/// Prometheus label names, excluding the reserved `__` prefix
+fn is_valid_label_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && !name.starts_with("__")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_label() {
+        assert_eq!(
+            parse_label("rack=r12").unwrap(),
+            ("rack".to_string(), "r12".to_string())
+        );
+        assert_eq!(parse_label("tenant=").unwrap().1, "");
+        assert_eq!(parse_label("zone=a=b").unwrap().1, "a=b");
+        assert!(parse_label("rack").is_err());
+        assert!(parse_label("1rack=x").is_err());
+        assert!(parse_label("__name__=x").is_err());
+        assert!(parse_label("rack-id=x").is_err());
+    }
+
+    #[test]
+    fn test_file_and_cli_labels() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("labels");
+        std::fs::write(&path, "# site\ncluster = alpha\n\nrack=r1\n").unwrap();
+
+        let labels = NodeLabels {
+            cli: vec![("rack".to_string(), "r7".to_string())],
+            file: Some(path.clone()),
+        };
+        let loaded = labels.load().unwrap();
+        assert_eq!(loaded["cluster"], "alpha");
+        assert_eq!(loaded["rack"], "r7");
+
+        std::fs::write(&path, "cluster=alpha\nbad line\n").unwrap();
+        let err = labels.load().unwrap_err();
+        assert!(err.contains("line 2"), "{err}");
+    }
+}
@@ -0,0 +1,462 @@
+// Copyright 2025 Lablup Inc. and Jeongkyu Shin
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! API server listeners
+//!
+//! Addresses come from `--listen` (`IP:PORT` or `unix:PATH`, repeatable), or
+//! from `--port` and `--socket` when it is not given. Under systemd socket
+//! activation the sockets passed in `LISTEN_FDS` are served instead.
+
+use std::fmt;
+use std::future::Future;
+use std::io;
+use std::net::{Ipv4Addr, SocketAddr};
+use std::pin::Pin;
+
+#[cfg(unix)]
+use std::path::{Path, PathBuf};
+
+use axum::Router;
+use futures_util::future::select_all;
+use socket2::{Domain, Protocol, Socket, Type};
+use tokio::net::TcpListener;
+use tokio_rustls::TlsAcceptor;
+
+#[cfg(unix)]
+use tokio::net::UnixListener;
+
+use super::tls::TlsListener;
+use crate::cli::ApiArgs;
+
+/// Pending connection queue of the TCP listeners all-smi binds itself
+const TCP_BACKLOG: i32 = 1024;
+
+/// A configured listen address
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ListenAddr {
+    Tcp(SocketAddr),
+    #[cfg(unix)]
+    Unix(PathBuf),
+}
+
+impl ListenAddr {
+    /// Parse `IP:PORT`, `[IPv6]:PORT` or `unix:PATH`
+    pub fn parse(addr: &str) -> Result<Self, String> {
+        if let Some(path) = addr.strip_prefix("unix:") {
+            #[cfg(unix)]
+            {
+                if path.is_empty() {
+                    return Err(format!("listen address '{addr}' has no socket path"));
+                }
+                return Ok(Self::Unix(PathBuf::from(path)));
+            }
+            #[cfg(not(unix))]
+            {
+                let _ = path;
+                return Err(format!(
+                    "listen address '{addr}': Unix sockets are not supported on this platform"
+                ));
+            }
+        }
+        addr.parse().map(Self::Tcp).map_err(|_| {
+            format!("invalid listen address '{addr}' (expected IP:PORT, [IPv6]:PORT or unix:PATH)")
+        })
+    }
+}
+
+impl fmt::Display for ListenAddr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Tcp(addr) => write!(f, "{addr}"),
+            #[cfg(unix)]
+            Self::Unix(path) => write!(f, "unix:{}", path.display()),
+        }
+    }
+}
+
+/// The addresses to bind: every `--listen`, or the `--port` listener when
+/// there is none, plus the `--socket` path
+pub fn listen_addrs(args: &ApiArgs) -> Result<Vec<ListenAddr>, String> {
+    let mut addrs = args
+        .listen
+        .iter()
+        .map(|addr| ListenAddr::parse(addr))
+        .collect::<Result<Vec<_>, _>>()?;
+    if addrs.is_empty() && args.port > 0 {
+        addrs.push(ListenAddr::Tcp(SocketAddr::from((
+            Ipv4Addr::UNSPECIFIED,
+            args.port,
+        ))));
+    }
+    #[cfg(unix)]
+    if let Some(socket) = &args.socket {
+        let path = if socket.is_empty() {
+            get_default_socket_path()
+        } else {
+            PathBuf::from(socket)
+        };
+        addrs.push(ListenAddr::Unix(path));
+    }
+    Ok(addrs)
+}
+
+/// Bind the configured listeners, or adopt the sockets passed by systemd
+pub fn bind_listeners(args: &ApiArgs) -> Result<Vec<Listener>, String> {
+    #[cfg(unix)]
+    {
+        let activated = super::systemd::activated_listeners()
+            .map_err(|e| format!("Invalid systemd socket activation: {e}"))?;
+        if !activated.is_empty() {
+            tracing::info!("Using {} socket(s) passed by systemd", activated.len());
+            return Ok(activated);
+        }
+    }
+
+    let addrs = listen_addrs(args)?;
+    if addrs.is_empty() {
+        return Err(
+            "No listeners configured. Use --listen, --port or --socket to specify a listener."
+                .to_string(),
+        );
+    }
+    addrs
+        .iter()
+        .map(|addr| Listener::bind(addr).map_err(|e| format!("Failed to bind {addr}: {e}")))
+        .collect()
+}
+
+/// A bound listener
+pub enum Listener {
+    Tcp(TcpListener),
+    #[cfg(unix)]
+    Unix {
+        listener: UnixListener,
+        /// Socket file to remove on shutdown; `None` for inherited sockets
+        path: Option<PathBuf>,
+    },
+}
+
+impl Listener {
+    pub fn bind(addr: &ListenAddr) -> io::Result<Self> {
+        match addr {
+            ListenAddr::Tcp(addr) => bind_tcp(*addr).map(Self::Tcp),
+            #[cfg(unix)]
+            ListenAddr::Unix(path) => bind_unix(path).map(|listener| Self::Unix {
+                listener,
+                path: Some(path.clone()),
+            }),
+        }
+    }
+
+    /// Take over a listening socket, e.g. one inherited from systemd
+    #[cfg(unix)]
+    pub fn from_fd(fd: std::os::fd::OwnedFd) -> io::Result<Self> {
+        use std::os::fd::{AsRawFd, OwnedFd};
+
+        let raw = fd.as_raw_fd();
+        let socket = Socket::from(fd);
+        if socket.r#type()? != Type::STREAM {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("descriptor {raw} is not a stream socket"),
+            ));
+        }
+        socket.set_nonblocking(true)?;
+        let local_addr = socket.local_addr()?;
+        if local_addr.as_socket().is_some() {
+            TcpListener::from_std(socket.into()).map(Self::Tcp)
+        } else if local_addr.is_unix() {
+            let listener = std::os::unix::net::UnixListener::from(OwnedFd::from(socket));
+            UnixListener::from_std(listener).map(|listener| Self::Unix {
+                listener,
+                path: None,
+            })
+        } else {
+            Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("descriptor {raw} is neither a TCP nor a Unix socket"),
+            ))
+        }
+    }
+
+    fn describe(&self, tls: bool) -> String {
+        match self {
+            Self::Tcp(listener) => format!(
+                "{}://{}",
+                if tls { "https" } else { "http" },
+                listener
+                    .local_addr()
+                    .map_or_else(|_| "unknown".to_string(), |addr| addr.to_string())
+            ),
+            #[cfg(unix)]
+            Self::Unix { listener, .. } => format!(
+                "Unix socket {}",
+                listener
+                    .local_addr()
+                    .ok()
+                    .and_then(|addr| addr.as_pathname().map(|p| p.display().to_string()))
+                    .unwrap_or_else(|| "(unnamed)".to_string())
+            ),
+        }
+    }
+}
+
+fn bind_tcp(addr: SocketAddr) -> io::Result<TcpListener> {
+    let socket = Socket::new(Domain::for_address(addr), Type::STREAM, Some(Protocol::TCP))?;
+    // IPv6 listeners take IPv6 only, so `0.0.0.0:P` and `[::]:P` can be
+    // given together
+    if addr.is_ipv6() {
+        socket.set_only_v6(true)?;
+    }
+    #[cfg(unix)]
+    socket.set_reuse_address(true)?;
+    socket.set_nonblocking(true)?;
+    socket.bind(&addr.into())?;
+    socket.listen(TCP_BACKLOG)?;
+    TcpListener::from_std(socket.into())
+}
+
+#[cfg(unix)]
+fn bind_unix(path: &Path) -> io::Result<UnixListener> {
+    // Remove stale socket file if it exists
+    if let Err(e) = remove_stale_socket(path) {
+        tracing::warn!("Failed to remove stale socket file: {e}");
+    }
+
+    // Create parent directory if it doesn't exist
+    if let Some(parent) = path.parent() {
+        if !parent.as_os_str().is_empty() && !parent.exists() {
+            std::fs::create_dir_all(parent)?;
+        }
+    }
+
+    let listener = UnixListener::bind(path)?;
+
+    // Set restrictive permissions (0o600) on the socket file
+    if let Err(e) = set_socket_permissions(path) {
+        tracing::warn!("Failed to set socket permissions: {e}");
+    }
+    Ok(listener)
+}
+
+/// Get the default Unix domain socket path for the current platform.
+/// - Linux: /var/run/all-smi.sock (fallback to /tmp/all-smi.sock if no permission)
+/// - macOS: /tmp/all-smi.sock
+#[cfg(unix)]
+fn get_default_socket_path() -> PathBuf {
+    #[cfg(target_os = "linux")]
+    {
+        let var_run_path = PathBuf::from("/var/run/all-smi.sock");
+        // Check if we can write to /var/run
+        if let Ok(metadata) = std::fs::metadata("/var/run") {
+            if metadata.is_dir() {
+                // Try to create a test file to check write permission
+                let test_path = PathBuf::from("/var/run/.all-smi-test");
+                if std::fs::write(&test_path, b"").is_ok() {
+                    let _ = std::fs::remove_file(&test_path);
+                    return var_run_path;
+                }
+            }
+        }
+        // Fallback to /tmp
+        PathBuf::from("/tmp/all-smi.sock")
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        PathBuf::from("/tmp/all-smi.sock")
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "macos")))]
+    {
+        PathBuf::from("/tmp/all-smi.sock")
+    }
+}
+
+/// Remove stale socket file if it exists.
+/// This is necessary because Unix sockets leave files on disk that prevent rebinding.
+/// Uses atomic remove to avoid TOCTOU race conditions.
+#[cfg(unix)]
+fn remove_stale_socket(path: &Path) -> io::Result<()> {
+    match std::fs::remove_file(path) {
+        Ok(()) => {
+            tracing::info!("Removed stale socket file: {}", path.display());
+            Ok(())
+        }
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            // File doesn't exist, that's fine
+            Ok(())
+        }
+        Err(e) => Err(e),
+    }
+}
+
+/// Set restrictive permissions (0o600) on the socket file.
+/// This ensures only the owner can connect to the socket.
+#[cfg(unix)]
+fn set_socket_permissions(path: &Path) -> io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    let permissions = std::fs::Permissions::from_mode(0o600);
+    std::fs::set_permissions(path, permissions)
+}
+
+/// Clean up the Unix domain socket file.
+/// Uses atomic remove to avoid TOCTOU race conditions.
+#[cfg(unix)]
+fn cleanup_socket(path: &Path) {
+    match std::fs::remove_file(path) {
+        Ok(()) => {
+            tracing::info!("Cleaned up socket file: {}", path.display());
+        }
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            // File already removed, that's fine
+        }
+        Err(e) => {
+            tracing::warn!("Failed to remove socket file on shutdown: {e}");
+        }
+    }
+}
+
+/// Serve the router on a TCP listener, over TLS if an acceptor is given
+async fn serve_tcp(listener: TcpListener, app: Router, tls: Option<TlsAcceptor>) -> io::Result<()> {
+    match tls {
+        Some(acceptor) => axum::serve(TlsListener::new(listener, acceptor)?, app).await,
+        None => axum::serve(listener, app).await,
+    }
+}
+
+type ServeFuture = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+/// Serve the router on every listener until one of them fails. TLS applies
+/// to TCP listeners only.
+pub async fn serve(listeners: Vec<Listener>, app: Router, tls: Option<TlsAcceptor>) {
+    #[cfg(unix)]
+    let socket_files: Vec<PathBuf> = listeners
+        .iter()
+        .filter_map(|listener| match listener {
+            Listener::Unix { path, .. } => path.clone(),
+            _ => None,
+        })
+        .collect();
+
+    // Set up socket cleanup on shutdown
+    #[cfg(unix)]
+    let cleanup_handle = {
+        let paths = socket_files.clone();
+        tokio::spawn(async move {
+            tokio::signal::ctrl_c()
+                .await
+                .expect("Failed to listen for Ctrl+C");
+            paths.iter().for_each(|path| cleanup_socket(path));
+        })
+    };
+
+    let servers: Vec<ServeFuture> = listeners
+        .into_iter()
+        .map(|listener| -> ServeFuture {
+            tracing::info!(
+                "API server listening on {}",
+                listener.describe(tls.is_some())
+            );
+            let app = app.clone();
+            match listener {
+                Listener::Tcp(listener) => {
+                    let tls = tls.clone();
+                    Box::pin(async move {
+                        if let Err(e) = serve_tcp(listener, app, tls).await {
+                            tracing::error!("TCP server error: {e}");
+                        }
+                    })
+                }
+                #[cfg(unix)]
+                Listener::Unix { listener, .. } => Box::pin(async move {
+                    if let Err(e) = axum::serve(listener, app).await {
+                        tracing::error!("Unix socket server error: {e}");
+                    }
+                }),
+            }
+        })
+        .collect();
+    if !servers.is_empty() {
+        select_all(servers).await;
+    }
+
+    // Cancel cleanup handle and do cleanup
+    #[cfg(unix)]
+    {
+        cleanup_handle.abort();
+        socket_files.iter().for_each(|path| cleanup_socket(path));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_listen_addr() {
+        assert_eq!(
+            ListenAddr::parse("127.0.0.1:9090").unwrap(),
+            ListenAddr::Tcp("127.0.0.1:9090".parse().unwrap())
+        );
+        assert_eq!(
+            ListenAddr::parse("[::1]:9090").unwrap(),
+            ListenAddr::Tcp("[::1]:9090".parse().unwrap())
+        );
+        #[cfg(unix)]
+        assert_eq!(
+            ListenAddr::parse("unix:/run/all-smi.sock").unwrap(),
+            ListenAddr::Unix(PathBuf::from("/run/all-smi.sock"))
+        );
+        assert!(ListenAddr::parse("unix:").is_err());
+        assert!(ListenAddr::parse("9090").is_err());
+        assert!(ListenAddr::parse("localhost:9090").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_ipv4_and_ipv6_on_same_port() {
+        let v4 = Listener::bind(&ListenAddr::parse("127.0.0.1:0").unwrap()).unwrap();
+        let Listener::Tcp(v4_listener) = &v4 else {
+            panic!("expected a TCP listener");
+        };
+        let port = v4_listener.local_addr().unwrap().port();
+        // Skipped on hosts without IPv6
+        if let Ok(v6) = Listener::bind(&ListenAddr::Tcp(SocketAddr::from((
+            std::net::Ipv6Addr::LOCALHOST,
+            port,
+        )))) {
+            assert!(v6.describe(false).ends_with(&format!("]:{port}")));
+        }
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_adopt_inherited_sockets() {
+        use std::os::fd::OwnedFd;
+
+        let tcp = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = tcp.local_addr().unwrap();
+        let Listener::Tcp(listener) = Listener::from_fd(OwnedFd::from(tcp)).unwrap() else {
+            panic!("expected a TCP listener");
+        };
+        assert_eq!(listener.local_addr().unwrap(), addr);
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("inherited.sock");
+        let unix = std::os::unix::net::UnixListener::bind(&path).unwrap();
+        let listener = Listener::from_fd(OwnedFd::from(unix)).unwrap();
+        assert!(matches!(listener, Listener::Unix { path: None, .. }));
+        assert!(listener.describe(false).ends_with("inherited.sock"));
+    }
+}
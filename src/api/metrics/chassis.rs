@@ -91,11 +91,9 @@ impl MetricPresenceFlags {
 }
 
 impl<'a> MetricExporter for ChassisMetricExporter<'a> {
-    fn export_metrics(&self) -> String {
-        let mut builder = MetricBuilder::new();
-
+    fn export_to(&self, builder: &mut MetricBuilder) {
         if self.chassis_info.is_empty() {
-            return builder.build();
+            return;
         }
 
         // Single pass to determine which metrics are present
@@ -287,8 +285,6 @@ impl<'a> MetricExporter for ChassisMetricExporter<'a> {
                 }
             }
         }
-
-        builder.build()
     }
 }
 
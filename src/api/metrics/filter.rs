@@ -0,0 +1,183 @@
+// Copyright 2025 Lablup Inc. and Jeongkyu Shin
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Scrape-time selection of exporter groups and metric families
+//!
+//! `/metrics?collect[]=gpu&collect[]=cpu` runs only the named exporter
+//! groups, like node_exporter's `collect[]`. `include[]` and `exclude[]` take
+//! regular expressions matched against whole family names; an exclude always
+//! wins over an include.
+
+use regex::Regex;
+
+/// A set of exporters that can be selected with `collect[]`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExporterGroup {
+    Gpu,
+    Npu,
+    Cpu,
+    Memory,
+    Disk,
+    Process,
+    Chassis,
+    Runtime,
+    /// Reader status and exporter self-metrics
+    Collector,
+}
+
+impl ExporterGroup {
+    pub const ALL: &'static [ExporterGroup] = &[
+        Self::Gpu,
+        Self::Npu,
+        Self::Cpu,
+        Self::Memory,
+        Self::Disk,
+        Self::Process,
+        Self::Chassis,
+        Self::Runtime,
+        Self::Collector,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Gpu => "gpu",
+            Self::Npu => "npu",
+            Self::Cpu => "cpu",
+            Self::Memory => "memory",
+            Self::Disk => "disk",
+            Self::Process => "process",
+            Self::Chassis => "chassis",
+            Self::Runtime => "runtime",
+            Self::Collector => "collector",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|group| group.as_str().eq_ignore_ascii_case(name))
+    }
+}
+
+/// Which exporters run and which families are kept in a scrape
+#[derive(Debug, Default)]
+pub struct MetricFilter {
+    /// `None` selects every group
+    groups: Option<Vec<ExporterGroup>>,
+    include: Vec<Regex>,
+    exclude: Vec<Regex>,
+}
+
+impl MetricFilter {
+    /// Parse the query string of a `/metrics` request. Unknown groups and
+    /// invalid patterns are errors; other parameters are ignored.
+    pub fn from_query(query: &str) -> Result<Self, String> {
+        let mut filter = Self::default();
+        for (key, value) in url::form_urlencoded::parse(query.as_bytes()) {
+            match key.trim_end_matches("[]") {
+                "collect" => {
+                    let groups = filter.groups.get_or_insert_with(Vec::new);
+                    for name in value.split(',').map(str::trim).filter(|n| !n.is_empty()) {
+                        let group = ExporterGroup::parse(name).ok_or_else(|| {
+                            let known: Vec<_> =
+                                ExporterGroup::ALL.iter().map(|g| g.as_str()).collect();
+                            format!(
+                                "unknown collect group '{name}' (expected one of: {})",
+                                known.join(", ")
+                            )
+                        })?;
+                        groups.push(group);
+                    }
+                }
+                "include" => filter.include.push(anchored(&value)?),
+                "exclude" => filter.exclude.push(anchored(&value)?),
+                _ => {}
+            }
+        }
+        Ok(filter)
+    }
+
+    /// Whether the exporters of `group` should run
+    pub fn wants(&self, group: ExporterGroup) -> bool {
+        self.groups
+            .as_ref()
+            .is_none_or(|groups| groups.contains(&group))
+    }
+
+    /// Whether every group and family is selected
+    pub fn selects_everything(&self) -> bool {
+        self.groups.is_none() && !self.has_name_patterns()
+    }
+
+    /// Whether any name patterns were given
+    pub fn has_name_patterns(&self) -> bool {
+        !self.include.is_empty() || !self.exclude.is_empty()
+    }
+
+    /// Whether a metric family is kept
+    pub fn allows(&self, family: &str) -> bool {
+        (self.include.is_empty() || self.include.iter().any(|re| re.is_match(family)))
+            && !self.exclude.iter().any(|re| re.is_match(family))
+    }
+}
+
+/// Compile a pattern that must match the whole family name
+fn anchored(pattern: &str) -> Result<Regex, String> {
+    Regex::new(&format!("^(?:{pattern})$")).map_err(|e| format!("invalid pattern '{pattern}': {e}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_selects_everything() {
+        let filter = MetricFilter::from_query("").unwrap();
+        assert!(ExporterGroup::ALL.iter().all(|g| filter.wants(*g)));
+        assert!(filter.allows("all_smi_gpu_utilization"));
+        assert!(!filter.has_name_patterns());
+        assert!(filter.selects_everything());
+    }
+
+    #[test]
+    fn test_collect_groups() {
+        let filter =
+            MetricFilter::from_query("collect[]=gpu&collect%5B%5D=CPU&collect=disk,npu").unwrap();
+        assert!(filter.wants(ExporterGroup::Gpu));
+        assert!(filter.wants(ExporterGroup::Cpu));
+        assert!(filter.wants(ExporterGroup::Disk));
+        assert!(filter.wants(ExporterGroup::Npu));
+        assert!(!filter.wants(ExporterGroup::Process));
+
+        let err = MetricFilter::from_query("collect[]=gpus").unwrap_err();
+        assert!(err.contains("gpus"));
+    }
+
+    #[test]
+    fn test_name_patterns() {
+        let filter = MetricFilter::from_query(
+            "include[]=all_smi_gpu_.*&include[]=all_smi_cpu_utilization&exclude[]=.*_info",
+        )
+        .unwrap();
+        assert!(filter.allows("all_smi_gpu_utilization"));
+        assert!(filter.allows("all_smi_cpu_utilization"));
+        assert!(!filter.allows("all_smi_gpu_info"));
+        // Patterns are anchored to the whole name
+        assert!(!filter.allows("all_smi_cpu_utilization_per_core"));
+        assert!(!filter.allows("all_smi_memory_used_bytes"));
+
+        assert!(MetricFilter::from_query("include[]=(").is_err());
+    }
+}
@@ -204,8 +204,11 @@ impl<'a> GpuMetricExporter<'a> {
             all_labels.push((*key, *value));
         }
 
-        // Add detail labels
+        // Add detail labels, skipping keys that would duplicate an existing label
         for (key, value) in &detail_labels {
+            if all_labels.iter().any(|(k, _)| *k == key.as_str()) {
+                continue;
+            }
             all_labels.push((key.as_str(), value.as_str()));
         }
 
@@ -308,19 +311,15 @@ impl<'a> GpuMetricExporter<'a> {
 }
 
 impl<'a> MetricExporter for GpuMetricExporter<'a> {
-    fn export_metrics(&self) -> String {
-        let mut builder = MetricBuilder::new();
-
+    fn export_to(&self, builder: &mut MetricBuilder) {
         for (i, info) in self.gpu_info.iter().enumerate() {
             // Export metrics for GPU, NPU, and TPU devices
             if info.device_type == "GPU" || info.device_type == "NPU" || info.device_type == "TPU" {
-                self.export_basic_metrics(&mut builder, info, i);
-                self.export_apple_silicon_metrics(&mut builder, info, i);
-                self.export_device_info(&mut builder, info, i);
-                self.export_cuda_metrics(&mut builder, info, i);
+                self.export_basic_metrics(builder, info, i);
+                self.export_apple_silicon_metrics(builder, info, i);
+                self.export_device_info(builder, info, i);
+                self.export_cuda_metrics(builder, info, i);
             }
         }
-
-        builder.build()
     }
 }
@@ -15,84 +15,301 @@
 pub mod chassis;
 pub mod cpu;
 pub mod disk;
+pub mod filter;
 pub mod gpu;
 pub mod memory;
 pub mod npu;
+pub mod openmetrics;
 pub mod process;
+pub mod reader;
 pub mod runtime;
 
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use all_smi::traits::{Histogram, MetricType};
+use tokio::sync::RwLock;
+
 /// Trait for exporting metrics in Prometheus format
 pub trait MetricExporter {
+    /// Add this exporter's samples to a shared builder, so families that span
+    /// several exporters are still rendered once
+    fn export_to(&self, builder: &mut MetricBuilder);
+
     /// Export metrics to Prometheus format string
-    fn export_metrics(&self) -> String;
+    #[allow(dead_code)]
+    fn export_metrics(&self) -> String {
+        let mut builder = MetricBuilder::new();
+        self.export_to(&mut builder);
+        builder.build()
+    }
+}
+
+/// Settings that shape what the exporters emit; replaced on reload
+#[derive(Debug, Clone, Default)]
+pub struct ExportOptions {
+    pub process: process::ProcessMetricOptions,
+}
+
+pub type SharedExportOptions = Arc<RwLock<ExportOptions>>;
+
+/// Sample name suffixes that belong to the family without the suffix
+const FAMILY_SUFFIXES: &[&str] = &["_total", "_created", "_bucket", "_count", "_sum"];
+
+/// A single sample line of a metric family
+#[derive(Clone)]
+struct Sample {
+    name: String,
+    labels: Vec<(String, String)>,
+    value: String,
+}
+
+/// Metadata and samples for all series sharing a metric name
+#[derive(Clone)]
+struct MetricFamily {
+    name: String,
+    help: Option<String>,
+    metric_type: Option<String>,
+    samples: Vec<Sample>,
+    /// Sample name and labels of every series, to drop repeated host-level series
+    series: HashSet<(String, Vec<(String, String)>)>,
 }
 
 /// Helper struct to build Prometheus metrics
+///
+/// Works as a metric-family registry: samples are grouped by family name in
+/// first-seen order, and `# HELP`/`# TYPE` are rendered once per family no
+/// matter how many devices or exporters contribute samples to it. A series
+/// that is added again with identical labels (e.g. a driver version reported
+/// by every device) keeps its first value.
+#[derive(Clone)]
 pub struct MetricBuilder {
-    metrics: String,
+    families: Vec<MetricFamily>,
+    index: HashMap<String, usize>,
+    /// Labels added to every sample that does not already carry them
+    const_labels: Vec<(String, String)>,
 }
 
 impl MetricBuilder {
     pub fn new() -> Self {
         Self {
-            metrics: String::new(),
+            families: Vec::new(),
+            index: HashMap::new(),
+            const_labels: Vec::new(),
         }
     }
 
-    /// Add a comment line
-    #[allow(dead_code)]
-    pub fn comment(&mut self, text: &str) -> &mut Self {
-        self.metrics.push_str("# ");
-        self.metrics.push_str(text);
-        self.metrics.push('\n');
+    fn family_mut(&mut self, name: &str) -> &mut MetricFamily {
+        let idx = match self.index.get(name) {
+            Some(&idx) => idx,
+            None => {
+                self.families.push(MetricFamily {
+                    name: name.to_string(),
+                    help: None,
+                    metric_type: None,
+                    samples: Vec::new(),
+                    series: HashSet::new(),
+                });
+                self.index.insert(name.to_string(), self.families.len() - 1);
+                self.families.len() - 1
+            }
+        };
+        &mut self.families[idx]
+    }
+
+    /// Find the family a sample belongs to. Suffixed samples such as
+    /// `_bucket` or `_total` are attached to an already typed base family.
+    fn family_for_sample(&mut self, name: &str) -> &mut MetricFamily {
+        if !self.index.contains_key(name) {
+            for suffix in FAMILY_SUFFIXES {
+                if let Some(base) = name.strip_suffix(suffix) {
+                    if let Some(&idx) = self.index.get(base) {
+                        if matches!(
+                            self.families[idx].metric_type.as_deref(),
+                            Some("counter" | "histogram" | "summary")
+                        ) {
+                            return &mut self.families[idx];
+                        }
+                    }
+                }
+            }
+        }
+        self.family_mut(name)
+    }
+
+    /// Add labels to every sample registered from now on. A label the
+    /// exporter sets itself keeps the exporter's value.
+    pub fn const_labels<'a>(
+        &mut self,
+        labels: impl IntoIterator<Item = (&'a String, &'a String)>,
+    ) -> &mut Self {
+        self.const_labels
+            .extend(labels.into_iter().map(|(k, v)| (k.clone(), v.clone())));
         self
     }
 
-    /// Add a HELP line
+    /// Set the HELP text of a family (the first description wins)
     pub fn help(&mut self, name: &str, description: &str) -> &mut Self {
-        self.metrics
-            .push_str(&format!("# HELP {name} {description}\n"));
+        self.family_mut(name)
+            .help
+            .get_or_insert_with(|| description.to_string());
         self
     }
 
-    /// Add a TYPE line
+    /// Set the TYPE of a family (the first type wins)
     pub fn type_(&mut self, name: &str, metric_type: &str) -> &mut Self {
-        self.metrics
-            .push_str(&format!("# TYPE {name} {metric_type}\n"));
+        self.family_mut(name)
+            .metric_type
+            .get_or_insert_with(|| metric_type.to_string());
         self
     }
 
-    /// Add a metric line with labels
+    /// Add a metric line with labels. A value that is not a number is
+    /// skipped, since neither text format could carry it.
     pub fn metric(
         &mut self,
         name: &str,
         labels: &[(&str, &str)],
         value: impl ToString,
     ) -> &mut Self {
-        self.metrics.push_str(name);
+        let value = format_value(value.to_string());
+        if parse_value(&value).is_none() {
+            return self;
+        }
+        let mut labels: Vec<(String, String)> = labels
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        // Histogram buckets keep `le` last
+        let at = labels
+            .iter()
+            .position(|(k, _)| k == "le")
+            .unwrap_or(labels.len());
+        let extra: Vec<(String, String)> = self
+            .const_labels
+            .iter()
+            .filter(|(key, _)| !labels.iter().any(|(k, _)| k == key))
+            .cloned()
+            .collect();
+        labels.splice(at..at, extra);
+        let family = self.family_for_sample(name);
 
-        if !labels.is_empty() {
-            self.metrics.push('{');
-            for (i, (key, value)) in labels.iter().enumerate() {
-                if i > 0 {
-                    self.metrics.push_str(", ");
-                }
-                // Escape quotes in values for Prometheus format
-                let escaped_value = value.replace('"', "\\\"");
-                self.metrics.push_str(&format!("{key}=\"{escaped_value}\""));
-            }
-            self.metrics.push('}');
+        let mut series_key = labels.clone();
+        series_key.sort();
+        if family.series.insert((name.to_string(), series_key)) {
+            family.samples.push(Sample {
+                name: name.to_string(),
+                labels,
+                value,
+            });
         }
+        self
+    }
 
-        self.metrics.push(' ');
-        self.metrics.push_str(&value.to_string());
-        self.metrics.push('\n');
+    /// Add the `_bucket`, `_sum` and `_count` samples of one histogram series
+    pub fn histogram(
+        &mut self,
+        name: &str,
+        labels: &[(&str, &str)],
+        histogram: &Histogram,
+    ) -> &mut Self {
+        self.type_(name, MetricType::Histogram.as_str());
+        let bucket_name = format!("{name}_bucket");
+        for (bound, count) in histogram.buckets() {
+            let le = format_value(format!("{bound:?}"));
+            let mut bucket_labels = labels.to_vec();
+            bucket_labels.push(("le", &le));
+            self.metric(&bucket_name, &bucket_labels, count);
+        }
+        self.metric(&format!("{name}_sum"), labels, histogram.sum());
+        self.metric(&format!("{name}_count"), labels, histogram.count());
         self
     }
 
-    /// Build the final metric string
+    /// Drop every family whose name is rejected by `keep`
+    pub fn retain_families(&mut self, mut keep: impl FnMut(&str) -> bool) {
+        self.families.retain(|family| keep(&family.name));
+        self.index = self
+            .families
+            .iter()
+            .enumerate()
+            .map(|(idx, family)| (family.name.clone(), idx))
+            .collect();
+    }
+
+    /// Every sample with its value parsed back to a float, for push protocols
+    /// that carry samples instead of text
+    pub fn samples(&self) -> impl Iterator<Item = (&str, &[(String, String)], f64)> {
+        self.families
+            .iter()
+            .flat_map(|family| family.samples.iter())
+            .filter_map(|sample| {
+                parse_value(&sample.value)
+                    .map(|value| (sample.name.as_str(), sample.labels.as_slice(), value))
+            })
+    }
+
+    /// Build the final metric string in Prometheus text format
     pub fn build(self) -> String {
-        self.metrics
+        let mut output = String::new();
+
+        for family in self.families.iter().filter(|f| !f.samples.is_empty()) {
+            if let Some(help) = &family.help {
+                output.push_str(&format!(
+                    "# HELP {} {}\n",
+                    family.name,
+                    escape_help(help, false)
+                ));
+            }
+            if let Some(metric_type) = &family.metric_type {
+                output.push_str(&format!("# TYPE {} {metric_type}\n", family.name));
+            }
+            for sample in &family.samples {
+                push_sample(&mut output, &sample.name, sample, ", ");
+            }
+        }
+
+        output
+    }
+
+    /// Build the final metric string in OpenMetrics 1.0 text format
+    pub fn build_openmetrics(self) -> String {
+        let mut output = String::new();
+
+        for family in self.families.iter().filter(|f| !f.samples.is_empty()) {
+            let metric_type = match family.metric_type.as_deref() {
+                None | Some("untyped") => "unknown",
+                Some(other) => other,
+            };
+            let is_counter = metric_type == "counter";
+            let family_name = if is_counter {
+                family.name.strip_suffix("_total").unwrap_or(&family.name)
+            } else {
+                family.name.as_str()
+            };
+
+            output.push_str(&format!("# TYPE {family_name} {metric_type}\n"));
+            if let Some(unit) = openmetrics::unit_for(family_name) {
+                output.push_str(&format!("# UNIT {family_name} {unit}\n"));
+            }
+            if let Some(help) = &family.help {
+                output.push_str(&format!(
+                    "# HELP {family_name} {}\n",
+                    escape_help(help, true)
+                ));
+            }
+            for sample in &family.samples {
+                if is_counter && sample.name == family_name {
+                    let name = format!("{family_name}_total");
+                    push_sample(&mut output, &name, sample, ",");
+                } else {
+                    push_sample(&mut output, &sample.name, sample, ",");
+                }
+            }
+        }
+
+        output.push_str(openmetrics::OPENMETRICS_EOF);
+        output
     }
 }
 
@@ -101,3 +318,272 @@ impl Default for MetricBuilder {
         Self::new()
     }
 }
+
+fn push_sample(output: &mut String, name: &str, sample: &Sample, separator: &str) {
+    output.push_str(name);
+
+    if !sample.labels.is_empty() {
+        output.push('{');
+        for (i, (key, value)) in sample.labels.iter().enumerate() {
+            if i > 0 {
+                output.push_str(separator);
+            }
+            output.push_str(&format!("{key}=\"{}\"", escape_label_value(value)));
+        }
+        output.push('}');
+    }
+
+    output.push(' ');
+    output.push_str(&sample.value);
+    output.push('\n');
+}
+
+/// Escape a label value per the exposition format (backslash, quote, newline)
+fn escape_label_value(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '"' => escaped.push_str("\\\""),
+            '\n' => escaped.push_str("\\n"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Escape HELP text. OpenMetrics additionally escapes double quotes.
+fn escape_help(help: &str, escape_quotes: bool) -> String {
+    let mut escaped = String::with_capacity(help.len());
+    for c in help.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '"' if escape_quotes => escaped.push_str("\\\""),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Rust formats non-finite floats as `inf`/`NaN`; both text formats expect `+Inf`/`-Inf`/`NaN`
+fn format_value(value: String) -> String {
+    match value.as_str() {
+        "inf" => "+Inf".to_string(),
+        "-inf" => "-Inf".to_string(),
+        _ => value,
+    }
+}
+
+/// Inverse of `format_value`
+fn parse_value(value: &str) -> Option<f64> {
+    match value {
+        "+Inf" => Some(f64::INFINITY),
+        "-Inf" => Some(f64::NEG_INFINITY),
+        _ => value.parse().ok(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_help_and_type_emitted_once_per_family() {
+        let mut builder = MetricBuilder::new();
+        for index in ["0", "1"] {
+            builder
+                .help("all_smi_gpu_utilization", "GPU utilization percentage")
+                .type_("all_smi_gpu_utilization", "gauge")
+                .metric("all_smi_gpu_utilization", &[("index", index)], 50);
+            builder
+                .help("all_smi_gpu_temperature_celsius", "GPU temperature")
+                .type_("all_smi_gpu_temperature_celsius", "gauge")
+                .metric("all_smi_gpu_temperature_celsius", &[("index", index)], 40);
+        }
+
+        assert_eq!(
+            builder.build(),
+            "\
+# HELP all_smi_gpu_utilization GPU utilization percentage
+# TYPE all_smi_gpu_utilization gauge
+all_smi_gpu_utilization{index=\"0\"} 50
+all_smi_gpu_utilization{index=\"1\"} 50
+# HELP all_smi_gpu_temperature_celsius GPU temperature
+# TYPE all_smi_gpu_temperature_celsius gauge
+all_smi_gpu_temperature_celsius{index=\"0\"} 40
+all_smi_gpu_temperature_celsius{index=\"1\"} 40
+"
+        );
+    }
+
+    #[test]
+    fn test_repeated_series_keeps_first_value() {
+        let mut builder = MetricBuilder::new();
+        builder
+            .metric("all_smi_driver_info", &[("version", "1.0")], 1)
+            .metric("all_smi_driver_info", &[("version", "1.0")], 2)
+            .metric("all_smi_driver_info", &[("version", "2.0")], 1);
+        assert_eq!(
+            builder.build(),
+            "all_smi_driver_info{version=\"1.0\"} 1\nall_smi_driver_info{version=\"2.0\"} 1\n"
+        );
+    }
+
+    #[test]
+    fn test_label_value_escaping() {
+        let mut builder = MetricBuilder::new();
+        builder.metric("m", &[("v", "a\\b \"c\"\nd")], 1);
+        assert_eq!(builder.build(), "m{v=\"a\\\\b \\\"c\\\"\\nd\"} 1\n");
+    }
+
+    #[test]
+    fn test_non_finite_values() {
+        let mut builder = MetricBuilder::new();
+        builder
+            .metric("m", &[("a", "1")], f64::INFINITY)
+            .metric("m", &[("a", "2")], f64::NEG_INFINITY)
+            .metric("m", &[("a", "3")], f64::NAN);
+        let output = builder.build();
+        assert!(output.contains("m{a=\"1\"} +Inf\n"));
+        assert!(output.contains("m{a=\"2\"} -Inf\n"));
+        assert!(output.contains("m{a=\"3\"} NaN\n"));
+    }
+
+    #[test]
+    fn test_non_numeric_values_are_rejected() {
+        let mut builder = MetricBuilder::new();
+        builder
+            .metric("info", &[("version", "x")], "not-a-number")
+            .metric("info", &[("version", "y")], "")
+            .metric("m", &[], 1);
+        assert_eq!(builder.build(), "m 1\n");
+    }
+
+    #[test]
+    fn test_samples_parse_values() {
+        let mut builder = MetricBuilder::new();
+        builder
+            .metric("m", &[("a", "1")], 1.5)
+            .metric("m", &[("a", "2")], f64::INFINITY);
+        let samples: Vec<_> = builder.samples().collect();
+        assert_eq!(samples.len(), 2);
+        assert_eq!(samples[0].0, "m");
+        assert_eq!(samples[0].1, &[("a".to_string(), "1".to_string())]);
+        assert_eq!(samples[0].2, 1.5);
+        assert_eq!(samples[1].2, f64::INFINITY);
+    }
+
+    #[test]
+    fn test_openmetrics_rendering() {
+        let mut builder = MetricBuilder::new();
+        builder
+            .help("all_smi_gpu_memory_used_bytes", "GPU memory used in bytes")
+            .type_("all_smi_gpu_memory_used_bytes", "gauge")
+            .metric(
+                "all_smi_gpu_memory_used_bytes",
+                &[("gpu", "A"), ("index", "0")],
+                100,
+            )
+            .help("all_smi_tenstorrent_heartbeat", "Device heartbeat counter")
+            .type_("all_smi_tenstorrent_heartbeat", "counter")
+            .metric("all_smi_tenstorrent_heartbeat", &[("npu", "n150")], 42);
+
+        assert_eq!(
+            builder.build_openmetrics(),
+            "\
+# TYPE all_smi_gpu_memory_used_bytes gauge
+# UNIT all_smi_gpu_memory_used_bytes bytes
+# HELP all_smi_gpu_memory_used_bytes GPU memory used in bytes
+all_smi_gpu_memory_used_bytes{gpu=\"A\",index=\"0\"} 100
+# TYPE all_smi_tenstorrent_heartbeat counter
+# HELP all_smi_tenstorrent_heartbeat Device heartbeat counter
+all_smi_tenstorrent_heartbeat_total{npu=\"n150\"} 42
+# EOF
+"
+        );
+    }
+
+    #[test]
+    fn test_retain_families() {
+        let mut builder = MetricBuilder::new();
+        builder
+            .type_("all_smi_a", "gauge")
+            .metric("all_smi_a", &[], 1)
+            .type_("all_smi_b", "gauge")
+            .metric("all_smi_b", &[], 2);
+        builder.retain_families(|name| name != "all_smi_a");
+        // Families added after filtering still find their registry entry
+        builder.metric("all_smi_b", &[("x", "y")], 3);
+
+        assert_eq!(
+            builder.build(),
+            "# TYPE all_smi_b gauge\nall_smi_b 2\nall_smi_b{x=\"y\"} 3\n"
+        );
+    }
+
+    #[test]
+    fn test_histogram_series() {
+        let mut histogram = Histogram::new(&[0.1, 1.0]);
+        for value in [0.05, 0.5, 0.5, 3.0] {
+            histogram.observe(value);
+        }
+
+        let mut builder = MetricBuilder::new();
+        builder
+            .help("all_smi_test_duration_seconds", "Test durations")
+            .histogram(
+                "all_smi_test_duration_seconds",
+                &[("reader", "a")],
+                &histogram,
+            );
+
+        assert_eq!(
+            builder.build(),
+            "\
+# HELP all_smi_test_duration_seconds Test durations
+# TYPE all_smi_test_duration_seconds histogram
+all_smi_test_duration_seconds_bucket{reader=\"a\", le=\"0.1\"} 1
+all_smi_test_duration_seconds_bucket{reader=\"a\", le=\"1.0\"} 3
+all_smi_test_duration_seconds_bucket{reader=\"a\", le=\"+Inf\"} 4
+all_smi_test_duration_seconds_sum{reader=\"a\"} 4.05
+all_smi_test_duration_seconds_count{reader=\"a\"} 4
+"
+        );
+    }
+
+    #[test]
+    fn test_const_labels() {
+        let site: BTreeMap<String, String> = [("rack", "r1"), ("instance", "site")]
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        let mut histogram = Histogram::new(&[1.0]);
+        histogram.observe(0.5);
+
+        let mut builder = MetricBuilder::new();
+        builder
+            .const_labels(&site)
+            .metric("all_smi_a", &[("instance", "node-1")], 1)
+            .histogram("all_smi_b", &[], &histogram);
+        let output = builder.build();
+        // The exporter's own value wins over a site label of the same name
+        assert!(output.contains("all_smi_a{instance=\"node-1\", rack=\"r1\"} 1\n"));
+        assert!(output.contains("all_smi_b_bucket{instance=\"site\", rack=\"r1\", le=\"1.0\"} 1\n"));
+        assert!(output.contains("all_smi_b_count{instance=\"site\", rack=\"r1\"} 1\n"));
+    }
+
+    #[test]
+    fn test_suffixed_samples_join_typed_family() {
+        let mut builder = MetricBuilder::new();
+        builder
+            .type_("all_smi_collect_seconds", "histogram")
+            .metric("all_smi_collect_seconds_bucket", &[("le", "+Inf")], 1)
+            .metric("all_smi_collect_seconds_sum", &[], 0.5)
+            .metric("all_smi_collect_seconds_count", &[], 1);
+        let output = builder.build();
+        assert_eq!(output.matches("# TYPE").count(), 1);
+        assert!(output.starts_with("# TYPE all_smi_collect_seconds histogram\n"));
+    }
+}
@@ -0,0 +1,160 @@
+// Copyright 2025 Lablup Inc. and Jeongkyu Shin
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! OpenMetrics 1.0 content negotiation
+//!
+//! `MetricBuilder::build_openmetrics` renders the families; this module
+//! decides when to use it and which `# UNIT` a family carries.
+
+use all_smi::traits::ExportFormat;
+
+/// Content type for the Prometheus text format 0.0.4
+pub const PROMETHEUS_CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// The only OpenMetrics version rendered
+const OPENMETRICS_VERSION: &str = "1.0.0";
+
+/// Content type for the OpenMetrics 1.0 text format
+pub const OPENMETRICS_CONTENT_TYPE: &str =
+    "application/openmetrics-text; version=1.0.0; charset=utf-8";
+
+/// Marker that ends every OpenMetrics exposition
+pub const OPENMETRICS_EOF: &str = "# EOF\n";
+
+/// Metric name suffixes that are exposed as an OpenMetrics `# UNIT`
+const UNIT_SUFFIXES: &[&str] = &[
+    "bytes",
+    "seconds",
+    "microseconds",
+    "celsius",
+    "watts",
+    "volts",
+    "amperes",
+    "mhz",
+    "rpm",
+    "percent",
+];
+
+/// Pick the exposition format from an `Accept` header value.
+/// OpenMetrics is chosen only when the client ranks it at least as high as
+/// plain text, and only for version 1.0.0 or no version at all; anything
+/// else falls back to the Prometheus text format.
+pub fn negotiate_format(accept: Option<&str>) -> ExportFormat {
+    let Some(accept) = accept else {
+        return ExportFormat::Prometheus;
+    };
+
+    let mut openmetrics_q: f32 = 0.0;
+    let mut text_q: f32 = 0.0;
+
+    for media_range in accept.split(',') {
+        let mut params = media_range.split(';').map(str::trim);
+        let media_type = params.next().unwrap_or_default().to_ascii_lowercase();
+        let params: Vec<&str> = params.collect();
+        let param = |name: &str| {
+            params
+                .iter()
+                .filter_map(|p| p.split_once('='))
+                .filter(|(key, _)| key.trim().eq_ignore_ascii_case(name))
+                .map(|(_, value)| value.trim().trim_matches('"'))
+                .next_back()
+        };
+        let q = param("q")
+            .and_then(|v| v.parse::<f32>().ok())
+            .unwrap_or(1.0);
+
+        match media_type.as_str() {
+            "application/openmetrics-text" => {
+                if param("version").is_none_or(|version| version == OPENMETRICS_VERSION) {
+                    openmetrics_q = openmetrics_q.max(q);
+                }
+            }
+            "text/plain" | "text/*" | "*/*" => text_q = text_q.max(q),
+            _ => {}
+        }
+    }
+
+    if openmetrics_q > 0.0 && openmetrics_q >= text_q {
+        ExportFormat::OpenMetrics
+    } else {
+        ExportFormat::Prometheus
+    }
+}
+
+/// Unit of a metric family, derived from its name suffix
+pub fn unit_for(family_name: &str) -> Option<&'static str> {
+    UNIT_SUFFIXES
+        .iter()
+        .find(|unit| {
+            family_name
+                .strip_suffix(*unit)
+                .is_some_and(|prefix| prefix.ends_with('_'))
+        })
+        .copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_negotiate_defaults_to_prometheus() {
+        assert_eq!(negotiate_format(None), ExportFormat::Prometheus);
+        assert_eq!(negotiate_format(Some("*/*")), ExportFormat::Prometheus);
+        assert_eq!(
+            negotiate_format(Some("text/plain;version=0.0.4")),
+            ExportFormat::Prometheus
+        );
+    }
+
+    #[test]
+    fn test_negotiate_prometheus_scraper_header() {
+        let accept = "application/openmetrics-text;version=1.0.0,application/openmetrics-text;version=0.0.1;q=0.75,text/plain;version=0.0.4;q=0.5,*/*;q=0.1";
+        assert_eq!(negotiate_format(Some(accept)), ExportFormat::OpenMetrics);
+    }
+
+    #[test]
+    fn test_negotiate_respects_quality() {
+        let accept = "application/openmetrics-text;q=0.2,text/plain;q=0.9";
+        assert_eq!(negotiate_format(Some(accept)), ExportFormat::Prometheus);
+        let accept = "application/openmetrics-text;q=0";
+        assert_eq!(negotiate_format(Some(accept)), ExportFormat::Prometheus);
+    }
+
+    #[test]
+    fn test_negotiate_openmetrics_version() {
+        assert_eq!(
+            negotiate_format(Some("application/openmetrics-text")),
+            ExportFormat::OpenMetrics
+        );
+        assert_eq!(
+            negotiate_format(Some("application/openmetrics-text; version=\"1.0.0\"")),
+            ExportFormat::OpenMetrics
+        );
+        assert_eq!(
+            negotiate_format(Some("application/openmetrics-text; version=2.0.0")),
+            ExportFormat::Prometheus
+        );
+        let accept = "application/openmetrics-text;version=2.0.0,application/openmetrics-text;version=1.0.0;q=0.8,text/plain;q=0.5";
+        assert_eq!(negotiate_format(Some(accept)), ExportFormat::OpenMetrics);
+    }
+
+    #[test]
+    fn test_unit_for() {
+        assert_eq!(unit_for("all_smi_gpu_memory_used_bytes"), Some("bytes"));
+        assert_eq!(unit_for("all_smi_gpu_frequency_mhz"), Some("mhz"));
+        assert_eq!(unit_for("all_smi_gpu_utilization"), None);
+        assert_eq!(unit_for("all_smi_fakebytes"), None);
+    }
+}
@@ -12,28 +12,216 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+//! Per-process GPU memory series
+//!
+//! By default every process gets its own series, keyed by PID. On shared
+//! nodes that churns through series quickly, so processes can instead be
+//! summed per name, user, container or Slurm job, cut down to the N largest
+//! by GPU memory, and stripped of labels outside an allowlist.
+
+use std::collections::HashMap;
+
 use super::{MetricBuilder, MetricExporter};
+use crate::cli::{ApiArgs, ProcessAggregation};
+use crate::device::container_utils::{get_process_workload, ProcessWorkload};
 use crate::device::ProcessInfo;
 
+/// Labels a process series can carry
+pub const PROCESS_LABELS: &[&str] = &[
+    "pid",
+    "name",
+    "user",
+    "container",
+    "job",
+    "device_id",
+    "device_uuid",
+];
+
+/// Label value of the series that sums processes outside the top N
+const OTHER: &str = "other";
+
+/// How process series are keyed and labelled
+#[derive(Debug, Clone, Default)]
+pub struct ProcessMetricOptions {
+    pub aggregation: ProcessAggregation,
+    /// Keep only the N series using the most GPU memory
+    pub top_n: Option<usize>,
+    /// Labels to keep; `None` keeps every label of the aggregation mode
+    pub labels: Option<Vec<String>>,
+}
+
+impl ProcessMetricOptions {
+    pub fn from_args(args: &ApiArgs) -> Result<Self, String> {
+        if let Some(label) = args
+            .process_labels
+            .iter()
+            .flatten()
+            .find(|label| !PROCESS_LABELS.contains(&label.as_str()))
+        {
+            return Err(format!(
+                "unknown process label '{label}' (expected one of: {})",
+                PROCESS_LABELS.join(", ")
+            ));
+        }
+        Ok(Self {
+            aggregation: args.process_aggregation,
+            top_n: args.process_top_n,
+            labels: args.process_labels.clone(),
+        })
+    }
+
+    /// Labels identifying what a series is summed over
+    fn identity_labels(&self) -> &'static [&'static str] {
+        match self.aggregation {
+            ProcessAggregation::Pid => &["pid", "name"],
+            ProcessAggregation::Name => &["name"],
+            ProcessAggregation::User => &["user"],
+            ProcessAggregation::Container => &["container"],
+            ProcessAggregation::Job => &["job"],
+        }
+    }
+
+    /// Labels of the exported series, in output order
+    fn series_labels(&self) -> Vec<&'static str> {
+        self.identity_labels()
+            .iter()
+            .chain(&["device_id", "device_uuid"])
+            .copied()
+            .filter(|label| {
+                self.labels
+                    .as_ref()
+                    .is_none_or(|allowed| allowed.iter().any(|a| a == label))
+            })
+            .collect()
+    }
+
+    /// Whether a series may stand for more than one process
+    fn aggregates(&self) -> bool {
+        self.aggregation != ProcessAggregation::Pid || self.top_n.is_some() || self.labels.is_some()
+    }
+}
+
+/// Processes summed into one series
+struct ProcessSeries {
+    values: Vec<String>,
+    memory: u64,
+    processes: usize,
+}
+
 pub struct ProcessMetricExporter<'a> {
     pub process_info: &'a [ProcessInfo],
+    options: &'a ProcessMetricOptions,
 }
 
 impl<'a> ProcessMetricExporter<'a> {
-    pub fn new(process_info: &'a [ProcessInfo]) -> Self {
-        Self { process_info }
+    pub fn new(process_info: &'a [ProcessInfo], options: &'a ProcessMetricOptions) -> Self {
+        Self {
+            process_info,
+            options,
+        }
+    }
+
+    fn label_value(process: &ProcessInfo, workload: &ProcessWorkload, label: &str) -> String {
+        match label {
+            "pid" => process.pid.to_string(),
+            "name" => process.process_name.clone(),
+            "user" => process.user.clone(),
+            "container" => workload.container.clone().unwrap_or_default(),
+            "job" => workload.job.clone().unwrap_or_default(),
+            "device_id" => process.device_id.to_string(),
+            "device_uuid" => process.device_uuid.clone(),
+            _ => String::new(),
+        }
+    }
+
+    /// Sum the processes per label set, in first-seen order
+    fn group(&self, labels: &[&str]) -> Vec<ProcessSeries> {
+        let needs_workload = labels.iter().any(|l| matches!(*l, "container" | "job"));
+        let mut series: Vec<ProcessSeries> = Vec::new();
+        let mut index: HashMap<Vec<String>, usize> = HashMap::new();
+
+        for process in self.process_info {
+            let workload = if needs_workload {
+                get_process_workload(process.pid)
+            } else {
+                ProcessWorkload::default()
+            };
+            let values: Vec<String> = labels
+                .iter()
+                .map(|label| Self::label_value(process, &workload, label))
+                .collect();
+            add_to(&mut series, &mut index, values, process.used_memory, 1);
+        }
+        series
     }
 
-    fn export_process_metrics(&self, builder: &mut MetricBuilder, process: &ProcessInfo) {
-        let pid_str = process.pid.to_string();
-        let device_id_str = process.device_id.to_string();
+    /// Keep the `top_n` largest series and fold the rest into `other`
+    /// series, one per device when device labels are kept
+    fn truncate(&self, labels: &[&str], series: Vec<ProcessSeries>) -> Vec<ProcessSeries> {
+        let Some(top_n) = self.options.top_n else {
+            return series;
+        };
+        if series.len() <= top_n {
+            return series;
+        }
 
-        let labels = [
-            ("pid", pid_str.as_str()),
-            ("name", process.process_name.as_str()),
-            ("device_id", device_id_str.as_str()),
-            ("device_uuid", process.device_uuid.as_str()),
-        ];
+        let mut series = series;
+        series.sort_by_key(|entry| std::cmp::Reverse(entry.memory));
+        let rest = series.split_off(top_n);
+
+        let identity = self.options.identity_labels();
+        let mut index = HashMap::new();
+        let mut others = Vec::new();
+        for mut entry in rest {
+            for (value, label) in entry.values.iter_mut().zip(labels) {
+                if identity.contains(label) {
+                    *value = OTHER.to_string();
+                }
+            }
+            add_to(
+                &mut others,
+                &mut index,
+                entry.values,
+                entry.memory,
+                entry.processes,
+            );
+        }
+        series.extend(others);
+        series
+    }
+}
+
+fn add_to(
+    series: &mut Vec<ProcessSeries>,
+    index: &mut HashMap<Vec<String>, usize>,
+    values: Vec<String>,
+    memory: u64,
+    processes: usize,
+) {
+    match index.get(&values) {
+        Some(&idx) => {
+            series[idx].memory += memory;
+            series[idx].processes += processes;
+        }
+        None => {
+            index.insert(values.clone(), series.len());
+            series.push(ProcessSeries {
+                values,
+                memory,
+                processes,
+            });
+        }
+    }
+}
+
+impl<'a> MetricExporter for ProcessMetricExporter<'a> {
+    fn export_to(&self, builder: &mut MetricBuilder) {
+        if self.process_info.is_empty() {
+            return;
+        }
+
+        let labels = self.options.series_labels();
+        let series = self.truncate(&labels, self.group(&labels));
 
         // Process memory usage
         builder
@@ -41,27 +229,145 @@ impl<'a> ProcessMetricExporter<'a> {
                 "all_smi_process_memory_used_bytes",
                 "Process memory used in bytes",
             )
-            .type_("all_smi_process_memory_used_bytes", "gauge")
-            .metric(
+            .type_("all_smi_process_memory_used_bytes", "gauge");
+        if self.options.aggregates() {
+            builder
+                .help(
+                    "all_smi_processes",
+                    "Processes summed into the process memory series",
+                )
+                .type_("all_smi_processes", "gauge");
+        }
+
+        for entry in &series {
+            let series_labels: Vec<(&str, &str)> = labels
+                .iter()
+                .copied()
+                .zip(entry.values.iter().map(String::as_str))
+                .collect();
+            builder.metric(
                 "all_smi_process_memory_used_bytes",
-                &labels,
-                process.used_memory,
+                &series_labels,
+                entry.memory,
             );
+            if self.options.aggregates() {
+                builder.metric("all_smi_processes", &series_labels, entry.processes);
+            }
+        }
     }
 }
 
-impl<'a> MetricExporter for ProcessMetricExporter<'a> {
-    fn export_metrics(&self) -> String {
-        if self.process_info.is_empty() {
-            return String::new();
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn process(
+        pid: u32,
+        name: &str,
+        user: &str,
+        device_id: usize,
+        used_memory: u64,
+    ) -> ProcessInfo {
+        ProcessInfo {
+            host_id: String::new(),
+            device_id,
+            device_uuid: format!("GPU-{device_id}"),
+            pid,
+            process_name: name.to_string(),
+            used_memory,
+            cpu_percent: 0.0,
+            memory_percent: 0.0,
+            memory_rss: 0,
+            memory_vms: 0,
+            user: user.to_string(),
+            state: "S".to_string(),
+            start_time: String::new(),
+            cpu_time: 0,
+            command: name.to_string(),
+            ppid: 1,
+            threads: 1,
+            uses_gpu: true,
+            priority: 20,
+            nice_value: 0,
+            gpu_utilization: 0.0,
         }
+    }
+
+    fn processes() -> Vec<ProcessInfo> {
+        vec![
+            process(100, "python", "alice", 0, 4000),
+            process(101, "python", "bob", 0, 1000),
+            process(102, "python", "alice", 1, 2000),
+            process(103, "torchrun", "alice", 1, 500),
+        ]
+    }
 
+    fn export(options: &ProcessMetricOptions) -> String {
+        let processes = processes();
         let mut builder = MetricBuilder::new();
+        ProcessMetricExporter::new(&processes, options).export_to(&mut builder);
+        builder.build()
+    }
 
-        for process in self.process_info {
-            self.export_process_metrics(&mut builder, process);
-        }
+    fn samples(output: &str, name: &str) -> Vec<String> {
+        output
+            .lines()
+            .filter(|line| line.starts_with(&format!("{name}{{")))
+            .map(str::to_string)
+            .collect()
+    }
 
-        builder.build()
+    #[test]
+    fn test_per_pid_by_default() {
+        let output = export(&ProcessMetricOptions::default());
+        let memory = samples(&output, "all_smi_process_memory_used_bytes");
+        assert_eq!(memory.len(), 4);
+        assert_eq!(
+            memory[0],
+            "all_smi_process_memory_used_bytes{pid=\"100\", name=\"python\", device_id=\"0\", device_uuid=\"GPU-0\"} 4000"
+        );
+        assert!(!output.contains("all_smi_processes"));
+    }
+
+    #[test]
+    fn test_aggregate_per_user_without_device_labels() {
+        let options = ProcessMetricOptions {
+            aggregation: ProcessAggregation::User,
+            labels: Some(vec!["user".to_string()]),
+            ..Default::default()
+        };
+        let output = export(&options);
+        assert_eq!(
+            samples(&output, "all_smi_process_memory_used_bytes"),
+            [
+                "all_smi_process_memory_used_bytes{user=\"alice\"} 6500",
+                "all_smi_process_memory_used_bytes{user=\"bob\"} 1000",
+            ]
+        );
+        assert_eq!(
+            samples(&output, "all_smi_processes"),
+            [
+                "all_smi_processes{user=\"alice\"} 3",
+                "all_smi_processes{user=\"bob\"} 1",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_top_n_folds_the_rest_into_other() {
+        let options = ProcessMetricOptions {
+            top_n: Some(1),
+            labels: Some(vec!["pid".to_string(), "name".to_string()]),
+            ..Default::default()
+        };
+        let output = export(&options);
+        assert_eq!(
+            samples(&output, "all_smi_process_memory_used_bytes"),
+            [
+                "all_smi_process_memory_used_bytes{pid=\"100\", name=\"python\"} 4000",
+                "all_smi_process_memory_used_bytes{pid=\"other\", name=\"other\"} 3500",
+            ]
+        );
+        assert!(output.contains("all_smi_processes{pid=\"other\", name=\"other\"} 3\n"));
     }
 }
@@ -0,0 +1,122 @@
+// Copyright 2025 Lablup Inc. and Jeongkyu Shin
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Per-reader collection status: staleness, data age and deadline overruns
+
+use super::{MetricBuilder, MetricExporter};
+use crate::app_state::ReaderStatus;
+
+pub struct ReaderMetricExporter<'a> {
+    reader_status: &'a [ReaderStatus],
+    hostname: String,
+}
+
+impl<'a> ReaderMetricExporter<'a> {
+    pub fn new(reader_status: &'a [ReaderStatus]) -> Self {
+        Self {
+            reader_status,
+            hostname: crate::utils::get_hostname(),
+        }
+    }
+}
+
+impl<'a> MetricExporter for ReaderMetricExporter<'a> {
+    fn export_to(&self, builder: &mut MetricBuilder) {
+        if self.reader_status.is_empty() {
+            return;
+        }
+
+        builder
+            .help(
+                "all_smi_reader_stale",
+                "Whether the reader missed its deadline and its last data is being served",
+            )
+            .type_("all_smi_reader_stale", "gauge")
+            .help(
+                "all_smi_reader_data_age_seconds",
+                "Seconds since the reader last returned data",
+            )
+            .type_("all_smi_reader_data_age_seconds", "gauge")
+            .help(
+                "all_smi_reader_timeouts_total",
+                "Reader calls that exceeded the deadline",
+            )
+            .type_("all_smi_reader_timeouts_total", "counter");
+
+        for status in self.reader_status {
+            let labels = [
+                ("hostname", self.hostname.as_str()),
+                ("kind", status.kind),
+                ("reader", status.name),
+            ];
+            builder.metric("all_smi_reader_stale", &labels, u8::from(status.stale));
+            if let Some(age) = status.data_age {
+                builder.metric(
+                    "all_smi_reader_data_age_seconds",
+                    &labels,
+                    format!("{:.3}", age.as_secs_f64()),
+                );
+            }
+            builder.metric(
+                "all_smi_reader_timeouts_total",
+                &labels,
+                status.timeouts_total,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_reader_metrics() {
+        let status = vec![
+            ReaderStatus {
+                kind: "gpu",
+                name: "NvidiaGpuReader",
+                device_count: 8,
+                stale: true,
+                data_age: Some(Duration::from_millis(12_500)),
+                timeouts_total: 2,
+                call_duration: None,
+            },
+            ReaderStatus {
+                kind: "cpu",
+                name: "LinuxCpuReader",
+                device_count: 1,
+                stale: false,
+                data_age: None,
+                timeouts_total: 0,
+                call_duration: None,
+            },
+        ];
+        let mut builder = MetricBuilder::new();
+        ReaderMetricExporter::new(&status).export_to(&mut builder);
+        let output = builder.build();
+
+        assert!(output.contains("# TYPE all_smi_reader_timeouts_total counter"));
+        assert!(output.contains("kind=\"gpu\", reader=\"NvidiaGpuReader\"} 1\n"));
+        assert!(output.contains("all_smi_reader_data_age_seconds{"));
+        assert!(output.contains("} 12.500\n"));
+        assert!(output.contains("kind=\"cpu\", reader=\"LinuxCpuReader\"} 0\n"));
+        // No age is reported before a reader has returned data
+        assert_eq!(
+            output.matches("all_smi_reader_data_age_seconds{").count(),
+            1
+        );
+    }
+}
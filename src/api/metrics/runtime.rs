@@ -14,7 +14,7 @@
 
 use crate::utils::RuntimeEnvironment;
 
-use super::MetricExporter;
+use super::{MetricBuilder, MetricExporter};
 
 pub struct RuntimeMetricExporter<'a> {
     runtime_env: &'a RuntimeEnvironment,
@@ -31,32 +31,59 @@ impl<'a> RuntimeMetricExporter<'a> {
 }
 
 impl<'a> MetricExporter for RuntimeMetricExporter<'a> {
-    fn export_metrics(&self) -> String {
-        let mut output = String::new();
+    fn export_to(&self, builder: &mut MetricBuilder) {
+        let hostname = self.hostname.as_str();
 
         // Container environment metrics
         if self.runtime_env.container.is_containerized() {
             let runtime_name = self.runtime_env.container.runtime.as_str();
 
             // Container runtime info metric
-            output.push_str(&format!(
-                "# HELP all_smi_container_runtime_info Container runtime environment information\n\
-                 # TYPE all_smi_container_runtime_info gauge\n\
-                 all_smi_container_runtime_info{{hostname=\"{}\",runtime=\"{runtime_name}\",container_id=\"{}\"}} 1\n",
-                self.hostname,
-                self.runtime_env.container.container_id.as_deref().unwrap_or("unknown")
-            ));
+            builder
+                .help(
+                    "all_smi_container_runtime_info",
+                    "Container runtime environment information",
+                )
+                .type_("all_smi_container_runtime_info", "gauge")
+                .metric(
+                    "all_smi_container_runtime_info",
+                    &[
+                        ("hostname", hostname),
+                        ("runtime", runtime_name),
+                        (
+                            "container_id",
+                            self.runtime_env
+                                .container
+                                .container_id
+                                .as_deref()
+                                .unwrap_or("unknown"),
+                        ),
+                    ],
+                    1,
+                );
 
             // Additional Kubernetes-specific metrics
             if let crate::utils::ContainerRuntime::Kubernetes = self.runtime_env.container.runtime {
                 if let Some(pod_name) = &self.runtime_env.container.pod_name {
-                    output.push_str(&format!(
-                        "# HELP all_smi_kubernetes_pod_info Kubernetes pod information\n\
-                         # TYPE all_smi_kubernetes_pod_info gauge\n\
-                         all_smi_kubernetes_pod_info{{hostname=\"{}\",pod_name=\"{pod_name}\",namespace=\"{}\"}} 1\n",
-                        self.hostname,
-                        self.runtime_env.container.namespace.as_deref().unwrap_or("default")
-                    ));
+                    builder
+                        .help("all_smi_kubernetes_pod_info", "Kubernetes pod information")
+                        .type_("all_smi_kubernetes_pod_info", "gauge")
+                        .metric(
+                            "all_smi_kubernetes_pod_info",
+                            &[
+                                ("hostname", hostname),
+                                ("pod_name", pod_name.as_str()),
+                                (
+                                    "namespace",
+                                    self.runtime_env
+                                        .container
+                                        .namespace
+                                        .as_deref()
+                                        .unwrap_or("default"),
+                                ),
+                            ],
+                            1,
+                        );
                 }
             }
         }
@@ -65,25 +92,43 @@ impl<'a> MetricExporter for RuntimeMetricExporter<'a> {
         if self.runtime_env.virtualization.is_virtual {
             let vm_type = self.runtime_env.virtualization.vm_type.as_str();
 
-            output.push_str(&format!(
-                "# HELP all_smi_virtualization_info Virtualization environment information\n\
-                 # TYPE all_smi_virtualization_info gauge\n\
-                 all_smi_virtualization_info{{hostname=\"{}\",vm_type=\"{vm_type}\",hypervisor=\"{}\"}} 1\n",
-                self.hostname,
-                self.runtime_env.virtualization.hypervisor.as_deref().unwrap_or(vm_type)
-            ));
+            builder
+                .help(
+                    "all_smi_virtualization_info",
+                    "Virtualization environment information",
+                )
+                .type_("all_smi_virtualization_info", "gauge")
+                .metric(
+                    "all_smi_virtualization_info",
+                    &[
+                        ("hostname", hostname),
+                        ("vm_type", vm_type),
+                        (
+                            "hypervisor",
+                            self.runtime_env
+                                .virtualization
+                                .hypervisor
+                                .as_deref()
+                                .unwrap_or(vm_type),
+                        ),
+                    ],
+                    1,
+                );
         }
 
         // Combined runtime environment metric (what would be displayed in UI)
         if let Some((name, _color)) = self.runtime_env.display_info() {
-            output.push_str(&format!(
-                "# HELP all_smi_runtime_environment Current runtime environment (container or VM)\n\
-                 # TYPE all_smi_runtime_environment gauge\n\
-                 all_smi_runtime_environment{{hostname=\"{}\",environment=\"{name}\"}} 1\n",
-                self.hostname
-            ));
+            builder
+                .help(
+                    "all_smi_runtime_environment",
+                    "Current runtime environment (container or VM)",
+                )
+                .type_("all_smi_runtime_environment", "gauge")
+                .metric(
+                    "all_smi_runtime_environment",
+                    &[("hostname", hostname), ("environment", name)],
+                    1,
+                );
         }
-
-        output
     }
 }
@@ -12,8 +12,22 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+pub mod auth;
+pub mod collector;
+pub mod exposition;
 pub mod handlers;
+pub mod health;
+pub mod labels;
+pub mod listen;
 pub mod metrics;
+pub mod push;
+pub mod reload;
+pub mod self_metrics;
 pub mod server;
+pub mod snapshot;
+pub mod stream;
+#[cfg(unix)]
+pub mod systemd;
+pub mod tls;
 
 pub use server::*;
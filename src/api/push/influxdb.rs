@@ -0,0 +1,770 @@
+// Copyright 2025 Lablup Inc. and Jeongkyu Shin
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! InfluxDB line-protocol push sink
+//!
+//! Encodes every collection cycle as line protocol and POSTs it in batches to
+//! an InfluxDB v2 `/api/v2/write` endpoint (or anything that speaks it, such
+//! as Telegraf's `influxdb_v2_listener`). A batch that fails is spooled to
+//! disk at once, so the collection loop is never held up by a slow sink; the
+//! spool is retried from the flush timer with exponential backoff and drained
+//! oldest-first before any new batch is sent.
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use reqwest::header::{AUTHORIZATION, CONTENT_TYPE};
+use tokio::sync::watch;
+use tokio::time::Instant;
+
+use super::spool::{self, Spool};
+use super::{Backoff, PushError};
+use crate::api::handlers::SharedState;
+use crate::app_state::AppState;
+use crate::cli::ApiArgs;
+
+/// Environment variable consulted when `--influxdb-token` is not given
+pub const INFLUXDB_TOKEN_ENV: &str = "ALL_SMI_INFLUXDB_TOKEN";
+
+const SPOOL_EXTENSION: &str = "lp";
+const REQUEST_TIMEOUT_SECS: u64 = 10;
+
+#[derive(Debug, Clone)]
+pub struct InfluxDbConfig {
+    /// Full write URL including `org` and `bucket` query parameters
+    pub url: String,
+    pub token: Option<String>,
+    /// Flush as soon as this many lines are pending
+    pub batch_size: usize,
+    /// Flush pending lines at least this often
+    pub flush_interval: Duration,
+    pub spool_dir: PathBuf,
+    pub spool_max_bytes: u64,
+    pub backoff: Backoff,
+}
+
+impl InfluxDbConfig {
+    /// Build the sink configuration, or `None` when no URL was given
+    pub fn from_args(args: &ApiArgs) -> Option<Self> {
+        let url = args.influxdb_url.clone()?;
+        let token = args
+            .influxdb_token
+            .clone()
+            .or_else(|| std::env::var(INFLUXDB_TOKEN_ENV).ok())
+            .filter(|t| !t.is_empty());
+        let spool_dir = args
+            .influxdb_spool_dir
+            .as_ref()
+            .map(PathBuf::from)
+            .unwrap_or_else(|| spool::default_dir("influxdb-spool"));
+
+        Some(Self {
+            url,
+            token,
+            batch_size: args.influxdb_batch_size.max(1),
+            flush_interval: Duration::from_secs(args.influxdb_flush_interval.max(1)),
+            spool_dir,
+            spool_max_bytes: args.influxdb_spool_max_bytes,
+            backoff: Backoff::default(),
+        })
+    }
+}
+
+/// Run the sink until the collection loop goes away
+pub async fn run_influxdb_sink(
+    config: InfluxDbConfig,
+    state: SharedState,
+    mut updates: watch::Receiver<u64>,
+) {
+    tracing::info!("Pushing metrics to InfluxDB at {}", config.url);
+    let batch_size = config.batch_size;
+    let mut flush_timer = tokio::time::interval(config.flush_interval);
+    flush_timer.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    // The first tick completes immediately and there is nothing to flush yet
+    flush_timer.tick().await;
+
+    let mut sink = InfluxDbSink::new(config);
+    loop {
+        tokio::select! {
+            changed = updates.changed() => {
+                if changed.is_err() {
+                    sink.flush().await;
+                    return;
+                }
+                let timestamp_ns = chrono::Utc::now()
+                    .timestamp_nanos_opt()
+                    .unwrap_or_default();
+                let lines = encode_state(&*state.read().await, timestamp_ns);
+                sink.append(lines);
+                if sink.pending_lines >= batch_size {
+                    sink.flush().await;
+                }
+            }
+            _ = flush_timer.tick() => sink.flush().await,
+        }
+    }
+}
+
+pub struct InfluxDbSink {
+    client: reqwest::Client,
+    config: InfluxDbConfig,
+    spool: Option<Spool>,
+    batch: String,
+    pending_lines: usize,
+    failures: u32,
+    retry_at: Option<Instant>,
+}
+
+impl InfluxDbSink {
+    pub fn new(config: InfluxDbConfig) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
+            .build()
+            .unwrap_or_default();
+        let spool = match Spool::open(&config.spool_dir, config.spool_max_bytes, SPOOL_EXTENSION) {
+            Ok(spool) => {
+                if !spool.is_empty() {
+                    tracing::info!(
+                        "Found {} spooled InfluxDB batch(es) ({} bytes) in {}",
+                        spool.len(),
+                        spool.total_bytes(),
+                        spool.dir().display()
+                    );
+                }
+                Some(spool)
+            }
+            Err(e) => {
+                tracing::warn!(
+                    "Failed to open InfluxDB spool at {}: {e}; undeliverable batches will be dropped",
+                    config.spool_dir.display()
+                );
+                None
+            }
+        };
+
+        Self {
+            client,
+            config,
+            spool,
+            batch: String::new(),
+            pending_lines: 0,
+            failures: 0,
+            retry_at: None,
+        }
+    }
+
+    pub fn append(&mut self, lines: LineProtocol) {
+        self.pending_lines += lines.len();
+        self.batch.push_str(lines.as_str());
+    }
+
+    /// Drain the spool, then deliver the pending batch. A batch that cannot be
+    /// delivered goes to the spool so that ordering is preserved; while the
+    /// sink is backing off after a failure nothing is sent.
+    pub async fn flush(&mut self) {
+        let drained = self.ready() && self.drain_spool().await;
+        if self.batch.is_empty() {
+            return;
+        }
+
+        let payload = std::mem::take(&mut self.batch);
+        let lines = std::mem::take(&mut self.pending_lines);
+        if !drained {
+            tracing::debug!("InfluxDB unavailable, spooling {lines} line(s)");
+            self.spool_payload(payload.as_bytes());
+            return;
+        }
+
+        match send(&self.client, &self.config, payload.as_bytes()).await {
+            Ok(()) => {
+                self.failures = 0;
+                self.retry_at = None;
+                tracing::debug!("Wrote {lines} line(s) to InfluxDB");
+            }
+            Err(PushError::Rejected(msg)) => {
+                tracing::warn!("InfluxDB rejected a batch of {lines} line(s), dropping it: {msg}");
+            }
+            Err(PushError::Retryable(msg)) => {
+                let delay = self.config.backoff.delay(self.failures);
+                self.failures = self.failures.saturating_add(1);
+                self.retry_at = Some(Instant::now() + delay);
+                tracing::warn!(
+                    "InfluxDB write failed ({msg}), spooling {lines} line(s); retrying in {delay:?}"
+                );
+                self.spool_payload(payload.as_bytes());
+            }
+        }
+    }
+
+    /// Whether the backoff period after the last failure has passed
+    fn ready(&self) -> bool {
+        self.retry_at.is_none_or(|at| Instant::now() >= at)
+    }
+
+    /// Replay spooled batches oldest-first.
+    /// Returns `false` if the sink is still unreachable.
+    async fn drain_spool(&mut self) -> bool {
+        let Some(spool) = self.spool.as_mut() else {
+            return true;
+        };
+
+        loop {
+            let payload = match spool.peek() {
+                Ok(Some(payload)) => payload,
+                Ok(None) => break,
+                Err(e) => {
+                    tracing::warn!("Discarding unreadable spooled batch: {e}");
+                    spool.pop();
+                    continue;
+                }
+            };
+
+            match send(&self.client, &self.config, &payload).await {
+                Ok(()) => spool.pop(),
+                Err(PushError::Rejected(msg)) => {
+                    tracing::warn!("InfluxDB rejected a spooled batch, dropping it: {msg}");
+                    spool.pop();
+                }
+                Err(PushError::Retryable(msg)) => {
+                    let delay = self.config.backoff.delay(self.failures);
+                    self.failures = self.failures.saturating_add(1);
+                    self.retry_at = Some(Instant::now() + delay);
+                    tracing::debug!("InfluxDB still unavailable ({msg}), retrying in {delay:?}");
+                    return false;
+                }
+            }
+        }
+
+        self.failures = 0;
+        self.retry_at = None;
+        true
+    }
+
+    fn spool_payload(&mut self, payload: &[u8]) {
+        match self.spool.as_mut() {
+            Some(spool) => {
+                if let Err(e) = spool.push(payload) {
+                    tracing::warn!("Failed to spool InfluxDB batch: {e}");
+                }
+            }
+            None => tracing::warn!("No InfluxDB spool available, dropping batch"),
+        }
+    }
+}
+
+async fn send(
+    client: &reqwest::Client,
+    config: &InfluxDbConfig,
+    payload: &[u8],
+) -> Result<(), PushError> {
+    let mut request = client
+        .post(&config.url)
+        .header(CONTENT_TYPE, "text/plain; charset=utf-8")
+        .body(payload.to_vec());
+    if let Some(token) = &config.token {
+        request = request.header(AUTHORIZATION, format!("Token {token}"));
+    }
+
+    let response = request.send().await?;
+    let status = response.status();
+    if status.is_success() {
+        return Ok(());
+    }
+    let body = response.text().await.unwrap_or_default();
+    Err(PushError::from_status(status, &body))
+}
+
+/// Encoded line-protocol points sharing one timestamp
+#[derive(Debug, Default)]
+pub struct LineProtocol {
+    buf: String,
+    lines: usize,
+    timestamp_ns: i64,
+}
+
+impl LineProtocol {
+    pub fn new(timestamp_ns: i64) -> Self {
+        Self {
+            timestamp_ns,
+            ..Default::default()
+        }
+    }
+
+    pub fn point(&mut self, measurement: &str) -> Point<'_> {
+        Point {
+            out: self,
+            measurement: escape_measurement(measurement),
+            tags: Vec::new(),
+            fields: Vec::new(),
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.buf
+    }
+
+    pub fn len(&self) -> usize {
+        self.lines
+    }
+}
+
+/// A single point under construction; written by [`Point::finish`]
+pub struct Point<'a> {
+    out: &'a mut LineProtocol,
+    measurement: String,
+    tags: Vec<(&'static str, String)>,
+    fields: Vec<(&'static str, String)>,
+}
+
+impl Point<'_> {
+    /// Add a tag; empty values are omitted since line protocol cannot carry them
+    pub fn tag(mut self, key: &'static str, value: &str) -> Self {
+        if !value.is_empty() {
+            self.tags.push((key, escape_tag(value)));
+        }
+        self
+    }
+
+    /// Add a float field; NaN and infinities are not representable and are skipped
+    pub fn float(mut self, key: &'static str, value: f64) -> Self {
+        if value.is_finite() {
+            self.fields.push((key, format!("{value}")));
+        }
+        self
+    }
+
+    pub fn float_opt(self, key: &'static str, value: Option<f64>) -> Self {
+        match value {
+            Some(value) => self.float(key, value),
+            None => self,
+        }
+    }
+
+    pub fn int(mut self, key: &'static str, value: impl TryInto<i64>) -> Self {
+        let value = value.try_into().unwrap_or(i64::MAX);
+        self.fields.push((key, format!("{value}i")));
+        self
+    }
+
+    pub fn int_opt(self, key: &'static str, value: Option<impl TryInto<i64>>) -> Self {
+        match value {
+            Some(value) => self.int(key, value),
+            None => self,
+        }
+    }
+
+    pub fn string(mut self, key: &'static str, value: &str) -> Self {
+        self.fields
+            .push((key, format!("\"{}\"", escape_string(value))));
+        self
+    }
+
+    /// Append the point to the output; points without fields are dropped
+    pub fn finish(mut self) {
+        if self.fields.is_empty() {
+            return;
+        }
+        // Sorted tags are what InfluxDB recommends for write performance
+        self.tags.sort_by(|a, b| a.0.cmp(b.0));
+
+        let out = &mut *self.out;
+        out.buf.push_str(&self.measurement);
+        for (key, value) in &self.tags {
+            out.buf.push(',');
+            out.buf.push_str(key);
+            out.buf.push('=');
+            out.buf.push_str(value);
+        }
+        for (i, (key, value)) in self.fields.iter().enumerate() {
+            out.buf.push(if i == 0 { ' ' } else { ',' });
+            out.buf.push_str(key);
+            out.buf.push('=');
+            out.buf.push_str(value);
+        }
+        out.buf.push(' ');
+        out.buf.push_str(&out.timestamp_ns.to_string());
+        out.buf.push('\n');
+        out.lines += 1;
+    }
+}
+
+/// Escape a measurement name (commas and spaces)
+fn escape_measurement(value: &str) -> String {
+    escape(value, &[',', ' '])
+}
+
+/// Escape a tag value (commas, equals signs and spaces)
+fn escape_tag(value: &str) -> String {
+    escape(value, &[',', '=', ' '])
+}
+
+/// Escape a string field value (double quotes and backslashes)
+fn escape_string(value: &str) -> String {
+    escape(value, &['"', '\\'])
+}
+
+fn escape(value: &str, special: &[char]) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            // Line protocol is newline-delimited
+            '\n' | '\r' => escaped.push(' '),
+            c if special.contains(&c) => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Encode the cached collection data as line protocol
+pub fn encode_state(state: &AppState, timestamp_ns: i64) -> LineProtocol {
+    let mut lp = LineProtocol::new(timestamp_ns);
+
+    for (index, info) in state.gpu_info.iter().enumerate() {
+        let measurement = match info.device_type.as_str() {
+            "NPU" | "TPU" => "all_smi_npu",
+            _ => "all_smi_gpu",
+        };
+        lp.point(measurement)
+            .tag("host", &info.hostname)
+            .tag("instance", &info.instance)
+            .tag("uuid", &info.uuid)
+            .tag("index", &index.to_string())
+            .tag("name", &info.name)
+            .tag("type", &info.device_type)
+            .float("utilization", info.utilization)
+            .int("memory_used_bytes", info.used_memory)
+            .int("memory_total_bytes", info.total_memory)
+            .int("temperature_celsius", info.temperature)
+            .float("power_watts", info.power_consumption)
+            .int("frequency_mhz", info.frequency)
+            .float_opt("dla_utilization", info.dla_utilization)
+            .float_opt("tensorcore_utilization", info.tensorcore_utilization)
+            .int_opt("core_count", info.gpu_core_count)
+            .finish();
+    }
+
+    for (index, info) in state.cpu_info.iter().enumerate() {
+        lp.point("all_smi_cpu")
+            .tag("host", &info.hostname)
+            .tag("instance", &info.instance)
+            .tag("index", &index.to_string())
+            .tag("model", &info.cpu_model)
+            .tag("architecture", &info.architecture)
+            .float("utilization", info.utilization)
+            .int("socket_count", info.socket_count)
+            .int("core_count", info.total_cores)
+            .int("thread_count", info.total_threads)
+            .int("base_frequency_mhz", info.base_frequency_mhz)
+            .int("max_frequency_mhz", info.max_frequency_mhz)
+            .int("cache_size_mb", info.cache_size_mb)
+            .int_opt("temperature_celsius", info.temperature)
+            .float_opt("power_watts", info.power_consumption)
+            .finish();
+    }
+
+    for (index, info) in state.memory_info.iter().enumerate() {
+        lp.point("all_smi_memory")
+            .tag("host", &info.hostname)
+            .tag("instance", &info.instance)
+            .tag("index", &index.to_string())
+            .int("total_bytes", info.total_bytes)
+            .int("used_bytes", info.used_bytes)
+            .int("available_bytes", info.available_bytes)
+            .int("free_bytes", info.free_bytes)
+            .int("buffers_bytes", info.buffers_bytes)
+            .int("cached_bytes", info.cached_bytes)
+            .int("swap_total_bytes", info.swap_total_bytes)
+            .int("swap_used_bytes", info.swap_used_bytes)
+            .int("swap_free_bytes", info.swap_free_bytes)
+            .float("utilization", info.utilization)
+            .finish();
+    }
+
+    for info in &state.storage_info {
+        lp.point("all_smi_disk")
+            .tag("host", &info.hostname)
+            .tag("instance", &info.host_id)
+            .tag("index", &info.index.to_string())
+            .tag("mount_point", &info.mount_point)
+            .int("total_bytes", info.total_bytes)
+            .int("available_bytes", info.available_bytes)
+            .int(
+                "used_bytes",
+                info.total_bytes.saturating_sub(info.available_bytes),
+            )
+            .finish();
+    }
+
+    for info in &state.chassis_info {
+        let mut point = lp
+            .point("all_smi_chassis")
+            .tag("host", &info.hostname)
+            .tag("instance", &info.instance)
+            .float_opt("power_watts", info.total_power_watts)
+            .float_opt("inlet_temperature_celsius", info.inlet_temperature)
+            .float_opt("outlet_temperature_celsius", info.outlet_temperature);
+        if let Some(pressure) = &info.thermal_pressure {
+            point = point.string("thermal_pressure", pressure);
+        }
+        point.finish();
+
+        for fan in &info.fan_speeds {
+            lp.point("all_smi_chassis_fan")
+                .tag("host", &info.hostname)
+                .tag("instance", &info.instance)
+                .tag("fan_id", &fan.id.to_string())
+                .tag("fan_name", &fan.name)
+                .int("speed_rpm", fan.speed_rpm)
+                .int("max_rpm", fan.max_rpm)
+                .finish();
+        }
+    }
+
+    lp
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::device::{ChassisInfo, FanInfo, GpuInfo};
+    use axum::extract::State;
+    use axum::http::{HeaderMap, StatusCode};
+    use axum::routing::post;
+    use axum::Router;
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicU16, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    fn gpu(name: &str, device_type: &str) -> GpuInfo {
+        GpuInfo {
+            uuid: format!("{name}-uuid"),
+            time: String::new(),
+            name: name.to_string(),
+            device_type: device_type.to_string(),
+            host_id: "node-1".to_string(),
+            hostname: "node-1".to_string(),
+            instance: "node-1".to_string(),
+            utilization: 42.5,
+            ane_utilization: 0.0,
+            dla_utilization: None,
+            tensorcore_utilization: None,
+            temperature: 60,
+            used_memory: 1024,
+            total_memory: 4096,
+            frequency: 1500,
+            power_consumption: f64::NAN,
+            gpu_core_count: None,
+            detail: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_point_escaping() {
+        let mut lp = LineProtocol::new(123);
+        lp.point("m,eas ure")
+            .tag("name", "NVIDIA H100, 80GB=x")
+            .tag("empty", "")
+            .string("note", "say \"hi\"\\")
+            .float("bad", f64::INFINITY)
+            .int("count", 3u32)
+            .finish();
+        assert_eq!(
+            lp.as_str(),
+            "m\\,eas\\ ure,name=NVIDIA\\ H100\\,\\ 80GB\\=x note=\"say \\\"hi\\\"\\\\\",count=3i 123\n"
+        );
+    }
+
+    #[test]
+    fn test_point_without_fields_is_dropped() {
+        let mut lp = LineProtocol::new(0);
+        lp.point("m").tag("a", "b").float("nan", f64::NAN).finish();
+        assert_eq!(lp.len(), 0);
+        assert_eq!(lp.as_str(), "");
+    }
+
+    #[test]
+    fn test_encode_state() {
+        let mut state = AppState::new();
+        state.gpu_info.push(gpu("H100", "GPU"));
+        state.gpu_info.push(gpu("RBLN-CA12", "NPU"));
+        state.chassis_info.push(ChassisInfo {
+            hostname: "node-1".to_string(),
+            total_power_watts: Some(350.0),
+            fan_speeds: vec![FanInfo {
+                id: 1,
+                name: "Fan 1".to_string(),
+                speed_rpm: 1200,
+                max_rpm: 3000,
+            }],
+            ..Default::default()
+        });
+
+        let lp = encode_state(&state, 1_700_000_000_000_000_000);
+        let lines: Vec<&str> = lp.as_str().lines().collect();
+        assert_eq!(lp.len(), 4);
+        assert_eq!(
+            lines[0],
+            "all_smi_gpu,host=node-1,index=0,instance=node-1,name=H100,type=GPU,uuid=H100-uuid \
+             utilization=42.5,memory_used_bytes=1024i,memory_total_bytes=4096i,\
+             temperature_celsius=60i,frequency_mhz=1500i 1700000000000000000"
+        );
+        assert!(lines[1].starts_with("all_smi_npu,host=node-1,index=1,"));
+        assert!(lines[2].starts_with("all_smi_chassis,host=node-1 power_watts=350 "));
+        assert!(lines[3].starts_with(
+            "all_smi_chassis_fan,fan_id=1,fan_name=Fan\\ 1,host=node-1 speed_rpm=1200i"
+        ));
+    }
+
+    #[derive(Default)]
+    struct Receiver {
+        status: AtomicU16,
+        bodies: Mutex<Vec<String>>,
+        auth: Mutex<Vec<String>>,
+    }
+
+    async fn write_handler(
+        State(receiver): State<Arc<Receiver>>,
+        headers: HeaderMap,
+        body: String,
+    ) -> StatusCode {
+        let status = StatusCode::from_u16(receiver.status.load(Ordering::SeqCst)).unwrap();
+        if let Some(auth) = headers.get("authorization").and_then(|v| v.to_str().ok()) {
+            receiver.auth.lock().unwrap().push(auth.to_string());
+        }
+        if status.is_success() {
+            receiver.bodies.lock().unwrap().push(body);
+        }
+        status
+    }
+
+    async fn start_receiver() -> (Arc<Receiver>, String) {
+        let receiver = Arc::new(Receiver::default());
+        receiver.status.store(204, Ordering::SeqCst);
+        let app = Router::new()
+            .route("/api/v2/write", post(write_handler))
+            .with_state(receiver.clone());
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+        (
+            receiver,
+            format!("http://{addr}/api/v2/write?org=test&bucket=gpu"),
+        )
+    }
+
+    fn test_config(url: String, spool_dir: PathBuf) -> InfluxDbConfig {
+        InfluxDbConfig {
+            url,
+            token: Some("secret".to_string()),
+            batch_size: 100,
+            flush_interval: Duration::from_secs(1),
+            spool_dir,
+            spool_max_bytes: 1024 * 1024,
+            backoff: Backoff {
+                base: Duration::ZERO,
+                max: Duration::ZERO,
+            },
+        }
+    }
+
+    fn lines(text: &str) -> LineProtocol {
+        let mut lp = LineProtocol::new(1);
+        lp.point("m").string("v", text).finish();
+        lp
+    }
+
+    #[tokio::test]
+    async fn test_sink_spools_and_drains_in_order() {
+        let (receiver, url) = start_receiver().await;
+        let spool_dir = tempfile::tempdir().unwrap();
+        let mut sink = InfluxDbSink::new(test_config(url, spool_dir.path().to_path_buf()));
+
+        // Sink is down: the batch is spooled
+        receiver.status.store(503, Ordering::SeqCst);
+        sink.append(lines("first"));
+        sink.flush().await;
+        assert_eq!(sink.spool.as_ref().unwrap().len(), 1);
+        assert_eq!(receiver.auth.lock().unwrap().len(), 1);
+
+        // Still down: the spool is retried and blocks the new batch
+        sink.append(lines("second"));
+        sink.flush().await;
+        assert_eq!(sink.spool.as_ref().unwrap().len(), 2);
+        assert_eq!(receiver.auth.lock().unwrap().len(), 2);
+
+        // Back up: the spool drains oldest-first before the new batch
+        receiver.status.store(204, Ordering::SeqCst);
+        sink.append(lines("third"));
+        sink.flush().await;
+        assert!(sink.spool.as_ref().unwrap().is_empty());
+
+        let bodies = receiver.bodies.lock().unwrap().clone();
+        assert_eq!(
+            bodies,
+            vec![
+                "m v=\"first\" 1\n".to_string(),
+                "m v=\"second\" 1\n".to_string(),
+                "m v=\"third\" 1\n".to_string(),
+            ]
+        );
+        assert!(receiver
+            .auth
+            .lock()
+            .unwrap()
+            .iter()
+            .all(|auth| auth == "Token secret"));
+    }
+
+    #[tokio::test]
+    async fn test_sink_backs_off_without_blocking() {
+        let (receiver, url) = start_receiver().await;
+        let spool_dir = tempfile::tempdir().unwrap();
+        let mut config = test_config(url, spool_dir.path().to_path_buf());
+        config.backoff.base = Duration::from_secs(60);
+        config.backoff.max = Duration::from_secs(60);
+        let mut sink = InfluxDbSink::new(config);
+
+        receiver.status.store(503, Ordering::SeqCst);
+        let started = std::time::Instant::now();
+        sink.append(lines("first"));
+        sink.flush().await;
+        sink.append(lines("second"));
+        sink.flush().await;
+        assert!(started.elapsed() < Duration::from_secs(5));
+
+        // Only the first batch was tried; the second went straight to the spool
+        assert_eq!(receiver.auth.lock().unwrap().len(), 1);
+        assert_eq!(sink.spool.as_ref().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_sink_drops_rejected_batches() {
+        let (receiver, url) = start_receiver().await;
+        let spool_dir = tempfile::tempdir().unwrap();
+        let mut sink = InfluxDbSink::new(test_config(url, spool_dir.path().to_path_buf()));
+
+        receiver.status.store(400, Ordering::SeqCst);
+        sink.append(lines("bad"));
+        sink.flush().await;
+        assert!(sink.spool.as_ref().unwrap().is_empty());
+        assert_eq!(sink.pending_lines, 0);
+        assert_eq!(receiver.auth.lock().unwrap().len(), 1);
+    }
+}
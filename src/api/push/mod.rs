@@ -0,0 +1,120 @@
+// Copyright 2025 Lablup Inc. and Jeongkyu Shin
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Push-based sinks for API mode
+//!
+//! Sinks subscribe to the collection update channel and forward the cached
+//! data to an external system instead of waiting to be scraped.
+
+pub mod influxdb;
+pub mod otlp;
+pub mod remote_write;
+pub mod spool;
+pub mod statsd;
+
+use std::time::Duration;
+
+use reqwest::StatusCode;
+
+/// Outcome of a failed delivery attempt
+#[derive(Debug)]
+pub enum PushError {
+    /// The sink is unreachable or overloaded; the payload may be retried
+    Retryable(String),
+    /// The sink refused the payload; retrying will not help
+    Rejected(String),
+}
+
+impl std::fmt::Display for PushError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Retryable(msg) => write!(f, "retryable: {msg}"),
+            Self::Rejected(msg) => write!(f, "rejected: {msg}"),
+        }
+    }
+}
+
+impl PushError {
+    /// Classify a non-success HTTP response
+    pub fn from_status(status: StatusCode, body: &str) -> Self {
+        let msg = format!("HTTP {status}: {}", body.trim());
+        if status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS {
+            Self::Retryable(msg)
+        } else {
+            Self::Rejected(msg)
+        }
+    }
+}
+
+impl From<reqwest::Error> for PushError {
+    fn from(err: reqwest::Error) -> Self {
+        Self::Retryable(err.to_string())
+    }
+}
+
+/// Exponential backoff between delivery attempts
+#[derive(Debug, Clone, Copy)]
+pub struct Backoff {
+    pub base: Duration,
+    pub max: Duration,
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Self {
+            base: Duration::from_secs(1),
+            max: Duration::from_secs(30),
+        }
+    }
+}
+
+impl Backoff {
+    /// Delay before retry number `retry` (0-based)
+    pub fn delay(&self, retry: u32) -> Duration {
+        self.base
+            .saturating_mul(1u32.checked_shl(retry).unwrap_or(u32::MAX))
+            .min(self.max)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_doubles_until_cap() {
+        let backoff = Backoff::default();
+        assert_eq!(backoff.delay(0), Duration::from_secs(1));
+        assert_eq!(backoff.delay(1), Duration::from_secs(2));
+        assert_eq!(backoff.delay(4), Duration::from_secs(16));
+        assert_eq!(backoff.delay(5), Duration::from_secs(30));
+        assert_eq!(backoff.delay(40), Duration::from_secs(30));
+    }
+
+    #[test]
+    fn test_push_error_classification() {
+        assert!(matches!(
+            PushError::from_status(StatusCode::SERVICE_UNAVAILABLE, ""),
+            PushError::Retryable(_)
+        ));
+        assert!(matches!(
+            PushError::from_status(StatusCode::TOO_MANY_REQUESTS, ""),
+            PushError::Retryable(_)
+        ));
+        assert!(matches!(
+            PushError::from_status(StatusCode::BAD_REQUEST, "bad line"),
+            PushError::Rejected(_)
+        ));
+    }
+}
@@ -0,0 +1,790 @@
+// Copyright 2025 Lablup Inc. and Jeongkyu Shin
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! OpenTelemetry OTLP metrics push
+//!
+//! Every collection cycle is exported as one `ExportMetricsServiceRequest`
+//! holding OTel gauges, over OTLP/HTTP (protobuf) or OTLP/gRPC. Host and
+//! runtime environment details become resource attributes; device identity
+//! becomes data point attributes. Gauges carry the full state each cycle, so a
+//! failed export is logged and not retried.
+
+use std::time::Duration;
+
+use opentelemetry_proto::tonic::collector::metrics::v1::{
+    metrics_service_client::MetricsServiceClient, ExportMetricsServiceRequest,
+};
+use opentelemetry_proto::tonic::common::v1::{any_value, AnyValue, InstrumentationScope, KeyValue};
+use opentelemetry_proto::tonic::metrics::v1::{
+    metric, number_data_point, Gauge, Metric, NumberDataPoint, ResourceMetrics, ScopeMetrics,
+};
+use opentelemetry_proto::tonic::resource::v1::Resource;
+use prost::Message;
+use reqwest::header::CONTENT_TYPE;
+use tokio::sync::watch;
+use tonic::metadata::{MetadataKey, MetadataValue};
+use tonic::transport::{Channel, ClientTlsConfig, Endpoint};
+
+use super::PushError;
+use crate::api::handlers::SharedState;
+use crate::app_state::AppState;
+use crate::cli::{ApiArgs, OtlpProtocol};
+use crate::utils::get_hostname;
+
+const HTTP_METRICS_PATH: &str = "/v1/metrics";
+const REQUEST_TIMEOUT_SECS: u64 = 10;
+
+#[derive(Debug, Clone)]
+pub struct OtlpConfig {
+    /// Collector base URL, e.g. `http://localhost:4318` (HTTP) or
+    /// `http://localhost:4317` (gRPC)
+    pub endpoint: String,
+    pub protocol: OtlpProtocol,
+    /// Extra request headers (HTTP) or metadata (gRPC), e.g. for auth
+    pub headers: Vec<(String, String)>,
+}
+
+impl OtlpConfig {
+    /// Build the exporter configuration, or `None` when no endpoint was given
+    pub fn from_args(args: &ApiArgs) -> Option<Self> {
+        let endpoint = args.otlp_endpoint.clone()?;
+        let headers = args
+            .otlp_headers
+            .iter()
+            .filter_map(|header| match header.split_once('=') {
+                Some((key, value)) if !key.trim().is_empty() => {
+                    Some((key.trim().to_ascii_lowercase(), value.trim().to_string()))
+                }
+                _ => {
+                    tracing::warn!("Ignoring malformed OTLP header '{header}', expected key=value");
+                    None
+                }
+            })
+            .collect();
+
+        Some(Self {
+            endpoint,
+            protocol: args.otlp_protocol,
+            headers,
+        })
+    }
+
+    /// URL that OTLP/HTTP requests are posted to
+    fn http_url(&self) -> String {
+        let base = self.endpoint.trim_end_matches('/');
+        if base.ends_with(HTTP_METRICS_PATH) {
+            base.to_string()
+        } else {
+            format!("{base}{HTTP_METRICS_PATH}")
+        }
+    }
+}
+
+/// Run the exporter until the collection loop goes away
+pub async fn run_otlp_sink(
+    config: OtlpConfig,
+    state: SharedState,
+    mut updates: watch::Receiver<u64>,
+) {
+    let mut exporter = match OtlpExporter::new(config) {
+        Ok(exporter) => exporter,
+        Err(e) => {
+            tracing::error!("Failed to set up OTLP exporter: {e}");
+            return;
+        }
+    };
+    tracing::info!(
+        "Pushing OTLP metrics to {} over {:?}",
+        exporter.config.endpoint,
+        exporter.config.protocol
+    );
+
+    while updates.changed().await.is_ok() {
+        let request = {
+            let state = state.read().await;
+            build_request(&state, now_unix_nano())
+        };
+        if let Err(e) = exporter.export(request).await {
+            tracing::warn!("OTLP export failed: {e}");
+        }
+    }
+}
+
+pub struct OtlpExporter {
+    config: OtlpConfig,
+    transport: Transport,
+}
+
+enum Transport {
+    Http(reqwest::Client),
+    Grpc(MetricsServiceClient<Channel>),
+}
+
+impl OtlpExporter {
+    pub fn new(config: OtlpConfig) -> Result<Self, String> {
+        let transport = match config.protocol {
+            OtlpProtocol::Http => Transport::Http(
+                reqwest::Client::builder()
+                    .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
+                    .build()
+                    .map_err(|e| e.to_string())?,
+            ),
+            OtlpProtocol::Grpc => {
+                let mut endpoint = Endpoint::from_shared(config.endpoint.clone())
+                    .map_err(|e| format!("invalid endpoint {}: {e}", config.endpoint))?
+                    .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS));
+                if endpoint.uri().scheme_str() == Some("https") {
+                    endpoint = endpoint
+                        .tls_config(ClientTlsConfig::new().with_native_roots())
+                        .map_err(|e| format!("invalid TLS setup for {}: {e}", config.endpoint))?;
+                }
+                // Connects on first use and reconnects after failures
+                Transport::Grpc(MetricsServiceClient::new(endpoint.connect_lazy()))
+            }
+        };
+        Ok(Self { config, transport })
+    }
+
+    pub async fn export(&mut self, request: ExportMetricsServiceRequest) -> Result<(), PushError> {
+        match &mut self.transport {
+            Transport::Http(client) => {
+                let mut builder = client
+                    .post(self.config.http_url())
+                    .header(CONTENT_TYPE, "application/x-protobuf")
+                    .body(request.encode_to_vec());
+                for (key, value) in &self.config.headers {
+                    builder = builder.header(key.as_str(), value.as_str());
+                }
+
+                let response = builder.send().await?;
+                let status = response.status();
+                if status.is_success() {
+                    return Ok(());
+                }
+                let body = response.text().await.unwrap_or_default();
+                Err(PushError::from_status(status, &body))
+            }
+            Transport::Grpc(client) => {
+                let mut grpc_request = tonic::Request::new(request);
+                for (key, value) in &self.config.headers {
+                    match (
+                        MetadataKey::from_bytes(key.as_bytes()),
+                        MetadataValue::try_from(value.as_str()),
+                    ) {
+                        (Ok(key), Ok(value)) => {
+                            grpc_request.metadata_mut().insert(key, value);
+                        }
+                        _ => tracing::debug!("Skipping invalid gRPC metadata '{key}'"),
+                    }
+                }
+
+                client
+                    .export(grpc_request)
+                    .await
+                    .map(|_| ())
+                    .map_err(|status| match status.code() {
+                        tonic::Code::Unavailable
+                        | tonic::Code::DeadlineExceeded
+                        | tonic::Code::ResourceExhausted => {
+                            PushError::Retryable(status.to_string())
+                        }
+                        _ => PushError::Rejected(status.to_string()),
+                    })
+            }
+        }
+    }
+}
+
+fn now_unix_nano() -> u64 {
+    chrono::Utc::now()
+        .timestamp_nanos_opt()
+        .and_then(|ns| u64::try_from(ns).ok())
+        .unwrap_or_default()
+}
+
+/// Build one OTLP export request from the cached collection data
+pub fn build_request(state: &AppState, time_unix_nano: u64) -> ExportMetricsServiceRequest {
+    let mut gauges = GaugeSet::new(time_unix_nano);
+
+    for (index, info) in state.gpu_info.iter().enumerate() {
+        let kind = match info.device_type.as_str() {
+            "NPU" | "TPU" => "npu",
+            _ => "gpu",
+        };
+        let index = index.to_string();
+        let lib_name = info.detail.get("lib_name").map(String::as_str);
+        let attributes = attributes(&[
+            ("instance", info.instance.as_str()),
+            ("uuid", info.uuid.as_str()),
+            ("index", index.as_str()),
+            ("name", info.name.as_str()),
+            ("type", info.device_type.as_str()),
+            ("lib_name", lib_name.unwrap_or_default()),
+        ]);
+        let metric = |name: &str| format!("all_smi.{kind}.{name}");
+
+        gauges.add(
+            &metric("utilization"),
+            "Device utilization",
+            "%",
+            &attributes,
+            info.utilization,
+        );
+        gauges.add(
+            &metric("memory.used"),
+            "Device memory in use",
+            "By",
+            &attributes,
+            info.used_memory as f64,
+        );
+        gauges.add(
+            &metric("memory.total"),
+            "Total device memory",
+            "By",
+            &attributes,
+            info.total_memory as f64,
+        );
+        gauges.add(
+            &metric("temperature"),
+            "Device temperature",
+            "Cel",
+            &attributes,
+            info.temperature as f64,
+        );
+        gauges.add(
+            &metric("power"),
+            "Device power consumption",
+            "W",
+            &attributes,
+            info.power_consumption,
+        );
+        gauges.add(
+            &metric("frequency"),
+            "Device clock frequency",
+            "MHz",
+            &attributes,
+            info.frequency as f64,
+        );
+    }
+
+    for (index, info) in state.cpu_info.iter().enumerate() {
+        let index = index.to_string();
+        let attributes = attributes(&[
+            ("instance", info.instance.as_str()),
+            ("index", index.as_str()),
+            ("model", info.cpu_model.as_str()),
+        ]);
+        gauges.add(
+            "all_smi.cpu.utilization",
+            "CPU utilization",
+            "%",
+            &attributes,
+            info.utilization,
+        );
+        gauges.add(
+            "all_smi.cpu.core_count",
+            "Logical CPU cores",
+            "{core}",
+            &attributes,
+            info.total_cores as f64,
+        );
+        if let Some(temperature) = info.temperature {
+            gauges.add(
+                "all_smi.cpu.temperature",
+                "CPU temperature",
+                "Cel",
+                &attributes,
+                temperature as f64,
+            );
+        }
+        if let Some(power) = info.power_consumption {
+            gauges.add(
+                "all_smi.cpu.power",
+                "CPU power consumption",
+                "W",
+                &attributes,
+                power,
+            );
+        }
+    }
+
+    for info in &state.memory_info {
+        let attributes = attributes(&[("instance", info.instance.as_str())]);
+        gauges.add(
+            "all_smi.memory.total",
+            "Total system memory",
+            "By",
+            &attributes,
+            info.total_bytes as f64,
+        );
+        gauges.add(
+            "all_smi.memory.used",
+            "System memory in use",
+            "By",
+            &attributes,
+            info.used_bytes as f64,
+        );
+        gauges.add(
+            "all_smi.memory.available",
+            "Available system memory",
+            "By",
+            &attributes,
+            info.available_bytes as f64,
+        );
+        gauges.add(
+            "all_smi.memory.swap.used",
+            "Swap space in use",
+            "By",
+            &attributes,
+            info.swap_used_bytes as f64,
+        );
+        gauges.add(
+            "all_smi.memory.utilization",
+            "System memory utilization",
+            "%",
+            &attributes,
+            info.utilization,
+        );
+    }
+
+    for info in &state.chassis_info {
+        let attributes = attributes(&[("instance", info.instance.as_str())]);
+        if let Some(power) = info.total_power_watts {
+            gauges.add(
+                "all_smi.chassis.power",
+                "Total chassis power consumption",
+                "W",
+                &attributes,
+                power,
+            );
+        }
+        if let Some(inlet) = info.inlet_temperature {
+            gauges.add(
+                "all_smi.chassis.inlet_temperature",
+                "Chassis inlet temperature",
+                "Cel",
+                &attributes,
+                inlet,
+            );
+        }
+        if let Some(outlet) = info.outlet_temperature {
+            gauges.add(
+                "all_smi.chassis.outlet_temperature",
+                "Chassis outlet temperature",
+                "Cel",
+                &attributes,
+                outlet,
+            );
+        }
+        for fan in &info.fan_speeds {
+            let fan_id = fan.id.to_string();
+            let fan_attributes = self::attributes(&[
+                ("instance", info.instance.as_str()),
+                ("fan_id", fan_id.as_str()),
+                ("fan_name", fan.name.as_str()),
+            ]);
+            gauges.add(
+                "all_smi.chassis.fan.speed",
+                "Fan speed",
+                "{rpm}",
+                &fan_attributes,
+                fan.speed_rpm as f64,
+            );
+        }
+    }
+
+    ExportMetricsServiceRequest {
+        resource_metrics: vec![ResourceMetrics {
+            resource: Some(resource(state)),
+            scope_metrics: vec![ScopeMetrics {
+                scope: Some(InstrumentationScope {
+                    name: env!("CARGO_PKG_NAME").to_string(),
+                    version: env!("CARGO_PKG_VERSION").to_string(),
+                    ..Default::default()
+                }),
+                metrics: gauges.metrics,
+                ..Default::default()
+            }],
+            ..Default::default()
+        }],
+    }
+}
+
+/// Resource attributes describing the host and the runtime environment,
+/// using OpenTelemetry semantic convention names where one exists
+fn resource(state: &AppState) -> Resource {
+    let container = &state.runtime_environment.container;
+    let virtualization = &state.runtime_environment.virtualization;
+    let hostname = get_hostname();
+
+    let mut pairs = vec![
+        ("service.name", env!("CARGO_PKG_NAME")),
+        ("service.version", env!("CARGO_PKG_VERSION")),
+        ("host.name", hostname.as_str()),
+    ];
+    if container.is_containerized() {
+        pairs.push(("container.runtime.name", container.runtime.as_str()));
+    }
+    if let Some(id) = container.container_id.as_deref() {
+        pairs.push(("container.id", id));
+    }
+    if let Some(pod) = container.pod_name.as_deref() {
+        pairs.push(("k8s.pod.name", pod));
+    }
+    if let Some(namespace) = container.namespace.as_deref() {
+        pairs.push(("k8s.namespace.name", namespace));
+    }
+    if virtualization.is_virtual {
+        pairs.push(("all_smi.virtualization", virtualization.vm_type.as_str()));
+    }
+    if let Some(hypervisor) = virtualization.hypervisor.as_deref() {
+        pairs.push(("all_smi.hypervisor", hypervisor));
+    }
+
+    Resource {
+        attributes: attributes(&pairs),
+        ..Default::default()
+    }
+}
+
+fn attributes(pairs: &[(&str, &str)]) -> Vec<KeyValue> {
+    pairs
+        .iter()
+        .filter(|(_, value)| !value.is_empty())
+        .map(|(key, value)| KeyValue {
+            key: key.to_string(),
+            value: Some(AnyValue {
+                value: Some(any_value::Value::StringValue(value.to_string())),
+            }),
+        })
+        .collect()
+}
+
+/// Gauges keyed by metric name, in first-seen order
+struct GaugeSet {
+    time_unix_nano: u64,
+    metrics: Vec<Metric>,
+}
+
+impl GaugeSet {
+    fn new(time_unix_nano: u64) -> Self {
+        Self {
+            time_unix_nano,
+            metrics: Vec::new(),
+        }
+    }
+
+    fn add(
+        &mut self,
+        name: &str,
+        description: &str,
+        unit: &str,
+        attributes: &[KeyValue],
+        value: f64,
+    ) {
+        if !value.is_finite() {
+            return;
+        }
+
+        let position = match self.metrics.iter().position(|m| m.name == name) {
+            Some(position) => position,
+            None => {
+                self.metrics.push(Metric {
+                    name: name.to_string(),
+                    description: description.to_string(),
+                    unit: unit.to_string(),
+                    data: Some(metric::Data::Gauge(Gauge::default())),
+                    ..Default::default()
+                });
+                self.metrics.len() - 1
+            }
+        };
+
+        if let Some(metric::Data::Gauge(gauge)) = &mut self.metrics[position].data {
+            gauge.data_points.push(NumberDataPoint {
+                attributes: attributes.to_vec(),
+                time_unix_nano: self.time_unix_nano,
+                value: Some(number_data_point::Value::AsDouble(value)),
+                ..Default::default()
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::device::{ChassisInfo, GpuInfo, MemoryInfo};
+    use axum::body::Bytes;
+    use axum::extract::State;
+    use axum::http::{HeaderMap, StatusCode};
+    use axum::routing::post;
+    use axum::Router;
+    use opentelemetry_proto::tonic::collector::metrics::v1::metrics_service_server::{
+        MetricsService, MetricsServiceServer,
+    };
+    use opentelemetry_proto::tonic::collector::metrics::v1::ExportMetricsServiceResponse;
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+
+    fn test_state() -> AppState {
+        let mut state = AppState::new();
+        let mut detail = HashMap::new();
+        detail.insert("lib_name".to_string(), "CUDA".to_string());
+        for index in 0..2 {
+            state.gpu_info.push(GpuInfo {
+                uuid: format!("GPU-{index}"),
+                time: String::new(),
+                name: "NVIDIA H100".to_string(),
+                device_type: "GPU".to_string(),
+                host_id: "node-1".to_string(),
+                hostname: "node-1".to_string(),
+                instance: "node-1".to_string(),
+                utilization: 10.0 * (index + 1) as f64,
+                ane_utilization: 0.0,
+                dla_utilization: None,
+                tensorcore_utilization: None,
+                temperature: 60,
+                used_memory: 1024,
+                total_memory: 4096,
+                frequency: 1500,
+                power_consumption: f64::NAN,
+                gpu_core_count: None,
+                detail: detail.clone(),
+            });
+        }
+        state.memory_info.push(MemoryInfo {
+            host_id: "node-1".to_string(),
+            hostname: "node-1".to_string(),
+            instance: "node-1".to_string(),
+            total_bytes: 8192,
+            used_bytes: 4096,
+            available_bytes: 4096,
+            free_bytes: 4096,
+            buffers_bytes: 0,
+            cached_bytes: 0,
+            swap_total_bytes: 0,
+            swap_used_bytes: 0,
+            swap_free_bytes: 0,
+            utilization: 50.0,
+            time: String::new(),
+        });
+        state.chassis_info.push(ChassisInfo {
+            total_power_watts: Some(350.0),
+            ..Default::default()
+        });
+        state
+    }
+
+    fn find_metric<'a>(request: &'a ExportMetricsServiceRequest, name: &str) -> Option<&'a Metric> {
+        request.resource_metrics[0].scope_metrics[0]
+            .metrics
+            .iter()
+            .find(|m| m.name == name)
+    }
+
+    fn gauge_points(metric: &Metric) -> &[NumberDataPoint] {
+        match &metric.data {
+            Some(metric::Data::Gauge(gauge)) => &gauge.data_points,
+            _ => panic!("{} is not a gauge", metric.name),
+        }
+    }
+
+    fn string_attribute<'a>(attributes: &'a [KeyValue], key: &str) -> Option<&'a str> {
+        attributes.iter().find(|kv| kv.key == key).and_then(|kv| {
+            match kv.value.as_ref()?.value.as_ref()? {
+                any_value::Value::StringValue(s) => Some(s.as_str()),
+                _ => None,
+            }
+        })
+    }
+
+    #[test]
+    fn test_build_request() {
+        let request = build_request(&test_state(), 42);
+
+        let resource = request.resource_metrics[0].resource.as_ref().unwrap();
+        assert_eq!(
+            string_attribute(&resource.attributes, "service.name"),
+            Some("all-smi")
+        );
+        assert!(string_attribute(&resource.attributes, "host.name").is_some());
+
+        let utilization = find_metric(&request, "all_smi.gpu.utilization").unwrap();
+        assert_eq!(utilization.unit, "%");
+        let points = gauge_points(utilization);
+        assert_eq!(points.len(), 2);
+        assert_eq!(points[1].time_unix_nano, 42);
+        assert_eq!(
+            points[1].value,
+            Some(number_data_point::Value::AsDouble(20.0))
+        );
+        assert_eq!(
+            string_attribute(&points[1].attributes, "uuid"),
+            Some("GPU-1")
+        );
+        assert_eq!(string_attribute(&points[1].attributes, "index"), Some("1"));
+        assert_eq!(
+            string_attribute(&points[1].attributes, "lib_name"),
+            Some("CUDA")
+        );
+
+        // NaN power is not representable and produces no gauge
+        assert!(find_metric(&request, "all_smi.gpu.power").is_none());
+        assert_eq!(
+            find_metric(&request, "all_smi.memory.used").unwrap().unit,
+            "By"
+        );
+        assert!(find_metric(&request, "all_smi.chassis.power").is_some());
+    }
+
+    #[test]
+    fn test_http_url() {
+        let mut config = OtlpConfig {
+            endpoint: "http://localhost:4318/".to_string(),
+            protocol: OtlpProtocol::Http,
+            headers: Vec::new(),
+        };
+        assert_eq!(config.http_url(), "http://localhost:4318/v1/metrics");
+        config.endpoint = "http://gateway/otlp/v1/metrics".to_string();
+        assert_eq!(config.http_url(), "http://gateway/otlp/v1/metrics");
+    }
+
+    type Received = Arc<Mutex<Vec<(ExportMetricsServiceRequest, Option<String>)>>>;
+
+    async fn http_receiver(
+        State(received): State<Received>,
+        headers: HeaderMap,
+        body: Bytes,
+    ) -> StatusCode {
+        let content_type = headers.get("content-type").and_then(|v| v.to_str().ok());
+        if content_type != Some("application/x-protobuf") {
+            return StatusCode::UNSUPPORTED_MEDIA_TYPE;
+        }
+        let Ok(request) = ExportMetricsServiceRequest::decode(body) else {
+            return StatusCode::BAD_REQUEST;
+        };
+        let auth = headers
+            .get("authorization")
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string);
+        received.lock().unwrap().push((request, auth));
+        StatusCode::OK
+    }
+
+    #[tokio::test]
+    async fn test_export_over_http() {
+        let received = Received::default();
+        let app = Router::new()
+            .route("/v1/metrics", post(http_receiver))
+            .with_state(received.clone());
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+
+        let mut exporter = OtlpExporter::new(OtlpConfig {
+            endpoint: format!("http://{addr}"),
+            protocol: OtlpProtocol::Http,
+            headers: vec![("authorization".to_string(), "Bearer t0ken".to_string())],
+        })
+        .unwrap();
+        exporter
+            .export(build_request(&test_state(), 1))
+            .await
+            .unwrap();
+
+        let received = received.lock().unwrap();
+        assert_eq!(received.len(), 1);
+        let (request, auth) = &received[0];
+        assert_eq!(auth.as_deref(), Some("Bearer t0ken"));
+        assert!(find_metric(request, "all_smi.gpu.memory.used").is_some());
+    }
+
+    struct GrpcReceiver(Received);
+
+    #[tonic::async_trait]
+    impl MetricsService for GrpcReceiver {
+        async fn export(
+            &self,
+            request: tonic::Request<ExportMetricsServiceRequest>,
+        ) -> Result<tonic::Response<ExportMetricsServiceResponse>, tonic::Status> {
+            let auth = request
+                .metadata()
+                .get("authorization")
+                .and_then(|v| v.to_str().ok())
+                .map(str::to_string);
+            self.0.lock().unwrap().push((request.into_inner(), auth));
+            Ok(tonic::Response::new(ExportMetricsServiceResponse::default()))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_export_over_grpc() {
+        let received = Received::default();
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let service = MetricsServiceServer::new(GrpcReceiver(received.clone()));
+        tokio::spawn(async move {
+            tonic::transport::Server::builder()
+                .add_service(service)
+                .serve_with_incoming(tonic::transport::server::TcpIncoming::from(listener))
+                .await
+                .unwrap();
+        });
+
+        let mut exporter = OtlpExporter::new(OtlpConfig {
+            endpoint: format!("http://{addr}"),
+            protocol: OtlpProtocol::Grpc,
+            headers: vec![("authorization".to_string(), "Bearer t0ken".to_string())],
+        })
+        .unwrap();
+        exporter
+            .export(build_request(&test_state(), 1))
+            .await
+            .unwrap();
+
+        let received = received.lock().unwrap();
+        assert_eq!(received.len(), 1);
+        let (request, auth) = &received[0];
+        assert_eq!(auth.as_deref(), Some("Bearer t0ken"));
+        let points = gauge_points(find_metric(request, "all_smi.gpu.utilization").unwrap());
+        assert_eq!(points.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_grpc_over_https_uses_tls() {
+        // A plain TCP listener that closes every connection; the exporter
+        // must get as far as a TLS handshake instead of refusing the scheme
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                drop(stream);
+            }
+        });
+
+        let mut exporter = OtlpExporter::new(OtlpConfig {
+            endpoint: format!("https://{addr}"),
+            protocol: OtlpProtocol::Grpc,
+            headers: Vec::new(),
+        })
+        .unwrap();
+        match exporter.export(build_request(&test_state(), 1)).await {
+            Err(PushError::Retryable(msg)) => assert!(msg.contains("tls handshake"), "{msg}"),
+            other => panic!("unexpected result: {other:?}"),
+        }
+    }
+}
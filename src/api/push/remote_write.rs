@@ -0,0 +1,500 @@
+// Copyright 2025 Lablup Inc. and Jeongkyu Shin
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Prometheus remote_write sender
+//!
+//! Each collection cycle runs the same exporters as `/metrics`, so series
+//! names and labels match a scrape exactly. The samples are encoded as a
+//! snappy-compressed `WriteRequest` (remote_write 1.0) and appended to an
+//! on-disk write-ahead queue before anything is sent. The queue is drained
+//! oldest-first; retryable failures back off exponentially and keep the
+//! queue intact, so nothing is lost while the receiver is unreachable (up to
+//! the queue size limit).
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use prost::Message;
+use reqwest::header::{AUTHORIZATION, CONTENT_ENCODING, CONTENT_TYPE, USER_AGENT};
+use tokio::sync::watch;
+use tokio::time::Instant;
+
+use super::spool::{self, Spool};
+use super::{Backoff, PushError};
+use crate::api::handlers::{collect_metrics, SharedState};
+use crate::api::metrics::{ExportOptions, SharedExportOptions};
+use crate::app_state::AppState;
+use crate::cli::ApiArgs;
+
+/// Environment variable consulted when `--remote-write-token` is not given
+pub const REMOTE_WRITE_TOKEN_ENV: &str = "ALL_SMI_REMOTE_WRITE_TOKEN";
+
+const QUEUE_EXTENSION: &str = "rw";
+const REQUEST_TIMEOUT_SECS: u64 = 30;
+
+/// remote_write 1.0 message types (`prometheus/prompb/remote.proto` and
+/// `types.proto`); only the fields the sender fills in are declared
+pub mod prompb {
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct WriteRequest {
+        #[prost(message, repeated, tag = "1")]
+        pub timeseries: Vec<TimeSeries>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct TimeSeries {
+        /// Sorted by name, including `__name__`
+        #[prost(message, repeated, tag = "1")]
+        pub labels: Vec<Label>,
+        #[prost(message, repeated, tag = "2")]
+        pub samples: Vec<Sample>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct Label {
+        #[prost(string, tag = "1")]
+        pub name: String,
+        #[prost(string, tag = "2")]
+        pub value: String,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct Sample {
+        #[prost(double, tag = "1")]
+        pub value: f64,
+        /// Milliseconds since the Unix epoch
+        #[prost(int64, tag = "2")]
+        pub timestamp: i64,
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RemoteWriteConfig {
+    pub url: String,
+    pub token: Option<String>,
+    pub queue_dir: PathBuf,
+    pub queue_max_bytes: u64,
+    pub backoff: Backoff,
+}
+
+impl RemoteWriteConfig {
+    /// Build the sender configuration, or `None` when no URL was given
+    pub fn from_args(args: &ApiArgs) -> Option<Self> {
+        let url = args.remote_write_url.clone()?;
+        let token = args
+            .remote_write_token
+            .clone()
+            .or_else(|| std::env::var(REMOTE_WRITE_TOKEN_ENV).ok())
+            .filter(|t| !t.is_empty());
+        let queue_dir = args
+            .remote_write_queue_dir
+            .as_ref()
+            .map(PathBuf::from)
+            .unwrap_or_else(|| spool::default_dir("remote-write-queue"));
+
+        Some(Self {
+            url,
+            token,
+            queue_dir,
+            queue_max_bytes: args.remote_write_queue_max_bytes,
+            backoff: Backoff::default(),
+        })
+    }
+}
+
+/// Run the sender until the collection loop goes away
+pub async fn run_remote_write_sender(
+    config: RemoteWriteConfig,
+    state: SharedState,
+    options: SharedExportOptions,
+    mut updates: watch::Receiver<u64>,
+) {
+    tracing::info!("Sending Prometheus remote_write to {}", config.url);
+    let mut sender = RemoteWriteSender::new(config);
+
+    while updates.changed().await.is_ok() {
+        let timestamp_ms = chrono::Utc::now().timestamp_millis();
+        let payload = {
+            let state = state.read().await;
+            encode_write_request(&state, &*options.read().await, timestamp_ms)
+        };
+        match payload {
+            Ok(payload) => sender.enqueue(payload).await,
+            Err(e) => tracing::warn!("Failed to compress remote_write payload: {e}"),
+        }
+        if sender.ready() {
+            sender.drain().await;
+        }
+    }
+}
+
+/// Encode the cached data as a snappy-compressed `WriteRequest`
+pub fn encode_write_request(
+    state: &AppState,
+    options: &ExportOptions,
+    timestamp_ms: i64,
+) -> Result<Vec<u8>, snap::Error> {
+    let request = build_write_request(state, options, timestamp_ms);
+    snap::raw::Encoder::new().compress_vec(&request.encode_to_vec())
+}
+
+/// Convert the `/metrics` samples into remote_write time series
+pub fn build_write_request(
+    state: &AppState,
+    options: &ExportOptions,
+    timestamp_ms: i64,
+) -> prompb::WriteRequest {
+    let builder = collect_metrics(state, options);
+    let timeseries = builder
+        .samples()
+        .map(|(name, labels, value)| {
+            let mut series_labels: Vec<prompb::Label> = labels
+                .iter()
+                .map(|(name, value)| prompb::Label {
+                    name: name.clone(),
+                    value: value.clone(),
+                })
+                .collect();
+            series_labels.push(prompb::Label {
+                name: "__name__".to_string(),
+                value: name.to_string(),
+            });
+            series_labels.sort_by(|a, b| a.name.cmp(&b.name));
+
+            prompb::TimeSeries {
+                labels: series_labels,
+                samples: vec![prompb::Sample {
+                    value,
+                    timestamp: timestamp_ms,
+                }],
+            }
+        })
+        .collect();
+
+    prompb::WriteRequest { timeseries }
+}
+
+pub struct RemoteWriteSender {
+    client: reqwest::Client,
+    config: RemoteWriteConfig,
+    /// Write-ahead queue; `None` if the queue directory is unusable, in which
+    /// case the latest payload is sent directly without retries
+    queue: Option<Spool>,
+    unqueued: Option<Vec<u8>>,
+    failures: u32,
+    retry_at: Option<Instant>,
+}
+
+impl RemoteWriteSender {
+    pub fn new(config: RemoteWriteConfig) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
+            .build()
+            .unwrap_or_default();
+        let queue = match Spool::open(&config.queue_dir, config.queue_max_bytes, QUEUE_EXTENSION) {
+            Ok(queue) => {
+                if !queue.is_empty() {
+                    tracing::info!(
+                        "Resuming {} queued remote_write request(s) ({} bytes) from {}",
+                        queue.len(),
+                        queue.total_bytes(),
+                        queue.dir().display()
+                    );
+                }
+                Some(queue)
+            }
+            Err(e) => {
+                tracing::warn!(
+                    "Failed to open remote_write queue at {}: {e}; sending without a queue",
+                    config.queue_dir.display()
+                );
+                None
+            }
+        };
+
+        Self {
+            client,
+            config,
+            queue,
+            unqueued: None,
+            failures: 0,
+            retry_at: None,
+        }
+    }
+
+    /// Append a payload to the write-ahead queue
+    pub async fn enqueue(&mut self, payload: Vec<u8>) {
+        match self.queue.as_mut() {
+            Some(queue) => {
+                if let Err(e) = queue.push(&payload) {
+                    tracing::warn!("Failed to queue remote_write request: {e}");
+                }
+            }
+            None => self.unqueued = Some(payload),
+        }
+    }
+
+    /// Whether the backoff period after the last failure has passed
+    pub fn ready(&self) -> bool {
+        self.retry_at.is_none_or(|at| Instant::now() >= at)
+    }
+
+    /// Send queued requests oldest-first until the queue is empty or the
+    /// receiver fails
+    pub async fn drain(&mut self) {
+        let Some(queue) = self.queue.as_mut() else {
+            if let Some(payload) = self.unqueued.take() {
+                if let Err(e) = send(&self.client, &self.config, &payload).await {
+                    tracing::warn!("remote_write failed: {e}");
+                }
+            }
+            return;
+        };
+
+        loop {
+            let payload = match queue.peek() {
+                Ok(Some(payload)) => payload,
+                Ok(None) => break,
+                Err(e) => {
+                    tracing::warn!("Discarding unreadable queued remote_write request: {e}");
+                    queue.pop();
+                    continue;
+                }
+            };
+
+            match send(&self.client, &self.config, &payload).await {
+                Ok(()) => queue.pop(),
+                Err(PushError::Rejected(msg)) => {
+                    // Retrying a malformed or out-of-order request would block
+                    // the queue forever
+                    tracing::warn!("remote_write request rejected, dropping it: {msg}");
+                    queue.pop();
+                }
+                Err(PushError::Retryable(msg)) => {
+                    let delay = self.config.backoff.delay(self.failures);
+                    self.failures = self.failures.saturating_add(1);
+                    self.retry_at = Some(Instant::now() + delay);
+                    tracing::warn!(
+                        "remote_write failed ({msg}); {} request(s) queued, retrying in {delay:?}",
+                        queue.len()
+                    );
+                    return;
+                }
+            }
+        }
+
+        self.failures = 0;
+        self.retry_at = None;
+    }
+}
+
+async fn send(
+    client: &reqwest::Client,
+    config: &RemoteWriteConfig,
+    payload: &[u8],
+) -> Result<(), PushError> {
+    let mut request = client
+        .post(&config.url)
+        .header(CONTENT_TYPE, "application/x-protobuf")
+        .header(CONTENT_ENCODING, "snappy")
+        .header("X-Prometheus-Remote-Write-Version", "0.1.0")
+        .header(
+            USER_AGENT,
+            concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION")),
+        )
+        .body(payload.to_vec());
+    if let Some(token) = &config.token {
+        request = request.header(AUTHORIZATION, format!("Bearer {token}"));
+    }
+
+    let response = request.send().await?;
+    let status = response.status();
+    if status.is_success() {
+        return Ok(());
+    }
+    let body = response.text().await.unwrap_or_default();
+    Err(PushError::from_status(status, &body))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::device::{ChassisInfo, GpuInfo};
+    use axum::body::Bytes;
+    use axum::extract::State;
+    use axum::http::{HeaderMap, StatusCode};
+    use axum::routing::post;
+    use axum::Router;
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicU16, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    fn test_state() -> AppState {
+        let mut state = AppState::new();
+        state.gpu_info.push(GpuInfo {
+            uuid: "GPU-1234".to_string(),
+            time: String::new(),
+            name: "NVIDIA H100".to_string(),
+            device_type: "GPU".to_string(),
+            host_id: "node-1".to_string(),
+            hostname: "node-1".to_string(),
+            instance: "node-1".to_string(),
+            utilization: 42.5,
+            ane_utilization: 0.0,
+            dla_utilization: None,
+            tensorcore_utilization: None,
+            temperature: 60,
+            used_memory: 1024,
+            total_memory: 4096,
+            frequency: 1500,
+            power_consumption: 300.0,
+            gpu_core_count: None,
+            detail: HashMap::new(),
+        });
+        state.chassis_info.push(ChassisInfo {
+            hostname: "node-1".to_string(),
+            instance: "node-1".to_string(),
+            total_power_watts: Some(350.0),
+            ..Default::default()
+        });
+        state
+    }
+
+    fn decode(payload: &[u8]) -> prompb::WriteRequest {
+        let raw = snap::raw::Decoder::new().decompress_vec(payload).unwrap();
+        prompb::WriteRequest::decode(raw.as_slice()).unwrap()
+    }
+
+    fn label<'a>(series: &'a prompb::TimeSeries, name: &str) -> Option<&'a str> {
+        series
+            .labels
+            .iter()
+            .find(|l| l.name == name)
+            .map(|l| l.value.as_str())
+    }
+
+    #[test]
+    fn test_write_request_matches_scrape() {
+        let state = test_state();
+        let request = decode(
+            &encode_write_request(&state, &ExportOptions::default(), 1_700_000_000_000).unwrap(),
+        );
+
+        // Every numeric sample line of /metrics becomes one series
+        let scrape = collect_metrics(&state, &ExportOptions::default()).build();
+        let sample_lines = scrape.lines().filter(|l| !l.starts_with('#')).count();
+        assert_eq!(request.timeseries.len(), sample_lines);
+
+        let utilization = request
+            .timeseries
+            .iter()
+            .find(|s| label(s, "__name__") == Some("all_smi_gpu_utilization"))
+            .unwrap();
+        assert_eq!(label(utilization, "uuid"), Some("GPU-1234"));
+        assert_eq!(utilization.samples.len(), 1);
+        assert_eq!(utilization.samples[0].value, 42.5);
+        assert_eq!(utilization.samples[0].timestamp, 1_700_000_000_000);
+
+        for series in &request.timeseries {
+            let names: Vec<&str> = series.labels.iter().map(|l| l.name.as_str()).collect();
+            let mut sorted = names.clone();
+            sorted.sort();
+            assert_eq!(names, sorted, "labels must be sorted");
+        }
+    }
+
+    #[derive(Default)]
+    struct Receiver {
+        status: AtomicU16,
+        requests: Mutex<Vec<prompb::WriteRequest>>,
+    }
+
+    async fn write_handler(
+        State(receiver): State<Arc<Receiver>>,
+        headers: HeaderMap,
+        body: Bytes,
+    ) -> StatusCode {
+        assert_eq!(headers["content-encoding"], "snappy");
+        assert_eq!(headers["x-prometheus-remote-write-version"], "0.1.0");
+        assert_eq!(headers["authorization"], "Bearer secret");
+        let status = StatusCode::from_u16(receiver.status.load(Ordering::SeqCst)).unwrap();
+        if status.is_success() {
+            receiver.requests.lock().unwrap().push(decode(&body));
+        }
+        status
+    }
+
+    #[tokio::test]
+    async fn test_sender_queues_and_retries() {
+        let receiver = Arc::new(Receiver::default());
+        let app = Router::new()
+            .route("/api/v1/write", post(write_handler))
+            .with_state(receiver.clone());
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+
+        let queue_dir = tempfile::tempdir().unwrap();
+        let mut sender = RemoteWriteSender::new(RemoteWriteConfig {
+            url: format!("http://{addr}/api/v1/write"),
+            token: Some("secret".to_string()),
+            queue_dir: queue_dir.path().to_path_buf(),
+            queue_max_bytes: 1024 * 1024,
+            backoff: Backoff {
+                base: Duration::from_millis(1),
+                max: Duration::from_millis(1),
+            },
+        });
+        let state = test_state();
+
+        // Receiver down: both cycles stay in the queue
+        receiver.status.store(503, Ordering::SeqCst);
+        sender
+            .enqueue(encode_write_request(&state, &ExportOptions::default(), 1).unwrap())
+            .await;
+        sender.drain().await;
+        sender
+            .enqueue(encode_write_request(&state, &ExportOptions::default(), 2).unwrap())
+            .await;
+        assert_eq!(sender.queue.as_ref().unwrap().len(), 2);
+        assert!(sender.retry_at.is_some());
+
+        // Receiver back: the queue is replayed in order
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        assert!(sender.ready());
+        receiver.status.store(200, Ordering::SeqCst);
+        sender.drain().await;
+        assert!(sender.queue.as_ref().unwrap().is_empty());
+        assert_eq!(sender.failures, 0);
+
+        let timestamps: Vec<i64> = receiver
+            .requests
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|r| r.timeseries[0].samples[0].timestamp)
+            .collect();
+        assert_eq!(timestamps, vec![1, 2]);
+
+        // Rejected requests are dropped instead of blocking the queue
+        receiver.status.store(400, Ordering::SeqCst);
+        sender
+            .enqueue(encode_write_request(&state, &ExportOptions::default(), 3).unwrap())
+            .await;
+        sender.drain().await;
+        assert!(sender.queue.as_ref().unwrap().is_empty());
+    }
+}
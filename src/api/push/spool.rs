@@ -0,0 +1,323 @@
+// Copyright 2025 Lablup Inc. and Jeongkyu Shin
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Bounded on-disk spool for payloads that could not be delivered
+//!
+//! Each payload is stored as one file named by a monotonically increasing
+//! sequence number, so replay order survives restarts. When the total size
+//! exceeds the configured bound, the oldest payloads are dropped first.
+//!
+//! Spooled payloads are replayed with the sink's credentials, so the
+//! directory must be private: it is created with mode 0700 and refused if
+//! another user owns it or others can write to it.
+
+use std::collections::VecDeque;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+struct SpoolEntry {
+    path: PathBuf,
+    size: u64,
+}
+
+pub struct Spool {
+    dir: PathBuf,
+    extension: &'static str,
+    max_bytes: u64,
+    total_bytes: u64,
+    next_seq: u64,
+    entries: VecDeque<SpoolEntry>,
+}
+
+impl Spool {
+    /// Open (or create) a spool directory and pick up payloads left by a
+    /// previous run.
+    pub fn open(
+        dir: impl Into<PathBuf>,
+        max_bytes: u64,
+        extension: &'static str,
+    ) -> io::Result<Self> {
+        let dir = dir.into();
+        create_private_dir(&dir)?;
+
+        let mut found = Vec::new();
+        for entry in fs::read_dir(&dir)? {
+            let entry = entry?;
+            if !entry.file_type()?.is_file() {
+                continue;
+            }
+            let path = entry.path();
+            if path.extension().and_then(|e| e.to_str()) != Some(extension) {
+                continue;
+            }
+            let Some(seq) = path
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .and_then(|s| s.parse::<u64>().ok())
+            else {
+                continue;
+            };
+            found.push((seq, path, entry.metadata()?.len()));
+        }
+        found.sort_by_key(|(seq, _, _)| *seq);
+
+        let next_seq = found.last().map(|(seq, _, _)| seq + 1).unwrap_or(0);
+        let total_bytes = found.iter().map(|(_, _, size)| size).sum();
+        let entries = found
+            .into_iter()
+            .map(|(_, path, size)| SpoolEntry { path, size })
+            .collect();
+
+        let mut spool = Self {
+            dir,
+            extension,
+            max_bytes,
+            total_bytes,
+            next_seq,
+            entries,
+        };
+        spool.enforce_limit();
+        Ok(spool)
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn total_bytes(&self) -> u64 {
+        self.total_bytes
+    }
+
+    /// Store a payload, dropping the oldest ones if the spool grows past its
+    /// bound. Payloads larger than the bound on their own are discarded.
+    pub fn push(&mut self, data: &[u8]) -> io::Result<()> {
+        let size = data.len() as u64;
+        if size > self.max_bytes {
+            tracing::warn!(
+                "Dropping {size}-byte payload larger than the spool limit of {} bytes",
+                self.max_bytes
+            );
+            return Ok(());
+        }
+
+        let path = self
+            .dir
+            .join(format!("{:020}.{}", self.next_seq, self.extension));
+        // Write under a temporary name first so a crash never leaves a
+        // truncated payload that would be replayed
+        let tmp_path = path.with_extension("tmp");
+        fs::write(&tmp_path, data)?;
+        fs::rename(&tmp_path, &path)?;
+
+        self.next_seq += 1;
+        self.total_bytes += size;
+        self.entries.push_back(SpoolEntry { path, size });
+        self.enforce_limit();
+        Ok(())
+    }
+
+    /// Read the oldest spooled payload without removing it
+    pub fn peek(&self) -> io::Result<Option<Vec<u8>>> {
+        match self.entries.front() {
+            Some(entry) => fs::read(&entry.path).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Remove the oldest spooled payload
+    pub fn pop(&mut self) {
+        if let Some(entry) = self.entries.pop_front() {
+            self.total_bytes = self.total_bytes.saturating_sub(entry.size);
+            remove_file(&entry.path);
+        }
+    }
+
+    fn enforce_limit(&mut self) {
+        let mut dropped = 0;
+        while self.total_bytes > self.max_bytes && !self.entries.is_empty() {
+            self.pop();
+            dropped += 1;
+        }
+        if dropped > 0 {
+            tracing::warn!(
+                "Spool {} exceeded {} bytes; dropped {dropped} oldest payload(s)",
+                self.dir.display(),
+                self.max_bytes
+            );
+        }
+    }
+}
+
+/// Default directory for the spool `name`: under `$STATE_DIRECTORY` when
+/// systemd provides one, `/var/lib/all-smi` for root, and the user's state
+/// directory otherwise
+pub fn default_dir(name: &str) -> PathBuf {
+    std::env::var_os("STATE_DIRECTORY")
+        .and_then(|dirs| std::env::split_paths(&dirs).next())
+        .unwrap_or_else(state_home)
+        .join(name)
+}
+
+fn state_home() -> PathBuf {
+    #[cfg(unix)]
+    // SAFETY: geteuid has no preconditions and cannot fail
+    if unsafe { libc::geteuid() } == 0 {
+        return PathBuf::from("/var/lib/all-smi");
+    }
+    let user_dir = if cfg!(windows) {
+        std::env::var_os("LOCALAPPDATA").map(PathBuf::from)
+    } else {
+        std::env::var_os("XDG_STATE_HOME")
+            .map(PathBuf::from)
+            .or_else(|| {
+                std::env::var_os("HOME")
+                    .map(|home| PathBuf::from(home).join(".local").join("state"))
+            })
+    };
+    user_dir
+        .map(|dir| dir.join("all-smi"))
+        .unwrap_or_else(|| PathBuf::from("/var/lib/all-smi"))
+}
+
+/// Create `dir` accessible to this user only, or check that an existing one
+/// is owned by this user and not writable by anyone else
+fn create_private_dir(dir: &Path) -> io::Result<()> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::{DirBuilderExt, MetadataExt};
+
+        fs::DirBuilder::new()
+            .recursive(true)
+            .mode(0o700)
+            .create(dir)?;
+        let metadata = fs::symlink_metadata(dir)?;
+        let refuse = |reason: &str| {
+            Err(io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                format!("refusing spool directory {}: {reason}", dir.display()),
+            ))
+        };
+        if !metadata.is_dir() {
+            return refuse("not a directory");
+        }
+        // SAFETY: geteuid has no preconditions and cannot fail
+        if metadata.uid() != unsafe { libc::geteuid() } {
+            return refuse("owned by another user");
+        }
+        if metadata.mode() & 0o022 != 0 {
+            return refuse("writable by group or others");
+        }
+        Ok(())
+    }
+    #[cfg(not(unix))]
+    fs::create_dir_all(dir)
+}
+
+fn remove_file(path: &Path) {
+    if let Err(e) = fs::remove_file(path) {
+        if e.kind() != io::ErrorKind::NotFound {
+            tracing::warn!("Failed to remove spooled payload {}: {e}", path.display());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spool_is_fifo() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut spool = Spool::open(dir.path(), 1024, "lp").unwrap();
+        spool.push(b"first").unwrap();
+        spool.push(b"second").unwrap();
+
+        assert_eq!(spool.len(), 2);
+        assert_eq!(spool.total_bytes(), 11);
+        assert_eq!(spool.peek().unwrap().as_deref(), Some(&b"first"[..]));
+        spool.pop();
+        assert_eq!(spool.peek().unwrap().as_deref(), Some(&b"second"[..]));
+        spool.pop();
+        assert!(spool.is_empty());
+        assert_eq!(spool.peek().unwrap(), None);
+    }
+
+    #[test]
+    fn test_spool_drops_oldest_over_limit() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut spool = Spool::open(dir.path(), 10, "lp").unwrap();
+        spool.push(b"aaaa").unwrap();
+        spool.push(b"bbbb").unwrap();
+        spool.push(b"cccc").unwrap();
+
+        assert_eq!(spool.len(), 2);
+        assert_eq!(spool.peek().unwrap().as_deref(), Some(&b"bbbb"[..]));
+
+        // A payload larger than the whole spool is discarded outright
+        spool.push(&[0u8; 11]).unwrap();
+        assert_eq!(spool.len(), 2);
+    }
+
+    #[test]
+    fn test_spool_survives_reopen() {
+        let dir = tempfile::tempdir().unwrap();
+        {
+            let mut spool = Spool::open(dir.path(), 1024, "lp").unwrap();
+            spool.push(b"one").unwrap();
+            spool.push(b"two").unwrap();
+        }
+        // Files with another extension belong to someone else
+        fs::write(dir.path().join("00000000000000000099.other"), b"x").unwrap();
+
+        let mut spool = Spool::open(dir.path(), 1024, "lp").unwrap();
+        assert_eq!(spool.len(), 2);
+        assert_eq!(spool.peek().unwrap().as_deref(), Some(&b"one"[..]));
+        spool.push(b"three").unwrap();
+        spool.pop();
+        spool.pop();
+        assert_eq!(spool.peek().unwrap().as_deref(), Some(&b"three"[..]));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_spool_refuses_shared_directory() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let created = dir.path().join("state").join("spool");
+        Spool::open(&created, 1024, "lp").unwrap();
+        let mode = fs::metadata(&created).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o700);
+
+        let shared = dir.path().join("shared");
+        fs::create_dir(&shared).unwrap();
+        fs::set_permissions(&shared, fs::Permissions::from_mode(0o1777)).unwrap();
+        let err = Spool::open(&shared, 1024, "lp").err().unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::PermissionDenied);
+
+        // A planted symlink is not followed
+        let link = dir.path().join("link");
+        std::os::unix::fs::symlink(&created, &link).unwrap();
+        assert!(Spool::open(&link, 1024, "lp").is_err());
+    }
+}
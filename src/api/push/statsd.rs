@@ -0,0 +1,505 @@
+// Copyright 2025 Lablup Inc. and Jeongkyu Shin
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! StatsD / DogStatsD UDP emitter
+//!
+//! Every collection cycle is sent as a set of gauges
+//! (`<prefix>.gpu.utilization:42.5|g|#host:node-1,index:0,...`). Device
+//! identity travels as DogStatsD tags, so plain StatsD servers need tag
+//! support (e.g. Telegraf with `datadog_extensions`) to tell devices apart.
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+
+use all_smi::traits::{
+    ExportFormat, ExporterError, ExporterResult, MetricCollection, MetricValue, MetricsExporter,
+};
+use rand::Rng;
+use tokio::net::UdpSocket;
+use tokio::sync::watch;
+
+use crate::api::handlers::SharedState;
+use crate::app_state::AppState;
+use crate::cli::ApiArgs;
+
+#[derive(Debug, Clone)]
+pub struct StatsdConfig {
+    /// `host:port` of the StatsD server or Datadog agent
+    pub addr: String,
+    pub prefix: String,
+    /// Fraction of gauges sent per cycle, in (0, 1]
+    pub sample_rate: f64,
+    /// Upper bound for a single datagram
+    pub max_packet_size: usize,
+}
+
+impl StatsdConfig {
+    /// Build the emitter configuration, or `None` when no address was given
+    pub fn from_args(args: &ApiArgs) -> Option<Self> {
+        let addr = args.statsd_addr.clone()?;
+        let sample_rate = if args.statsd_sample_rate > 0.0 && args.statsd_sample_rate <= 1.0 {
+            args.statsd_sample_rate
+        } else {
+            tracing::warn!(
+                "Invalid StatsD sample rate {}, sending every gauge",
+                args.statsd_sample_rate
+            );
+            1.0
+        };
+
+        Some(Self {
+            addr,
+            prefix: args.statsd_prefix.trim_end_matches('.').to_string(),
+            sample_rate,
+            max_packet_size: args.statsd_max_packet_size.max(64),
+        })
+    }
+}
+
+/// Run the emitter until the collection loop goes away
+pub async fn run_statsd_sink(
+    config: StatsdConfig,
+    state: SharedState,
+    mut updates: watch::Receiver<u64>,
+) {
+    tracing::info!("Sending StatsD gauges to {}", config.addr);
+    let mut socket = None;
+
+    while updates.changed().await.is_ok() {
+        let payload = {
+            let state = state.read().await;
+            let exporter = StatsdExporter::new(&state, &config.prefix, config.sample_rate);
+            match exporter.export(ExportFormat::StatsD) {
+                Ok(payload) => payload,
+                Err(e) => {
+                    tracing::warn!("Failed to encode StatsD gauges: {e}");
+                    continue;
+                }
+            }
+        };
+
+        if socket.is_none() {
+            socket = connect(&config.addr).await;
+        }
+        let Some(sock) = socket.as_ref() else {
+            continue;
+        };
+
+        for packet in packetize(&payload, config.max_packet_size) {
+            if let Err(e) = sock.send(packet.as_bytes()).await {
+                tracing::debug!("Failed to send StatsD packet to {}: {e}", config.addr);
+                // Resolve the address again on the next cycle
+                socket = None;
+                break;
+            }
+        }
+    }
+}
+
+async fn connect(addr: &str) -> Option<UdpSocket> {
+    let target = match tokio::net::lookup_host(addr).await {
+        Ok(mut addrs) => addrs.next(),
+        Err(e) => {
+            tracing::warn!("Failed to resolve StatsD address {addr}: {e}");
+            return None;
+        }
+    }?;
+    let bind_addr = if target.is_ipv6() {
+        "[::]:0"
+    } else {
+        "0.0.0.0:0"
+    };
+
+    let socket = match UdpSocket::bind(bind_addr).await {
+        Ok(socket) => socket,
+        Err(e) => {
+            tracing::warn!("Failed to bind StatsD socket: {e}");
+            return None;
+        }
+    };
+    if let Err(e) = socket.connect(target).await {
+        tracing::warn!("Failed to connect StatsD socket to {target}: {e}");
+        return None;
+    }
+    Some(socket)
+}
+
+/// Split newline-separated lines into datagrams of at most `max_size` bytes.
+/// A line longer than `max_size` is sent on its own.
+pub fn packetize(payload: &str, max_size: usize) -> Vec<&str> {
+    let mut packets = Vec::new();
+    let mut start = 0;
+    let mut end = 0;
+
+    for line in payload.split_inclusive('\n') {
+        let line_end = end + line.len();
+        // The trailing newline of the last line in a packet is not sent
+        if line_end - start - 1 > max_size && end > start {
+            packets.push(payload[start..end].trim_end_matches('\n'));
+            start = end;
+        }
+        end = line_end;
+    }
+    if end > start {
+        packets.push(payload[start..end].trim_end_matches('\n'));
+    }
+    packets
+}
+
+/// Gauges for one collection cycle
+pub struct StatsdExporter<'a> {
+    state: &'a AppState,
+    prefix: &'a str,
+    sample_rate: f64,
+}
+
+impl<'a> StatsdExporter<'a> {
+    pub fn new(state: &'a AppState, prefix: &'a str, sample_rate: f64) -> Self {
+        Self {
+            state,
+            prefix,
+            sample_rate,
+        }
+    }
+
+    /// Render the gauges as newline-separated DogStatsD lines
+    pub fn render<R: Rng>(&self, rng: &mut R) -> String {
+        let collection = self.get_metrics();
+        let mut names: Vec<&String> = collection.metrics.keys().collect();
+        names.sort();
+
+        let mut out = String::new();
+        for name in names {
+            let metric_name = sanitize_name(&self.metric_name(name));
+            for value in &collection.metrics[name] {
+                if !value.value.is_finite() {
+                    continue;
+                }
+                if self.sample_rate < 1.0 && rng.random::<f64>() >= self.sample_rate {
+                    continue;
+                }
+
+                let _ = write!(out, "{metric_name}:{}|g", value.value);
+                if self.sample_rate < 1.0 {
+                    let _ = write!(out, "|@{}", self.sample_rate);
+                }
+                let mut tags: Vec<(&String, &String)> =
+                    value.labels.iter().filter(|(_, v)| !v.is_empty()).collect();
+                tags.sort();
+                for (i, (key, tag_value)) in tags.into_iter().enumerate() {
+                    out.push_str(if i == 0 { "|#" } else { "," });
+                    out.push_str(&sanitize_tag(key));
+                    out.push(':');
+                    out.push_str(&sanitize_tag(tag_value));
+                }
+                out.push('\n');
+            }
+        }
+        out
+    }
+
+    fn metric_name(&self, name: &str) -> String {
+        if self.prefix.is_empty() {
+            name.to_string()
+        } else {
+            format!("{}.{name}", self.prefix)
+        }
+    }
+}
+
+impl MetricsExporter for StatsdExporter<'_> {
+    fn export(&self, format: ExportFormat) -> ExporterResult<String> {
+        match format {
+            ExportFormat::StatsD => Ok(self.render(&mut rand::rng())),
+            other => Err(ExporterError::UnsupportedFormat(format!("{other:?}"))),
+        }
+    }
+
+    fn get_metrics(&self) -> MetricCollection {
+        let mut gauges = Gauges::default();
+        let state = self.state;
+
+        for (index, info) in state.gpu_info.iter().enumerate() {
+            let kind = match info.device_type.as_str() {
+                "NPU" | "TPU" => "npu",
+                _ => "gpu",
+            };
+            let index = index.to_string();
+            let lib_name = info.detail.get("lib_name").map(String::as_str);
+            let labels = [
+                ("host", info.hostname.as_str()),
+                ("uuid", info.uuid.as_str()),
+                ("index", index.as_str()),
+                ("lib_name", lib_name.unwrap_or_default()),
+            ];
+            gauges.add(kind, "utilization", &labels, info.utilization);
+            gauges.add(kind, "memory.used_bytes", &labels, info.used_memory as f64);
+            gauges.add(
+                kind,
+                "memory.total_bytes",
+                &labels,
+                info.total_memory as f64,
+            );
+            gauges.add(
+                kind,
+                "temperature_celsius",
+                &labels,
+                info.temperature as f64,
+            );
+            gauges.add(kind, "power_watts", &labels, info.power_consumption);
+            gauges.add(kind, "frequency_mhz", &labels, info.frequency as f64);
+        }
+
+        for (index, info) in state.cpu_info.iter().enumerate() {
+            let index = index.to_string();
+            let labels = [("host", info.hostname.as_str()), ("index", index.as_str())];
+            gauges.add("cpu", "utilization", &labels, info.utilization);
+            if let Some(temperature) = info.temperature {
+                gauges.add("cpu", "temperature_celsius", &labels, temperature as f64);
+            }
+            if let Some(power) = info.power_consumption {
+                gauges.add("cpu", "power_watts", &labels, power);
+            }
+        }
+
+        for info in &state.memory_info {
+            let labels = [("host", info.hostname.as_str())];
+            gauges.add("memory", "total_bytes", &labels, info.total_bytes as f64);
+            gauges.add("memory", "used_bytes", &labels, info.used_bytes as f64);
+            gauges.add(
+                "memory",
+                "available_bytes",
+                &labels,
+                info.available_bytes as f64,
+            );
+            gauges.add(
+                "memory",
+                "swap_used_bytes",
+                &labels,
+                info.swap_used_bytes as f64,
+            );
+            gauges.add("memory", "utilization", &labels, info.utilization);
+        }
+
+        for info in &state.storage_info {
+            let labels = [
+                ("host", info.hostname.as_str()),
+                ("mount_point", info.mount_point.as_str()),
+            ];
+            gauges.add("disk", "total_bytes", &labels, info.total_bytes as f64);
+            gauges.add(
+                "disk",
+                "available_bytes",
+                &labels,
+                info.available_bytes as f64,
+            );
+        }
+
+        for info in &state.chassis_info {
+            let labels = [("host", info.hostname.as_str())];
+            if let Some(power) = info.total_power_watts {
+                gauges.add("chassis", "power_watts", &labels, power);
+            }
+            if let Some(inlet) = info.inlet_temperature {
+                gauges.add("chassis", "inlet_temperature_celsius", &labels, inlet);
+            }
+            if let Some(outlet) = info.outlet_temperature {
+                gauges.add("chassis", "outlet_temperature_celsius", &labels, outlet);
+            }
+            for fan in &info.fan_speeds {
+                let fan_labels = [("host", info.hostname.as_str()), ("fan", fan.name.as_str())];
+                gauges.add(
+                    "chassis",
+                    "fan.speed_rpm",
+                    &fan_labels,
+                    fan.speed_rpm as f64,
+                );
+            }
+        }
+
+        MetricCollection {
+            metrics: gauges.0,
+            ..Default::default()
+        }
+    }
+
+    fn supported_formats(&self) -> Vec<ExportFormat> {
+        vec![ExportFormat::StatsD]
+    }
+}
+
+#[derive(Default)]
+struct Gauges(HashMap<String, Vec<MetricValue>>);
+
+impl Gauges {
+    fn add(&mut self, group: &str, name: &str, labels: &[(&str, &str)], value: f64) {
+        self.0
+            .entry(format!("{group}.{name}"))
+            .or_default()
+            .push(MetricValue {
+                value,
+                timestamp: None,
+                labels: labels
+                    .iter()
+                    .map(|(k, v)| (k.to_string(), v.to_string()))
+                    .collect(),
+            });
+    }
+}
+
+/// StatsD names may not contain the `:`, `|` or `@` delimiters
+fn sanitize_name(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// DogStatsD tags are comma-separated and end at `|`
+fn sanitize_tag(tag: &str) -> String {
+    tag.chars()
+        .map(|c| match c {
+            ',' | '|' | '#' | '\n' | '\r' => '_',
+            ' ' => '_',
+            c => c,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::device::GpuInfo;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn test_state() -> AppState {
+        let mut state = AppState::new();
+        let mut detail = HashMap::new();
+        detail.insert("lib_name".to_string(), "CUDA".to_string());
+        state.gpu_info.push(GpuInfo {
+            uuid: "GPU-1234".to_string(),
+            time: String::new(),
+            name: "NVIDIA H100".to_string(),
+            device_type: "GPU".to_string(),
+            host_id: "node-1".to_string(),
+            hostname: "node-1".to_string(),
+            instance: "node-1".to_string(),
+            utilization: 42.5,
+            ane_utilization: 0.0,
+            dla_utilization: None,
+            tensorcore_utilization: None,
+            temperature: 60,
+            used_memory: 1024,
+            total_memory: 4096,
+            frequency: 1500,
+            power_consumption: f64::NAN,
+            gpu_core_count: None,
+            detail,
+        });
+        state
+    }
+
+    #[test]
+    fn test_render_dogstatsd_lines() {
+        let state = test_state();
+        let exporter = StatsdExporter::new(&state, "all_smi", 1.0);
+        let payload = exporter.export(ExportFormat::StatsD).unwrap();
+        let lines: Vec<&str> = payload.lines().collect();
+
+        assert!(lines.contains(
+            &"all_smi.gpu.utilization:42.5|g|#host:node-1,index:0,lib_name:CUDA,uuid:GPU-1234"
+        ));
+        assert!(lines.contains(
+            &"all_smi.gpu.memory.used_bytes:1024|g|#host:node-1,index:0,lib_name:CUDA,uuid:GPU-1234"
+        ));
+        // NaN power is not representable and is skipped
+        assert!(!payload.contains("power_watts"));
+        assert_eq!(lines.len(), 5);
+    }
+
+    #[test]
+    fn test_render_sample_rate() {
+        let state = test_state();
+        let exporter = StatsdExporter::new(&state, "", 0.5);
+        let payload = exporter.render(&mut StdRng::seed_from_u64(7));
+
+        let lines: Vec<&str> = payload.lines().collect();
+        assert!(lines.len() < 5);
+        assert!(lines.iter().all(|l| l.contains("|g|@0.5|#")));
+        assert!(lines.iter().all(|l| l.starts_with("gpu.")));
+    }
+
+    #[test]
+    fn test_unsupported_format() {
+        let state = AppState::new();
+        let exporter = StatsdExporter::new(&state, "all_smi", 1.0);
+        assert!(exporter.export(ExportFormat::Prometheus).is_err());
+        assert_eq!(exporter.supported_formats(), vec![ExportFormat::StatsD]);
+    }
+
+    #[test]
+    fn test_sanitize() {
+        assert_eq!(
+            sanitize_name("all_smi.gpu:util|x@y"),
+            "all_smi.gpu_util_x_y"
+        );
+        assert_eq!(sanitize_tag("Fan 1,a|b#c"), "Fan_1_a_b_c");
+        assert_eq!(sanitize_tag("host:port"), "host:port");
+    }
+
+    #[test]
+    fn test_packetize() {
+        let payload = "aaaa\nbbbb\ncccc\n";
+        assert_eq!(packetize(payload, 100), vec!["aaaa\nbbbb\ncccc"]);
+        assert_eq!(packetize(payload, 9), vec!["aaaa\nbbbb", "cccc"]);
+        assert_eq!(packetize(payload, 4), vec!["aaaa", "bbbb", "cccc"]);
+        // Oversized lines still go out on their own
+        assert_eq!(packetize("aaaaaaaa\nb\n", 4), vec!["aaaaaaaa", "b"]);
+        assert!(packetize("", 10).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_sink_sends_udp_datagrams() {
+        let receiver = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let config = StatsdConfig {
+            addr: receiver.local_addr().unwrap().to_string(),
+            prefix: "test".to_string(),
+            sample_rate: 1.0,
+            max_packet_size: 1432,
+        };
+
+        let state = SharedState::new(tokio::sync::RwLock::new(test_state()));
+        let (tx, rx) = watch::channel(0u64);
+        let sink = tokio::spawn(run_statsd_sink(config, state, rx));
+        tx.send_replace(1);
+
+        let mut buf = [0u8; 2048];
+        let len = tokio::time::timeout(std::time::Duration::from_secs(5), receiver.recv(&mut buf))
+            .await
+            .expect("no datagram received")
+            .unwrap();
+        let datagram = std::str::from_utf8(&buf[..len]).unwrap();
+        assert!(datagram.contains("test.gpu.utilization:42.5|g|#"));
+        assert_eq!(datagram.lines().count(), 5);
+
+        drop(tx);
+        sink.await.unwrap();
+    }
+}
@@ -0,0 +1,81 @@
+// Copyright 2025 Lablup Inc. and Jeongkyu Shin
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Configuration reload on SIGHUP
+//!
+//! The command line is parsed again over a fresh read of the config file and
+//! the parts that can change in place are swapped: runtime settings, node
+//! labels, exporter options and the GPU readers together with `--processes`.
+//! Nothing is applied unless all of it is valid. Cached data, history and
+//! the readers that stay enabled carry on; listeners, TLS, authentication
+//! and push targets still need a restart.
+
+use tokio::sync::watch;
+
+use super::handlers::SharedState;
+use super::labels::NodeLabels;
+use super::metrics::process::ProcessMetricOptions;
+use super::metrics::{ExportOptions, SharedExportOptions};
+use super::self_metrics::SharedSelfMetrics;
+use crate::cli::Commands;
+use crate::common::config::Settings;
+use crate::common::config_file::parse_cli;
+
+pub struct Reloader {
+    pub state: SharedState,
+    pub export_options: SharedExportOptions,
+    pub self_metrics: SharedSelfMetrics,
+    /// Asks the collection loop to rebuild its readers, with `--processes`
+    pub readers: watch::Sender<bool>,
+}
+
+impl Reloader {
+    pub async fn reload(&self) -> Result<(), String> {
+        let (cli, config) = parse_cli()?;
+        let Some(Commands::Api(args)) = cli.command else {
+            return Err("command line no longer selects API mode".to_string());
+        };
+        let settings = config.settings()?;
+        let process = ProcessMetricOptions::from_args(&args)?;
+        let labels = NodeLabels::from_args(&args)?.load()?;
+
+        Settings::install(settings);
+        *self.export_options.write().await = ExportOptions { process };
+        self.state.write().await.node_labels = labels;
+        self.readers.send_replace(args.processes);
+        Ok(())
+    }
+}
+
+/// Reload the configuration on every SIGHUP
+#[cfg(unix)]
+pub async fn reload_on_hangup(reloader: Reloader) {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    let mut hangup = match signal(SignalKind::hangup()) {
+        Ok(hangup) => hangup,
+        Err(e) => {
+            tracing::warn!("Cannot listen for SIGHUP; configuration will not be reloaded: {e}");
+            return;
+        }
+    };
+    while hangup.recv().await.is_some() {
+        let result = reloader.reload().await;
+        match &result {
+            Ok(()) => tracing::info!("Reloaded configuration"),
+            Err(e) => tracing::warn!("Keeping previous configuration: {e}"),
+        }
+        reloader.self_metrics.record_reload(result.is_ok());
+    }
+}
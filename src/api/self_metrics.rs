@@ -0,0 +1,437 @@
+// Copyright 2025 Lablup Inc. and Jeongkyu Shin
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Exporter self-instrumentation (`all_smi_collector_*`)
+//!
+//! Tracks how long each reader takes and how often it fails, how often
+//! `/metrics` is scraped and how long answering and the per-cycle rendering
+//! take, the outcome of configuration reloads, and the resource usage of the
+//! all-smi process itself.
+//!
+//! These values move between scrapes of the same cycle, so they are rendered
+//! on every scrape and appended to the cached exposition.
+
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use all_smi::traits::Histogram;
+use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System};
+
+use super::metrics::{MetricBuilder, MetricExporter};
+use crate::app_state::ReaderStatus;
+
+pub type SharedSelfMetrics = Arc<SelfMetrics>;
+
+/// Bucket bounds for reader call durations, in seconds
+const READER_DURATION_BUCKETS: &[f64] = &[
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0,
+];
+
+/// Bucket bounds for answering one scrape or rendering one cycle, in seconds
+const SCRAPE_DURATION_BUCKETS: &[f64] =
+    &[0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0];
+
+struct ReaderStats {
+    kind: &'static str,
+    name: &'static str,
+    duration: Histogram,
+    errors: u64,
+    device_count: usize,
+    last_success: Option<SystemTime>,
+}
+
+struct Stats {
+    readers: Vec<ReaderStats>,
+    scrapes: u64,
+    scrape_duration: Histogram,
+    render_duration: Histogram,
+    reloads_succeeded: u64,
+    reloads_failed: u64,
+    last_reload_successful: bool,
+    /// The configuration loaded at startup counts as a successful load
+    last_reload_success: SystemTime,
+}
+
+pub struct SelfMetrics {
+    stats: Mutex<Stats>,
+    system: Mutex<System>,
+    pid: Option<Pid>,
+    hostname: String,
+}
+
+impl Default for SelfMetrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SelfMetrics {
+    pub fn new() -> Self {
+        Self {
+            stats: Mutex::new(Stats {
+                readers: Vec::new(),
+                scrapes: 0,
+                scrape_duration: Histogram::new(SCRAPE_DURATION_BUCKETS),
+                render_duration: Histogram::new(SCRAPE_DURATION_BUCKETS),
+                reloads_succeeded: 0,
+                reloads_failed: 0,
+                last_reload_successful: true,
+                last_reload_success: SystemTime::now(),
+            }),
+            system: Mutex::new(System::new()),
+            pid: sysinfo::get_current_pid().ok(),
+            hostname: crate::utils::get_hostname(),
+        }
+    }
+
+    /// Record the outcome of every reader in a collection cycle. Missed
+    /// deadlines count as errors; an empty result does not, since the device
+    /// count is exported on its own. Readers missing from the cycle, such as
+    /// those a reload disabled, are no longer exported.
+    pub fn record_cycle(&self, reader_status: &[ReaderStatus]) {
+        let mut stats = self.stats.lock().unwrap();
+        for status in reader_status {
+            let idx = match stats
+                .readers
+                .iter()
+                .position(|r| r.kind == status.kind && r.name == status.name)
+            {
+                Some(idx) => idx,
+                None => {
+                    stats.readers.push(ReaderStats {
+                        kind: status.kind,
+                        name: status.name,
+                        duration: Histogram::new(READER_DURATION_BUCKETS),
+                        errors: 0,
+                        device_count: 0,
+                        last_success: None,
+                    });
+                    stats.readers.len() - 1
+                }
+            };
+
+            let reader = &mut stats.readers[idx];
+            reader.device_count = status.device_count;
+            if let Some(duration) = status.call_duration {
+                reader.duration.observe(duration.as_secs_f64());
+            }
+            if status.stale {
+                reader.errors += 1;
+            } else if status.device_count > 0 {
+                reader.last_success = Some(SystemTime::now());
+            }
+        }
+        stats.readers.retain(|reader| {
+            reader_status
+                .iter()
+                .any(|status| status.kind == reader.kind && status.name == reader.name)
+        });
+    }
+
+    /// Record one answered `/metrics` request
+    pub fn record_scrape(&self, duration: Duration) {
+        let mut stats = self.stats.lock().unwrap();
+        stats.scrapes += 1;
+        stats.scrape_duration.observe(duration.as_secs_f64());
+    }
+
+    /// Record the once-per-cycle rendering of the cached exposition
+    pub fn record_render(&self, duration: Duration) {
+        let mut stats = self.stats.lock().unwrap();
+        stats.render_duration.observe(duration.as_secs_f64());
+    }
+
+    /// Record the outcome of a configuration reload
+    pub fn record_reload(&self, succeeded: bool) {
+        let mut stats = self.stats.lock().unwrap();
+        stats.last_reload_successful = succeeded;
+        if succeeded {
+            stats.reloads_succeeded += 1;
+            stats.last_reload_success = SystemTime::now();
+        } else {
+            stats.reloads_failed += 1;
+        }
+    }
+}
+
+impl MetricExporter for SelfMetrics {
+    fn export_to(&self, builder: &mut MetricBuilder) {
+        self.export_stats(builder);
+        self.export_process(builder);
+    }
+}
+
+impl SelfMetrics {
+    fn export_stats(&self, builder: &mut MetricBuilder) {
+        let hostname = self.hostname.as_str();
+        let stats = self.stats.lock().unwrap();
+
+        if !stats.readers.is_empty() {
+            builder
+                .help(
+                    "all_smi_collector_reader_duration_seconds",
+                    "Time spent waiting on a device reader per collection cycle",
+                )
+                .help(
+                    "all_smi_collector_reader_errors_total",
+                    "Cycles in which a reader missed its deadline",
+                )
+                .type_("all_smi_collector_reader_errors_total", "counter")
+                .help(
+                    "all_smi_collector_reader_devices",
+                    "Devices reported by a reader",
+                )
+                .type_("all_smi_collector_reader_devices", "gauge")
+                .help(
+                    "all_smi_collector_reader_last_success_timestamp_seconds",
+                    "Unix time of the last cycle in which a reader returned data",
+                )
+                .type_(
+                    "all_smi_collector_reader_last_success_timestamp_seconds",
+                    "gauge",
+                );
+        }
+        for reader in &stats.readers {
+            let labels = [
+                ("hostname", hostname),
+                ("kind", reader.kind),
+                ("reader", reader.name),
+            ];
+            builder
+                .histogram(
+                    "all_smi_collector_reader_duration_seconds",
+                    &labels,
+                    &reader.duration,
+                )
+                .metric(
+                    "all_smi_collector_reader_errors_total",
+                    &labels,
+                    reader.errors,
+                )
+                .metric(
+                    "all_smi_collector_reader_devices",
+                    &labels,
+                    reader.device_count,
+                );
+            if let Some(at) = reader.last_success {
+                let timestamp = at.duration_since(UNIX_EPOCH).unwrap_or_default();
+                builder.metric(
+                    "all_smi_collector_reader_last_success_timestamp_seconds",
+                    &labels,
+                    format!("{:.3}", timestamp.as_secs_f64()),
+                );
+            }
+        }
+
+        let labels = [("hostname", hostname)];
+        builder
+            .help(
+                "all_smi_collector_scrapes_total",
+                "Completed /metrics requests",
+            )
+            .type_("all_smi_collector_scrapes_total", "counter")
+            .metric("all_smi_collector_scrapes_total", &labels, stats.scrapes)
+            .help(
+                "all_smi_collector_scrape_duration_seconds",
+                "Time spent answering /metrics requests",
+            )
+            .histogram(
+                "all_smi_collector_scrape_duration_seconds",
+                &labels,
+                &stats.scrape_duration,
+            )
+            .help(
+                "all_smi_collector_render_duration_seconds",
+                "Time spent rendering the cached /metrics exposition per collection cycle",
+            )
+            .histogram(
+                "all_smi_collector_render_duration_seconds",
+                &labels,
+                &stats.render_duration,
+            );
+
+        builder
+            .help(
+                "all_smi_collector_config_reloads_total",
+                "Configuration reloads triggered by SIGHUP, by result",
+            )
+            .type_("all_smi_collector_config_reloads_total", "counter")
+            .metric(
+                "all_smi_collector_config_reloads_total",
+                &[("hostname", hostname), ("result", "success")],
+                stats.reloads_succeeded,
+            )
+            .metric(
+                "all_smi_collector_config_reloads_total",
+                &[("hostname", hostname), ("result", "failure")],
+                stats.reloads_failed,
+            )
+            .help(
+                "all_smi_collector_config_last_reload_successful",
+                "Whether the last configuration reload succeeded",
+            )
+            .type_("all_smi_collector_config_last_reload_successful", "gauge")
+            .metric(
+                "all_smi_collector_config_last_reload_successful",
+                &labels,
+                u8::from(stats.last_reload_successful),
+            )
+            .help(
+                "all_smi_collector_config_last_reload_success_timestamp_seconds",
+                "Unix time the configuration was last loaded successfully",
+            )
+            .type_(
+                "all_smi_collector_config_last_reload_success_timestamp_seconds",
+                "gauge",
+            )
+            .metric(
+                "all_smi_collector_config_last_reload_success_timestamp_seconds",
+                &labels,
+                format!(
+                    "{:.3}",
+                    stats
+                        .last_reload_success
+                        .duration_since(UNIX_EPOCH)
+                        .unwrap_or_default()
+                        .as_secs_f64()
+                ),
+            );
+    }
+
+    fn export_process(&self, builder: &mut MetricBuilder) {
+        let Some(pid) = self.pid else {
+            return;
+        };
+        let mut system = self.system.lock().unwrap();
+        system.refresh_processes_specifics(
+            ProcessesToUpdate::Some(&[pid]),
+            false,
+            ProcessRefreshKind::nothing().with_memory().with_cpu(),
+        );
+        let Some(process) = system.process(pid) else {
+            return;
+        };
+
+        let labels = [("hostname", self.hostname.as_str())];
+        builder
+            .help(
+                "all_smi_collector_process_resident_memory_bytes",
+                "Resident memory of the all-smi process",
+            )
+            .type_("all_smi_collector_process_resident_memory_bytes", "gauge")
+            .metric(
+                "all_smi_collector_process_resident_memory_bytes",
+                &labels,
+                process.memory(),
+            )
+            .help(
+                "all_smi_collector_process_cpu_seconds_total",
+                "CPU time consumed by the all-smi process",
+            )
+            .type_("all_smi_collector_process_cpu_seconds_total", "counter")
+            .metric(
+                "all_smi_collector_process_cpu_seconds_total",
+                &labels,
+                process.accumulated_cpu_time() as f64 / 1000.0,
+            )
+            .help(
+                "all_smi_collector_process_start_time_seconds",
+                "Start time of the all-smi process since the Unix epoch",
+            )
+            .type_("all_smi_collector_process_start_time_seconds", "gauge")
+            .metric(
+                "all_smi_collector_process_start_time_seconds",
+                &labels,
+                process.start_time(),
+            );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status(device_count: usize, stale: bool, call_ms: Option<u64>) -> ReaderStatus {
+        ReaderStatus {
+            kind: "gpu",
+            name: "NvidiaGpuReader",
+            device_count,
+            stale,
+            data_age: Some(Duration::ZERO),
+            timeouts_total: 0,
+            call_duration: call_ms.map(Duration::from_millis),
+        }
+    }
+
+    #[test]
+    fn test_reader_stats() {
+        let metrics = SelfMetrics::new();
+        metrics.record_cycle(&[status(8, false, Some(20))]);
+        metrics.record_cycle(&[status(0, false, Some(30))]);
+        metrics.record_cycle(&[status(8, true, Some(10_000))]);
+        // A cycle skipped while a call is stuck adds no duration sample
+        metrics.record_cycle(&[status(8, true, None)]);
+        metrics.record_scrape(Duration::from_millis(2));
+        metrics.record_render(Duration::from_millis(20));
+        metrics.record_reload(true);
+        metrics.record_reload(false);
+
+        let mut builder = MetricBuilder::new();
+        metrics.export_to(&mut builder);
+        let output = builder.build();
+
+        // Value of the first sample whose line starts with `prefix`
+        let value = |prefix: &str| {
+            output
+                .lines()
+                .find(|line| line.starts_with(prefix))
+                .and_then(|line| line.rsplit(' ').next())
+                .unwrap_or_else(|| panic!("no {prefix} in\n{output}"))
+                .to_string()
+        };
+
+        assert!(output.contains("# TYPE all_smi_collector_reader_duration_seconds histogram"));
+        assert!(output.contains("reader=\"NvidiaGpuReader\", le=\"0.025\"} 1\n"));
+        assert!(output.contains("reader=\"NvidiaGpuReader\", le=\"+Inf\"} 3\n"));
+        assert_eq!(
+            value("all_smi_collector_reader_duration_seconds_count{"),
+            "3"
+        );
+        // The empty result is not an error, the two missed deadlines are
+        assert_eq!(value("all_smi_collector_reader_errors_total{"), "2");
+        assert_eq!(value("all_smi_collector_reader_devices{"), "8");
+        assert!(output.contains("all_smi_collector_reader_last_success_timestamp_seconds{"));
+        assert_eq!(value("all_smi_collector_scrapes_total{"), "1");
+        assert!(output.contains("le=\"0.001\"} 0\n"));
+        assert!(output.contains("le=\"0.0025\"} 1\n"));
+        assert_eq!(
+            value("all_smi_collector_render_duration_seconds_count{"),
+            "1"
+        );
+        assert!(output.contains("result=\"success\"} 1\n"));
+        assert!(output.contains("result=\"failure\"} 1\n"));
+        assert_eq!(
+            value("all_smi_collector_config_last_reload_successful{"),
+            "0"
+        );
+        assert!(output.contains("all_smi_collector_process_resident_memory_bytes{"));
+        assert!(output.contains("all_smi_collector_process_cpu_seconds_total{"));
+
+        // A reader no longer reported stops being exported
+        metrics.record_cycle(&[]);
+        let mut builder = MetricBuilder::new();
+        metrics.export_to(&mut builder);
+        assert!(!builder.build().contains("NvidiaGpuReader"));
+    }
+}
@@ -12,89 +12,47 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use axum::{routing::get, Router};
-use std::time::Duration;
+use axum::{middleware, routing::get, Router};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 use sysinfo::Disks;
-use tokio::net::TcpListener;
-use tokio::sync::RwLock;
+use tokio::sync::{watch, RwLock};
 use tower_http::cors::{Any, CorsLayer};
 use tower_http::trace::TraceLayer;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
+use crate::api::auth::{require_bearer_token, watch_token_file, TokenStore};
+use crate::api::collector::Collector;
+use crate::api::exposition::SharedExposition;
+use crate::api::handlers::{collect_metrics, metrics_handler, ApiState, SharedState};
+use crate::api::health::{
+    default_stale_after, healthz_handler, readyz_handler, CollectorHealth, SharedHealth,
+};
+use crate::api::labels::NodeLabels;
+use crate::api::listen;
+use crate::api::metrics::process::ProcessMetricOptions;
+use crate::api::metrics::{ExportOptions, SharedExportOptions};
+use crate::api::push::influxdb::{run_influxdb_sink, InfluxDbConfig};
+use crate::api::push::otlp::{run_otlp_sink, OtlpConfig};
+use crate::api::push::remote_write::{run_remote_write_sender, RemoteWriteConfig};
+use crate::api::push::statsd::{run_statsd_sink, StatsdConfig};
 #[cfg(unix)]
-use std::path::PathBuf;
+use crate::api::reload::{reload_on_hangup, Reloader};
+use crate::api::self_metrics::{SelfMetrics, SharedSelfMetrics};
+use crate::api::snapshot::{
+    chassis_handler, cpus_handler, gpus_handler, memory_handler, processes_handler,
+    snapshot_handler, storage_handler,
+};
+use crate::api::stream::stream_handler;
 #[cfg(unix)]
-use tokio::net::UnixListener;
-
-use crate::api::handlers::{metrics_handler, SharedState};
+use crate::api::systemd;
+use crate::api::tls::TlsConfig;
 use crate::app_state::AppState;
 use crate::cli::ApiArgs;
-use crate::device::{get_cpu_readers, get_gpu_readers, get_memory_readers};
 use crate::storage::info::StorageInfo;
 use crate::utils::{filter_docker_aware_disks, get_hostname};
 
-/// Get the default Unix domain socket path for the current platform.
-/// - Linux: /var/run/all-smi.sock (fallback to /tmp/all-smi.sock if no permission)
-/// - macOS: /tmp/all-smi.sock
-#[cfg(unix)]
-fn get_default_socket_path() -> PathBuf {
-    #[cfg(target_os = "linux")]
-    {
-        let var_run_path = PathBuf::from("/var/run/all-smi.sock");
-        // Check if we can write to /var/run
-        if let Ok(metadata) = std::fs::metadata("/var/run") {
-            if metadata.is_dir() {
-                // Try to create a test file to check write permission
-                let test_path = PathBuf::from("/var/run/.all-smi-test");
-                if std::fs::write(&test_path, b"").is_ok() {
-                    let _ = std::fs::remove_file(&test_path);
-                    return var_run_path;
-                }
-            }
-        }
-        // Fallback to /tmp
-        PathBuf::from("/tmp/all-smi.sock")
-    }
-
-    #[cfg(target_os = "macos")]
-    {
-        PathBuf::from("/tmp/all-smi.sock")
-    }
-
-    #[cfg(not(any(target_os = "linux", target_os = "macos")))]
-    {
-        PathBuf::from("/tmp/all-smi.sock")
-    }
-}
-
-/// Remove stale socket file if it exists.
-/// This is necessary because Unix sockets leave files on disk that prevent rebinding.
-/// Uses atomic remove to avoid TOCTOU race conditions.
-#[cfg(unix)]
-fn remove_stale_socket(path: &std::path::Path) -> std::io::Result<()> {
-    match std::fs::remove_file(path) {
-        Ok(()) => {
-            tracing::info!("Removed stale socket file: {}", path.display());
-            Ok(())
-        }
-        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
-            // File doesn't exist, that's fine
-            Ok(())
-        }
-        Err(e) => Err(e),
-    }
-}
-
-/// Set restrictive permissions (0o600) on the socket file.
-/// This ensures only the owner can connect to the socket.
-#[cfg(unix)]
-fn set_socket_permissions(path: &std::path::Path) -> std::io::Result<()> {
-    use std::os::unix::fs::PermissionsExt;
-    let permissions = std::fs::Permissions::from_mode(0o600);
-    std::fs::set_permissions(path, permissions)
-}
-
-/// Run the API server with TCP and optionally Unix Domain Socket listeners.
+/// Run the API server on the configured TCP and Unix Domain Socket listeners.
 pub async fn run_api_mode(args: &ApiArgs) {
     tracing_subscriber::registry()
         .with(
@@ -105,308 +63,219 @@ pub async fn run_api_mode(args: &ApiArgs) {
         .init();
 
     println!("Starting API mode...");
-    let state = SharedState::new(RwLock::new(AppState::new()));
-    let state_clone = state.clone();
-    let processes = args.processes;
-    let interval = args.interval;
-
-    // Spawn background task for collecting metrics
-    tokio::spawn(async move {
-        let gpu_readers = get_gpu_readers();
-        let cpu_readers = get_cpu_readers();
-        let memory_readers = get_memory_readers();
-        loop {
-            let all_gpu_info = gpu_readers
-                .iter()
-                .flat_map(|reader| reader.get_gpu_info())
-                .collect();
-
-            let all_cpu_info = cpu_readers
-                .iter()
-                .flat_map(|reader| reader.get_cpu_info())
-                .collect();
-
-            let all_memory_info = memory_readers
-                .iter()
-                .flat_map(|reader| reader.get_memory_info())
-                .collect();
 
-            let all_processes = if processes {
-                gpu_readers
-                    .iter()
-                    .flat_map(|reader| reader.get_process_info())
-                    .collect()
-            } else {
-                Vec::new()
-            };
-
-            // Collect disk/storage info (cached in state to avoid per-request collection)
-            let storage_info = collect_storage_info();
-
-            let mut state = state_clone.write().await;
-            state.gpu_info = all_gpu_info;
-            state.cpu_info = all_cpu_info;
-            state.memory_info = all_memory_info;
-            state.process_info = all_processes;
-            state.storage_info = storage_info;
-            if state.loading {
-                state.loading = false;
+    // Load credentials before starting anything so misconfiguration fails fast
+    let token_store = match &args.auth_token_file {
+        Some(path) => match TokenStore::load(path) {
+            Ok(store) => {
+                tracing::info!("Loaded {} API token(s) from {path}", store.token_count());
+                Some(Arc::new(store))
             }
-
-            drop(state);
-            tokio::time::sleep(Duration::from_secs(interval)).await;
-        }
-    });
-
-    // Create the router with shared state
-    let app = Router::new()
-        .route("/metrics", get(metrics_handler))
-        .with_state(state)
-        .layer(
-            CorsLayer::new()
-                .allow_origin(Any)
-                .allow_methods(Any)
-                .allow_headers(Any),
-        )
-        .layer(TraceLayer::new_for_http());
-
-    // Determine which listeners to start
-    #[cfg(unix)]
-    {
-        let socket_path = args.socket.as_ref().map(|s| {
-            if s.is_empty() {
-                get_default_socket_path()
-            } else {
-                PathBuf::from(s)
-            }
-        });
-
-        let port = args.port;
-        match (port, socket_path) {
-            // Both TCP and UDS (port > 0 with socket)
-            (1..=u16::MAX, Some(path)) => {
-                run_dual_listeners(app, port, path).await;
-            }
-            // UDS only (port == 0 with socket)
-            (0, Some(path)) => {
-                run_unix_listener(app, path).await;
-            }
-            // TCP only (port > 0, no socket)
-            (1..=u16::MAX, None) => {
-                run_tcp_listener(app, port).await;
-            }
-            // No listeners - error (port == 0, no socket)
-            (0, None) => {
-                tracing::error!(
-                    "No listeners configured. Use --port or --socket to specify a listener."
-                );
-                eprintln!(
-                    "Error: No listeners configured. Use --port or --socket to specify a listener."
-                );
+            Err(e) => {
+                tracing::error!("Failed to load API tokens from {path}: {e}");
+                eprintln!("Error: Failed to load API tokens from {path}: {e}");
+                return;
             }
+        },
+        None => None,
+    };
+    let tls_acceptor = match TlsConfig::from_args(args).map(|config| config.acceptor()) {
+        Some(Ok(acceptor)) => Some(acceptor),
+        Some(Err(e)) => {
+            tracing::error!("{e}");
+            eprintln!("Error: {e}");
+            return;
         }
-    }
-
-    #[cfg(not(unix))]
-    {
-        run_tcp_listener(app, args.port).await;
-    }
-}
+        None => None,
+    };
 
-/// Run only the TCP listener
-async fn run_tcp_listener(app: Router, port: u16) {
-    let listener = match TcpListener::bind(&format!("0.0.0.0:{port}")).await {
-        Ok(l) => l,
+    // Bind every listener up front so a bad address fails before collection starts
+    let listeners = match listen::bind_listeners(args) {
+        Ok(listeners) => listeners,
         Err(e) => {
-            tracing::error!("Failed to bind TCP listener on port {port}: {e}");
-            eprintln!("Error: Failed to bind TCP listener on port {port}: {e}");
+            tracing::error!("{e}");
+            eprintln!("Error: {e}");
             return;
         }
     };
-    tracing::info!(
-        "API server listening on {}",
-        listener
-            .local_addr()
-            .unwrap_or_else(|_| "unknown".parse().unwrap())
-    );
-    if let Err(e) = axum::serve(listener, app).await {
-        tracing::error!("TCP server error: {e}");
-    }
-}
 
-/// Run only the Unix Domain Socket listener
-#[cfg(unix)]
-async fn run_unix_listener(app: Router, path: PathBuf) {
-    // Remove stale socket file if it exists
-    if let Err(e) = remove_stale_socket(&path) {
-        tracing::warn!("Failed to remove stale socket file: {e}");
-    }
-
-    // Create parent directory if it doesn't exist
-    if let Some(parent) = path.parent() {
-        if !parent.exists() {
-            if let Err(e) = std::fs::create_dir_all(parent) {
-                tracing::error!(
-                    "Failed to create socket directory {}: {e}",
-                    parent.display()
-                );
-                eprintln!(
-                    "Error: Failed to create socket directory {}: {e}",
-                    parent.display()
-                );
-                return;
-            }
-        }
-    }
-
-    let listener = match UnixListener::bind(&path) {
-        Ok(l) => l,
+    let export_options = match ProcessMetricOptions::from_args(args) {
+        Ok(process) => SharedExportOptions::new(RwLock::new(ExportOptions { process })),
         Err(e) => {
-            tracing::error!("Failed to bind Unix socket at {}: {e}", path.display());
-            eprintln!(
-                "Error: Failed to bind Unix socket at {}: {e}",
-                path.display()
-            );
+            tracing::error!("{e}");
+            eprintln!("Error: {e}");
             return;
         }
     };
 
-    // Set restrictive permissions (0o600) on the socket file
-    if let Err(e) = set_socket_permissions(&path) {
-        tracing::warn!("Failed to set socket permissions: {e}");
-    }
-
-    tracing::info!("API server listening on Unix socket: {}", path.display());
-
-    // Set up socket cleanup on shutdown
-    let path_clone = path.clone();
-    let cleanup_handle = tokio::spawn(async move {
-        tokio::signal::ctrl_c()
-            .await
-            .expect("Failed to listen for Ctrl+C");
-        cleanup_socket(&path_clone);
-    });
-
-    // Serve the application
-    if let Err(e) = axum::serve(listener, app).await {
-        tracing::error!("Unix socket server error: {e}");
-    }
-
-    // Cancel cleanup handle and do cleanup
-    cleanup_handle.abort();
-    cleanup_socket(&path);
-}
-
-/// Run both TCP and Unix Domain Socket listeners simultaneously
-#[cfg(unix)]
-async fn run_dual_listeners(app: Router, port: u16, socket_path: PathBuf) {
-    // Remove stale socket file if it exists
-    if let Err(e) = remove_stale_socket(&socket_path) {
-        tracing::warn!("Failed to remove stale socket file: {e}");
-    }
-
-    // Create parent directory if it doesn't exist
-    if let Some(parent) = socket_path.parent() {
-        if !parent.exists() {
-            if let Err(e) = std::fs::create_dir_all(parent) {
-                tracing::error!(
-                    "Failed to create socket directory {}: {e}",
-                    parent.display()
-                );
-                eprintln!(
-                    "Error: Failed to create socket directory {}: {e}",
-                    parent.display()
-                );
-                return;
-            }
-        }
-    }
-
-    // Create TCP listener
-    let tcp_listener = match TcpListener::bind(&format!("0.0.0.0:{port}")).await {
-        Ok(l) => l,
+    let node_labels = match NodeLabels::from_args(args) {
+        Ok(labels) => labels,
         Err(e) => {
-            tracing::error!("Failed to bind TCP listener on port {port}: {e}");
-            eprintln!("Error: Failed to bind TCP listener on port {port}: {e}");
+            tracing::error!("{e}");
+            eprintln!("Error: {e}");
             return;
         }
     };
-
-    // Create Unix listener
-    let unix_listener = match UnixListener::bind(&socket_path) {
-        Ok(l) => l,
+    let mut app_state = AppState::new();
+    app_state.node_labels = match node_labels.load() {
+        Ok(labels) => labels,
         Err(e) => {
-            tracing::error!(
-                "Failed to bind Unix socket at {}: {e}",
-                socket_path.display()
-            );
-            eprintln!(
-                "Error: Failed to bind Unix socket at {}: {e}",
-                socket_path.display()
-            );
+            tracing::error!("{e}");
+            eprintln!("Error: {e}");
             return;
         }
     };
 
-    // Set restrictive permissions (0o600) on the socket file
-    if let Err(e) = set_socket_permissions(&socket_path) {
-        tracing::warn!("Failed to set socket permissions: {e}");
-    }
-
-    tracing::info!(
-        "API server listening on TCP {} and Unix socket {}",
-        tcp_listener
-            .local_addr()
-            .unwrap_or_else(|_| "unknown".parse().unwrap()),
-        socket_path.display()
-    );
+    let state = SharedState::new(RwLock::new(app_state));
+    let state_clone = state.clone();
+    let (update_tx, update_rx) = watch::channel(0u64);
+    let (readers_tx, mut readers_rx) = watch::channel(args.processes);
+    let processes = args.processes;
+    let interval = args.interval;
+    let stale_after = args
+        .stale_after
+        .map(Duration::from_secs)
+        .unwrap_or_else(|| default_stale_after(interval));
+    let health = SharedHealth::new(RwLock::new(CollectorHealth::new(stale_after)));
+    let health_clone = health.clone();
+    let self_metrics = SharedSelfMetrics::new(SelfMetrics::new());
+    let self_metrics_clone = self_metrics.clone();
+    let exposition = SharedExposition::default();
+    let exposition_clone = exposition.clone();
+    let export_options_clone = export_options.clone();
+
+    let reader_timeout = Duration::from_secs(args.reader_timeout);
 
-    // Clone the app for the second server
-    let app_clone = app.clone();
+    // Spawn background task for collecting metrics
+    tokio::spawn(async move {
+        let mut collector = Collector::new(processes, reader_timeout);
+        loop {
+            if readers_rx.has_changed().unwrap_or(false) {
+                collector.reload(*readers_rx.borrow_and_update());
+            }
+            let cycle = collector.collect().await;
+            let reader_status = collector.status();
 
-    // Set up socket cleanup on shutdown
-    let path_clone = socket_path.clone();
-    let cleanup_handle = tokio::spawn(async move {
-        tokio::signal::ctrl_c()
-            .await
-            .expect("Failed to listen for Ctrl+C");
-        cleanup_socket(&path_clone);
-    });
+            // Collect disk/storage info (cached in state to avoid per-request collection)
+            let storage_info = collect_storage_info();
 
-    // Run both servers concurrently
-    tokio::select! {
-        result = axum::serve(tcp_listener, app) => {
-            if let Err(e) = result {
-                tracing::error!("TCP server error: {e}");
+            {
+                let mut health = health_clone.write().await;
+                for status in &reader_status {
+                    health.record(status);
+                }
+                health.retain_readers(&reader_status);
             }
-        }
-        result = axum::serve(unix_listener, app_clone) => {
-            if let Err(e) = result {
-                tracing::error!("Unix socket server error: {e}");
+            self_metrics_clone.record_cycle(&reader_status);
+
+            let mut state = state_clone.write().await;
+            state.gpu_info = cycle.gpu_info;
+            state.cpu_info = cycle.cpu_info;
+            state.memory_info = cycle.memory_info;
+            state.process_info = cycle.process_info;
+            state.storage_info = storage_info;
+            state.chassis_info = cycle.chassis_info;
+            state.reader_status = reader_status;
+            state.mark_data_changed();
+            if state.loading {
+                state.loading = false;
             }
-        }
-    }
+            let data_version = state.data_version;
 
-    // Cancel cleanup handle and do cleanup
-    cleanup_handle.abort();
-    cleanup_socket(&socket_path);
-}
+            drop(state);
 
-/// Clean up the Unix domain socket file.
-/// Uses atomic remove to avoid TOCTOU race conditions.
-#[cfg(unix)]
-fn cleanup_socket(path: &std::path::Path) {
-    match std::fs::remove_file(path) {
-        Ok(()) => {
-            tracing::info!("Cleaned up socket file: {}", path.display());
-        }
-        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
-            // File already removed, that's fine
-        }
-        Err(e) => {
-            tracing::warn!("Failed to remove socket file on shutdown: {e}");
+            // Render /metrics once for every scrape of this cycle. Self-metrics
+            // change between scrapes, so each scrape appends its own.
+            let render_started = Instant::now();
+            let builder = collect_metrics(
+                &*state_clone.read().await,
+                &*export_options_clone.read().await,
+            );
+            exposition_clone.update(builder, data_version);
+            self_metrics_clone.record_render(render_started.elapsed());
+
+            health_clone.write().await.finish_cycle();
+            update_tx.send_replace(data_version);
+            tokio::time::sleep(Duration::from_secs(interval)).await;
         }
+    });
+
+    #[cfg(unix)]
+    tokio::spawn(reload_on_hangup(Reloader {
+        state: state.clone(),
+        export_options: export_options.clone(),
+        self_metrics: self_metrics.clone(),
+        readers: readers_tx,
+    }));
+    #[cfg(not(unix))]
+    drop(readers_tx);
+
+    #[cfg(unix)]
+    tokio::spawn(systemd::notify_service_manager(
+        update_rx.clone(),
+        health.clone(),
+    ));
+
+    // Start push sinks; they pick up each completed collection cycle
+    if let Some(config) = InfluxDbConfig::from_args(args) {
+        tokio::spawn(run_influxdb_sink(config, state.clone(), update_rx.clone()));
+    }
+    if let Some(config) = StatsdConfig::from_args(args) {
+        tokio::spawn(run_statsd_sink(config, state.clone(), update_rx.clone()));
+    }
+    if let Some(config) = OtlpConfig::from_args(args) {
+        tokio::spawn(run_otlp_sink(config, state.clone(), update_rx.clone()));
     }
+    if let Some(config) = RemoteWriteConfig::from_args(args) {
+        tokio::spawn(run_remote_write_sender(
+            config,
+            state.clone(),
+            export_options.clone(),
+            update_rx.clone(),
+        ));
+    }
+
+    // Create the router with shared state
+    let api_state = ApiState {
+        app_state: state,
+        updates: update_rx,
+        health,
+        self_metrics,
+        exposition,
+        export_options,
+    };
+    let mut app = Router::new()
+        .route("/metrics", get(metrics_handler))
+        .route("/api/v1/snapshot", get(snapshot_handler))
+        .route("/api/v1/gpus", get(gpus_handler))
+        .route("/api/v1/cpus", get(cpus_handler))
+        .route("/api/v1/memory", get(memory_handler))
+        .route("/api/v1/storage", get(storage_handler))
+        .route("/api/v1/chassis", get(chassis_handler))
+        .route("/api/v1/processes", get(processes_handler))
+        .route("/api/v1/stream", get(stream_handler))
+        .with_state(api_state.clone());
+    if let Some(store) = token_store {
+        tokio::spawn(watch_token_file(store.clone()));
+        // Added before CORS so preflight requests are answered without a token
+        app = app.layer(middleware::from_fn_with_state(store, require_bearer_token));
+    }
+    // Probes are merged after authentication so kubelets need no token
+    let probes = Router::new()
+        .route("/healthz", get(healthz_handler))
+        .route("/readyz", get(readyz_handler))
+        .with_state(api_state);
+    let app = app
+        .merge(probes)
+        .layer(
+            CorsLayer::new()
+                .allow_origin(Any)
+                .allow_methods(Any)
+                .allow_headers(Any),
+        )
+        .layer(TraceLayer::new_for_http());
+
+    listen::serve(listeners, app, tls_acceptor).await;
 }
 
 /// Collect storage/disk information
@@ -0,0 +1,218 @@
+// Copyright 2025 Lablup Inc. and Jeongkyu Shin
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! JSON snapshot endpoints for API mode
+//!
+//! Serves the data cached in `AppState` by the background collection loop as
+//! typed JSON, so clients that need structured device data do not have to
+//! parse the Prometheus exposition format:
+//! - `/api/v1/snapshot`: every section in one document
+//! - `/api/v1/{gpus,cpus,memory,storage,chassis,processes}`: a single section
+
+use std::collections::BTreeMap;
+
+use axum::extract::State;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use serde::Serialize;
+
+use crate::app_state::AppState;
+use crate::device::{ChassisInfo, CpuInfo, GpuInfo, MemoryInfo, ProcessInfo};
+use crate::storage::info::StorageInfo;
+use crate::utils::{get_hostname, RuntimeEnvironment};
+
+use super::handlers::SharedState;
+
+/// Version of the JSON document layout.
+/// Bump this whenever a field is renamed or removed from the snapshot types.
+pub const SNAPSHOT_SCHEMA_VERSION: u32 = 1;
+
+/// Full snapshot of the cached collection data
+#[derive(Serialize)]
+pub struct Snapshot<'a> {
+    pub schema_version: u32,
+    pub hostname: String,
+    pub timestamp: String,
+    pub data_version: u64,
+    pub labels: &'a BTreeMap<String, String>,
+    pub gpus: &'a [GpuInfo],
+    pub cpus: &'a [CpuInfo],
+    pub memory: &'a [MemoryInfo],
+    pub storage: &'a [StorageInfo],
+    pub chassis: &'a [ChassisInfo],
+    pub processes: &'a [ProcessInfo],
+    pub runtime: RuntimeSnapshot<'a>,
+}
+
+impl<'a> Snapshot<'a> {
+    pub fn from_state(state: &'a AppState) -> Self {
+        Self {
+            schema_version: SNAPSHOT_SCHEMA_VERSION,
+            hostname: get_hostname(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            data_version: state.data_version,
+            labels: &state.node_labels,
+            gpus: &state.gpu_info,
+            cpus: &state.cpu_info,
+            memory: &state.memory_info,
+            storage: &state.storage_info,
+            chassis: &state.chassis_info,
+            processes: &state.process_info,
+            runtime: RuntimeSnapshot::new(&state.runtime_environment),
+        }
+    }
+}
+
+/// A single section of the snapshot wrapped with the common envelope fields
+#[derive(Serialize)]
+pub struct SectionSnapshot<'a, T: Serialize> {
+    pub schema_version: u32,
+    pub hostname: String,
+    pub timestamp: String,
+    pub data_version: u64,
+    pub labels: &'a BTreeMap<String, String>,
+    pub data: &'a [T],
+}
+
+impl<'a, T: Serialize> SectionSnapshot<'a, T> {
+    pub fn new(state: &'a AppState, data: &'a [T]) -> Self {
+        Self {
+            schema_version: SNAPSHOT_SCHEMA_VERSION,
+            hostname: get_hostname(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            data_version: state.data_version,
+            labels: &state.node_labels,
+            data,
+        }
+    }
+}
+
+/// Serializable view of the detected runtime environment
+#[derive(Serialize)]
+pub struct RuntimeSnapshot<'a> {
+    pub container_runtime: &'a str,
+    pub container_id: Option<&'a str>,
+    pub pod_name: Option<&'a str>,
+    pub namespace: Option<&'a str>,
+    pub virtualization: &'a str,
+    pub hypervisor: Option<&'a str>,
+}
+
+impl<'a> RuntimeSnapshot<'a> {
+    pub fn new(runtime_env: &'a RuntimeEnvironment) -> Self {
+        let container = &runtime_env.container;
+        let virtualization = &runtime_env.virtualization;
+        Self {
+            container_runtime: container.runtime.as_str(),
+            container_id: container.container_id.as_deref(),
+            pod_name: container.pod_name.as_deref(),
+            namespace: container.namespace.as_deref(),
+            virtualization: virtualization.vm_type.as_str(),
+            hypervisor: virtualization.hypervisor.as_deref(),
+        }
+    }
+}
+
+// `Json` serializes eagerly in `into_response`, so the borrowed snapshot
+// never outlives the read guard.
+
+pub async fn snapshot_handler(State(state): State<SharedState>) -> Response {
+    let state = state.read().await;
+    Json(Snapshot::from_state(&state)).into_response()
+}
+
+pub async fn gpus_handler(State(state): State<SharedState>) -> Response {
+    let state = state.read().await;
+    Json(SectionSnapshot::new(&state, &state.gpu_info)).into_response()
+}
+
+pub async fn cpus_handler(State(state): State<SharedState>) -> Response {
+    let state = state.read().await;
+    Json(SectionSnapshot::new(&state, &state.cpu_info)).into_response()
+}
+
+pub async fn memory_handler(State(state): State<SharedState>) -> Response {
+    let state = state.read().await;
+    Json(SectionSnapshot::new(&state, &state.memory_info)).into_response()
+}
+
+pub async fn storage_handler(State(state): State<SharedState>) -> Response {
+    let state = state.read().await;
+    Json(SectionSnapshot::new(&state, &state.storage_info)).into_response()
+}
+
+pub async fn chassis_handler(State(state): State<SharedState>) -> Response {
+    let state = state.read().await;
+    Json(SectionSnapshot::new(&state, &state.chassis_info)).into_response()
+}
+
+pub async fn processes_handler(State(state): State<SharedState>) -> Response {
+    let state = state.read().await;
+    Json(SectionSnapshot::new(&state, &state.process_info)).into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_state() -> AppState {
+        let mut state = AppState::new();
+        state.storage_info.push(StorageInfo {
+            mount_point: "/".to_string(),
+            total_bytes: 1000,
+            available_bytes: 400,
+            host_id: "test-host".to_string(),
+            hostname: "test-host".to_string(),
+            index: 0,
+        });
+        state.chassis_info.push(ChassisInfo {
+            hostname: "test-host".to_string(),
+            total_power_watts: Some(120.0),
+            ..Default::default()
+        });
+        state.data_version = 7;
+        state
+            .node_labels
+            .insert("cluster".to_string(), "alpha".to_string());
+        state
+    }
+
+    #[test]
+    fn test_snapshot_contains_all_sections() {
+        let state = test_state();
+        let value = serde_json::to_value(Snapshot::from_state(&state)).unwrap();
+
+        assert_eq!(value["schema_version"], SNAPSHOT_SCHEMA_VERSION);
+        assert_eq!(value["data_version"], 7);
+        for section in ["gpus", "cpus", "memory", "storage", "chassis", "processes"] {
+            assert!(value[section].is_array(), "missing section {section}");
+        }
+        assert_eq!(value["storage"][0]["mount_point"], "/");
+        assert_eq!(value["chassis"][0]["total_power_watts"], 120.0);
+        assert!(value["runtime"]["container_runtime"].is_string());
+        assert_eq!(value["labels"]["cluster"], "alpha");
+    }
+
+    #[test]
+    fn test_section_snapshot_envelope() {
+        let state = test_state();
+        let value =
+            serde_json::to_value(SectionSnapshot::new(&state, &state.storage_info)).unwrap();
+
+        assert_eq!(value["schema_version"], SNAPSHOT_SCHEMA_VERSION);
+        assert_eq!(value["data"].as_array().unwrap().len(), 1);
+        assert_eq!(value["data"][0]["available_bytes"], 400);
+        assert_eq!(value["labels"]["cluster"], "alpha");
+    }
+}
@@ -0,0 +1,220 @@
+// Copyright 2025 Lablup Inc. and Jeongkyu Shin
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Server-Sent Events stream of collection updates
+//!
+//! `/api/v1/stream` pushes one frame per completed collection cycle instead of
+//! making clients poll. The first frame is always a full `snapshot` event.
+//! With `?delta=true`, later frames are `delta` events holding a JSON merge
+//! patch (RFC 7386) against the previous frame; cycles that change nothing
+//! but `timestamp` and `data_version` are skipped.
+
+use std::convert::Infallible;
+
+use axum::extract::{Query, State};
+use axum::response::sse::{Event, KeepAlive, Sse};
+use futures_util::stream::{self, Stream};
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use tokio::sync::watch;
+
+use super::handlers::{ApiState, SharedState};
+use super::snapshot::Snapshot;
+
+#[derive(Debug, Default, Deserialize)]
+pub struct StreamParams {
+    /// Send only the fields that changed since the previous frame
+    #[serde(default)]
+    pub delta: bool,
+}
+
+struct StreamContext {
+    app_state: SharedState,
+    updates: watch::Receiver<u64>,
+    delta: bool,
+    previous: Option<Value>,
+}
+
+pub async fn stream_handler(
+    State(state): State<ApiState>,
+    Query(params): Query<StreamParams>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let mut updates = state.updates.clone();
+    // The first frame is sent right away, so the current version counts as seen
+    updates.mark_unchanged();
+
+    let context = StreamContext {
+        app_state: state.app_state,
+        updates,
+        delta: params.delta,
+        previous: None,
+    };
+
+    let events = stream::unfold(context, |mut ctx| async move {
+        loop {
+            if ctx.previous.is_some() && ctx.updates.changed().await.is_err() {
+                // Collection loop is gone; end the stream
+                return None;
+            }
+
+            let (version, current) = {
+                let state = ctx.app_state.read().await;
+                let value =
+                    serde_json::to_value(Snapshot::from_state(&state)).unwrap_or(Value::Null);
+                (state.data_version, value)
+            };
+
+            let event = match ctx.previous.as_ref() {
+                Some(previous) if ctx.delta => match delta_patch(previous, &current) {
+                    Some(patch) => Event::default().event("delta").json_data(patch),
+                    None => continue,
+                },
+                _ => Event::default().event("snapshot").json_data(&current),
+            };
+            ctx.previous = Some(current);
+
+            match event {
+                Ok(event) => return Some((Ok(event.id(version.to_string())), ctx)),
+                Err(e) => {
+                    tracing::warn!("Failed to encode stream frame: {e}");
+                    continue;
+                }
+            }
+        }
+    });
+
+    Sse::new(events).keep_alive(KeepAlive::default())
+}
+
+/// Snapshot fields that change every cycle whether or not the data did
+const VOLATILE_FIELDS: [&str; 2] = ["timestamp", "data_version"];
+
+/// Merge patch between two snapshot documents, or `None` when nothing but
+/// the volatile fields changed. A patch that is sent carries the current
+/// volatile fields too, so clients stay in step with the event `id`.
+fn delta_patch(previous: &Value, current: &Value) -> Option<Value> {
+    let strip = |doc: &Value| {
+        let mut doc = doc.clone();
+        if let Value::Object(map) = &mut doc {
+            for field in VOLATILE_FIELDS {
+                map.remove(field);
+            }
+        }
+        doc
+    };
+
+    let mut patch = merge_patch(&strip(previous), &strip(current))?;
+    if let (Value::Object(patch_map), Value::Object(current_map)) = (&mut patch, current) {
+        for field in VOLATILE_FIELDS {
+            if let Some(value) = current_map.get(field) {
+                patch_map.insert(field.to_string(), value.clone());
+            }
+        }
+    }
+    Some(patch)
+}
+
+/// Build a JSON merge patch (RFC 7386) that turns `old` into `new`.
+/// Returns `None` when the documents are identical. Arrays are replaced as a
+/// whole, and keys missing from `new` are emitted as `null`; a value that
+/// becomes `null` is therefore indistinguishable from a removed key.
+pub fn merge_patch(old: &Value, new: &Value) -> Option<Value> {
+    if old == new {
+        return None;
+    }
+
+    match (old, new) {
+        (Value::Object(old_map), Value::Object(new_map)) => {
+            let mut patch = Map::new();
+            for (key, new_value) in new_map {
+                match old_map.get(key) {
+                    Some(old_value) => {
+                        if let Some(child) = merge_patch(old_value, new_value) {
+                            patch.insert(key.clone(), child);
+                        }
+                    }
+                    None => {
+                        patch.insert(key.clone(), new_value.clone());
+                    }
+                }
+            }
+            for key in old_map.keys() {
+                if !new_map.contains_key(key) {
+                    patch.insert(key.clone(), Value::Null);
+                }
+            }
+            Some(Value::Object(patch))
+        }
+        _ => Some(new.clone()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app_state::AppState;
+    use serde_json::json;
+
+    #[test]
+    fn test_merge_patch_identical() {
+        let doc = json!({"a": 1, "b": {"c": [1, 2]}});
+        assert_eq!(merge_patch(&doc, &doc), None);
+    }
+
+    #[test]
+    fn test_merge_patch_nested_change() {
+        let old = json!({"data_version": 1, "memory": {"used": 10, "total": 100}});
+        let new = json!({"data_version": 2, "memory": {"used": 20, "total": 100}});
+        assert_eq!(
+            merge_patch(&old, &new),
+            Some(json!({"data_version": 2, "memory": {"used": 20}}))
+        );
+    }
+
+    #[test]
+    fn test_merge_patch_added_and_removed_keys() {
+        let old = json!({"a": 1, "gone": true});
+        let new = json!({"a": 1, "added": "x"});
+        assert_eq!(
+            merge_patch(&old, &new),
+            Some(json!({"gone": null, "added": "x"}))
+        );
+    }
+
+    #[test]
+    fn test_delta_patch_skips_unchanged_state() {
+        let mut state = AppState::new();
+        let first = serde_json::to_value(Snapshot::from_state(&state)).unwrap();
+        state.mark_data_changed();
+        let second = serde_json::to_value(Snapshot::from_state(&state)).unwrap();
+        assert_eq!(delta_patch(&first, &second), None);
+
+        state
+            .node_labels
+            .insert("rack".to_string(), "r1".to_string());
+        state.mark_data_changed();
+        let third = serde_json::to_value(Snapshot::from_state(&state)).unwrap();
+        let patch = delta_patch(&second, &third).unwrap();
+        assert_eq!(patch["labels"], json!({"rack": "r1"}));
+        assert_eq!(patch["data_version"], 2);
+        assert_eq!(patch["timestamp"], third["timestamp"]);
+    }
+
+    #[test]
+    fn test_merge_patch_replaces_arrays() {
+        let old = json!({"gpus": [{"utilization": 10.0}, {"utilization": 20.0}]});
+        let new = json!({"gpus": [{"utilization": 10.0}, {"utilization": 30.0}]});
+        assert_eq!(merge_patch(&old, &new), Some(new.clone()));
+    }
+}
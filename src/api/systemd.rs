@@ -0,0 +1,70 @@
+// Copyright 2025 Lablup Inc. and Jeongkyu Shin
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! systemd socket activation and service notifications
+//!
+//! As a `Type=notify` service, all-smi sends `READY=1` once its listeners are
+//! bound and the first collection cycle has completed. With `WatchdogSec=`
+//! set, `WATCHDOG=1` is sent at half the interval for as long as the
+//! collection loop keeps completing cycles, so a stalled loop gets the
+//! service restarted. Outside systemd all of this is a no-op.
+
+use std::io;
+use std::os::fd::{FromRawFd, OwnedFd};
+use std::time::{Duration, Instant};
+
+use sd_notify::NotifyState;
+use tokio::sync::watch;
+
+use super::health::SharedHealth;
+use super::listen::Listener;
+
+/// Sockets passed in `LISTEN_FDS`, empty when not socket-activated
+pub fn activated_listeners() -> io::Result<Vec<Listener>> {
+    sd_notify::listen_fds()?
+        .map(|fd| {
+            // SAFETY: descriptors listed in LISTEN_FDS are open and handed
+            // over to this process, which takes them exactly once
+            let fd = unsafe { OwnedFd::from_raw_fd(fd) };
+            Listener::from_fd(fd)
+        })
+        .collect()
+}
+
+/// Report readiness after the first collection cycle, then keep the
+/// watchdog fed while the collection loop is alive
+pub async fn notify_service_manager(mut updates: watch::Receiver<u64>, health: SharedHealth) {
+    if updates.wait_for(|version| *version > 0).await.is_err() {
+        return;
+    }
+    if let Err(e) = sd_notify::notify(false, &[NotifyState::Ready]) {
+        tracing::warn!("Failed to notify systemd of readiness: {e}");
+    }
+
+    let mut watchdog_usec = 0;
+    if !sd_notify::watchdog_enabled(false, &mut watchdog_usec) || watchdog_usec == 0 {
+        return;
+    }
+    let mut ticker = tokio::time::interval(Duration::from_micros(watchdog_usec / 2));
+    loop {
+        ticker.tick().await;
+        if health.read().await.is_stale(Instant::now()) {
+            tracing::warn!("Collection loop is stalled; withholding systemd watchdog ping");
+            continue;
+        }
+        if let Err(e) = sd_notify::notify(false, &[NotifyState::Watchdog]) {
+            tracing::warn!("Failed to ping systemd watchdog: {e}");
+        }
+    }
+}
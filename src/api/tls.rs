@@ -0,0 +1,309 @@
+// Copyright 2025 Lablup Inc. and Jeongkyu Shin
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! TLS and mutual TLS for the TCP listener
+//!
+//! Handshakes run in their own tasks so a slow or stalled client cannot hold
+//! up the accept loop; only completed TLS connections are handed to axum.
+
+use std::io;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use rustls::server::WebPkiClientVerifier;
+use rustls::{RootCertStore, ServerConfig};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc;
+use tokio_rustls::server::TlsStream;
+use tokio_rustls::TlsAcceptor;
+
+use crate::cli::ApiArgs;
+
+const HANDSHAKE_TIMEOUT_SECS: u64 = 10;
+const ACCEPT_QUEUE: usize = 64;
+
+#[derive(Debug, Clone)]
+pub struct TlsConfig {
+    pub cert: PathBuf,
+    pub key: PathBuf,
+    /// CA bundle used to verify client certificates; enables mutual TLS
+    pub client_ca: Option<PathBuf>,
+}
+
+impl TlsConfig {
+    /// Build the TLS configuration, or `None` when no certificate was given
+    pub fn from_args(args: &ApiArgs) -> Option<Self> {
+        Some(Self {
+            cert: PathBuf::from(args.tls_cert.as_ref()?),
+            key: PathBuf::from(args.tls_key.as_ref()?),
+            client_ca: args.tls_client_ca.as_ref().map(PathBuf::from),
+        })
+    }
+
+    /// Load the certificates and key into a TLS acceptor
+    pub fn acceptor(&self) -> Result<TlsAcceptor, String> {
+        let provider = Arc::new(rustls::crypto::ring::default_provider());
+        let certs = load_certs(&self.cert)?;
+        let key = PrivateKeyDer::from_pem_file(&self.key)
+            .map_err(|e| format!("Failed to read private key {}: {e}", self.key.display()))?;
+
+        let builder = ServerConfig::builder_with_provider(provider.clone())
+            .with_safe_default_protocol_versions()
+            .map_err(|e| format!("Failed to configure TLS: {e}"))?;
+        let builder = match &self.client_ca {
+            Some(path) => {
+                let mut roots = RootCertStore::empty();
+                for cert in load_certs(path)? {
+                    roots.add(cert).map_err(|e| {
+                        format!("Invalid CA certificate in {}: {e}", path.display())
+                    })?;
+                }
+                let verifier =
+                    WebPkiClientVerifier::builder_with_provider(Arc::new(roots), provider)
+                        .build()
+                        .map_err(|e| format!("Failed to configure client verification: {e}"))?;
+                builder.with_client_cert_verifier(verifier)
+            }
+            None => builder.with_no_client_auth(),
+        };
+
+        let mut config = builder
+            .with_single_cert(certs, key)
+            .map_err(|e| format!("Invalid TLS certificate or key: {e}"))?;
+        config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+        Ok(TlsAcceptor::from(Arc::new(config)))
+    }
+}
+
+fn load_certs(path: &Path) -> Result<Vec<CertificateDer<'static>>, String> {
+    let certs = CertificateDer::pem_file_iter(path)
+        .and_then(|iter| iter.collect::<Result<Vec<_>, _>>())
+        .map_err(|e| format!("Failed to read certificates from {}: {e}", path.display()))?;
+    if certs.is_empty() {
+        return Err(format!("No certificates found in {}", path.display()));
+    }
+    Ok(certs)
+}
+
+/// TCP listener that yields TLS streams
+pub struct TlsListener {
+    local_addr: SocketAddr,
+    incoming: mpsc::Receiver<(TlsStream<TcpStream>, SocketAddr)>,
+}
+
+impl TlsListener {
+    pub fn new(listener: TcpListener, acceptor: TlsAcceptor) -> io::Result<Self> {
+        let local_addr = listener.local_addr()?;
+        let (tx, incoming) = mpsc::channel(ACCEPT_QUEUE);
+        tokio::spawn(accept_loop(listener, acceptor, tx));
+        Ok(Self {
+            local_addr,
+            incoming,
+        })
+    }
+}
+
+async fn accept_loop(
+    listener: TcpListener,
+    acceptor: TlsAcceptor,
+    tx: mpsc::Sender<(TlsStream<TcpStream>, SocketAddr)>,
+) {
+    while !tx.is_closed() {
+        let (stream, addr) = match listener.accept().await {
+            Ok(conn) => conn,
+            Err(e) => {
+                // Typically EMFILE; back off instead of spinning
+                tracing::warn!("Failed to accept TCP connection: {e}");
+                tokio::time::sleep(Duration::from_secs(1)).await;
+                continue;
+            }
+        };
+
+        let acceptor = acceptor.clone();
+        let tx = tx.clone();
+        tokio::spawn(async move {
+            let handshake = tokio::time::timeout(
+                Duration::from_secs(HANDSHAKE_TIMEOUT_SECS),
+                acceptor.accept(stream),
+            );
+            match handshake.await {
+                Ok(Ok(stream)) => {
+                    let _ = tx.send((stream, addr)).await;
+                }
+                Ok(Err(e)) => tracing::debug!("TLS handshake with {addr} failed: {e}"),
+                Err(_) => tracing::debug!("TLS handshake with {addr} timed out"),
+            }
+        });
+    }
+}
+
+impl axum::serve::Listener for TlsListener {
+    type Io = TlsStream<TcpStream>;
+    type Addr = SocketAddr;
+
+    async fn accept(&mut self) -> (Self::Io, Self::Addr) {
+        match self.incoming.recv().await {
+            Some(conn) => conn,
+            // The accept loop never exits while we hold the receiver
+            None => std::future::pending().await,
+        }
+    }
+
+    fn local_addr(&self) -> io::Result<Self::Addr> {
+        Ok(self.local_addr)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::routing::get;
+    use axum::Router;
+    use rcgen::{
+        BasicConstraints, CertificateParams, DnType, ExtendedKeyUsagePurpose, IsCa, Issuer, KeyPair,
+    };
+
+    fn params(names: &[&str], common_name: &str) -> CertificateParams {
+        let names = names.iter().map(|n| n.to_string()).collect::<Vec<_>>();
+        let mut params = CertificateParams::new(names).unwrap();
+        // Distinct subjects, or OpenSSL treats leaf certificates as self-signed
+        params
+            .distinguished_name
+            .push(DnType::CommonName, common_name);
+        params
+    }
+
+    struct TestPki {
+        dir: tempfile::TempDir,
+        ca_pem: String,
+        client_cert_pem: String,
+        client_key_pem: String,
+    }
+
+    fn write_pki() -> TestPki {
+        let dir = tempfile::tempdir().unwrap();
+
+        let ca_key = KeyPair::generate().unwrap();
+        let mut ca_params = params(&[], "all-smi test CA");
+        ca_params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+        let ca_cert = ca_params.self_signed(&ca_key).unwrap();
+        let issuer = Issuer::new(ca_params, ca_key);
+
+        let server_key = KeyPair::generate().unwrap();
+        let server_cert = params(&["localhost"], "localhost")
+            .signed_by(&server_key, &issuer)
+            .unwrap();
+
+        let client_key = KeyPair::generate().unwrap();
+        let mut client_params = params(&["client"], "client");
+        client_params.extended_key_usages = vec![ExtendedKeyUsagePurpose::ClientAuth];
+        let client_cert = client_params.signed_by(&client_key, &issuer).unwrap();
+
+        std::fs::write(dir.path().join("ca.pem"), ca_cert.pem()).unwrap();
+        std::fs::write(dir.path().join("server.pem"), server_cert.pem()).unwrap();
+        std::fs::write(dir.path().join("server.key"), server_key.serialize_pem()).unwrap();
+
+        TestPki {
+            dir,
+            ca_pem: ca_cert.pem(),
+            client_cert_pem: client_cert.pem(),
+            client_key_pem: client_key.serialize_pem(),
+        }
+    }
+
+    async fn serve(config: TlsConfig) -> SocketAddr {
+        let acceptor = config.acceptor().unwrap();
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let listener = TlsListener::new(listener, acceptor).unwrap();
+        let addr = axum::serve::Listener::local_addr(&listener).unwrap();
+        let app = Router::new().route("/metrics", get(|| async { "ok" }));
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+        addr
+    }
+
+    fn client(pki: &TestPki, identity: bool) -> reqwest::Client {
+        let mut builder = reqwest::Client::builder()
+            .add_root_certificate(reqwest::Certificate::from_pem(pki.ca_pem.as_bytes()).unwrap());
+        if identity {
+            builder = builder.identity(
+                reqwest::Identity::from_pkcs8_pem(
+                    pki.client_cert_pem.as_bytes(),
+                    pki.client_key_pem.as_bytes(),
+                )
+                .unwrap(),
+            );
+        }
+        builder.build().unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_tls_listener() {
+        let pki = write_pki();
+        let addr = serve(TlsConfig {
+            cert: pki.dir.path().join("server.pem"),
+            key: pki.dir.path().join("server.key"),
+            client_ca: None,
+        })
+        .await;
+
+        let url = format!("https://localhost:{}/metrics", addr.port());
+        let body = client(&pki, false)
+            .get(&url)
+            .send()
+            .await
+            .unwrap()
+            .text()
+            .await
+            .unwrap();
+        assert_eq!(body, "ok");
+
+        // Plain HTTP is not served on a TLS listener
+        let plain = format!("http://localhost:{}/metrics", addr.port());
+        assert!(reqwest::get(&plain).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_mutual_tls_requires_client_certificate() {
+        let pki = write_pki();
+        let addr = serve(TlsConfig {
+            cert: pki.dir.path().join("server.pem"),
+            key: pki.dir.path().join("server.key"),
+            client_ca: Some(pki.dir.path().join("ca.pem")),
+        })
+        .await;
+
+        let url = format!("https://localhost:{}/metrics", addr.port());
+        assert!(client(&pki, false).get(&url).send().await.is_err());
+
+        let response = client(&pki, true).get(&url).send().await.unwrap();
+        assert!(response.status().is_success());
+    }
+
+    #[test]
+    fn test_acceptor_reports_missing_files() {
+        let config = TlsConfig {
+            cert: PathBuf::from("/nonexistent/cert.pem"),
+            key: PathBuf::from("/nonexistent/key.pem"),
+            client_ca: None,
+        };
+        let err = config.acceptor().err().unwrap();
+        assert!(err.contains("/nonexistent/cert.pem"));
+    }
+}
@@ -17,7 +17,7 @@ use crate::storage::info::StorageInfo;
 use crate::ui::notification::NotificationManager;
 use crate::utils::RuntimeEnvironment;
 use std::cmp::Ordering;
-use std::collections::{HashMap, VecDeque};
+use std::collections::{BTreeMap, HashMap, VecDeque};
 use std::time::{Duration, Instant};
 
 #[derive(Clone, Debug)]
@@ -31,6 +31,26 @@ pub struct ConnectionStatus {
     pub consecutive_failures: u32,
     pub last_error: Option<String>,
     pub last_update: Instant,
+    /// When a host that is backing off will next be contacted
+    pub next_retry: Option<Instant>,
+}
+
+/// Collection status of one device reader in API mode
+#[derive(Clone, Debug)]
+pub struct ReaderStatus {
+    pub kind: &'static str,
+    pub name: &'static str,
+    /// Devices in the data currently published for this reader
+    pub device_count: usize,
+    /// The reader overran its deadline and its last good data is being served
+    pub stale: bool,
+    /// Time since the published data was read, `None` before the first read
+    pub data_age: Option<Duration>,
+    /// Calls that exceeded the deadline since startup
+    pub timeouts_total: u64,
+    /// Time spent waiting on the reader this cycle, `None` when no call was
+    /// made because an earlier one is still running
+    pub call_duration: Option<Duration>,
 }
 
 impl ConnectionStatus {
@@ -44,6 +64,7 @@ impl ConnectionStatus {
             consecutive_failures: 0,
             last_error: None,
             last_update: Instant::now(),
+            next_retry: None,
         }
     }
 
@@ -122,6 +143,10 @@ pub struct AppState {
     pub runtime_environment: RuntimeEnvironment,
     /// Version counter that increments when data changes, used to detect if re-render is needed
     pub data_version: u64,
+    /// Per-reader collection status (API mode)
+    pub reader_status: Vec<ReaderStatus>,
+    /// Site labels added to every exported series and JSON document (API mode)
+    pub node_labels: BTreeMap<String, String>,
 }
 
 #[derive(Clone, Copy, PartialEq, Debug)]
@@ -213,6 +238,8 @@ impl AppState {
             is_local_mode: true, // Default to local mode
             runtime_environment: RuntimeEnvironment::detect(),
             data_version: 0,
+            reader_status: Vec::new(),
+            node_labels: BTreeMap::new(),
         }
     }
 
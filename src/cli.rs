@@ -12,15 +12,21 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
 
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
 pub struct Cli {
     #[command(subcommand)]
     pub command: Option<Commands>,
+    /// Config file to use instead of the system and user config files. Falls back to
+    /// the ALL_SMI_CONFIG environment variable.
+    #[arg(long, global = true, value_name = "PATH")]
+    pub config: Option<String>,
 }
 
+// Parsed once at startup, so the size of the API variant does not matter
+#[allow(clippy::large_enum_variant)]
 #[derive(Subcommand)]
 pub enum Commands {
     /// Run in API mode, exposing metrics in Prometheus format.
@@ -29,6 +35,8 @@ pub enum Commands {
     Local(LocalArgs),
     /// Run in remote view mode, monitoring remote nodes via API endpoints.
     View(ViewArgs),
+    /// Inspect the configuration file.
+    Config(ConfigArgs),
 }
 
 #[derive(Parser)]
@@ -36,12 +44,50 @@ pub struct ApiArgs {
     /// The port to listen on for the API server. Use 0 to disable TCP listener.
     #[arg(short, long, default_value_t = 9090)]
     pub port: u16,
+    /// Address to listen on: IP:PORT (e.g. 127.0.0.1:9090, [::]:9090) or unix:PATH.
+    /// Can be repeated; replaces the --port listener. Ignored under systemd socket
+    /// activation, where the passed sockets are used.
+    #[arg(long, value_name = "ADDR")]
+    pub listen: Vec<String>,
     /// The interval in seconds at which to update the GPU information.
     #[arg(short, long, default_value_t = 3)]
     pub interval: u64,
     /// Include the process list in the API output.
     #[arg(long)]
     pub processes: bool,
+    /// How process metrics are keyed: one series per process (pid), or summed per
+    /// process name, user, container or Slurm job.
+    #[arg(long, value_enum, default_value_t = ProcessAggregation::Pid)]
+    pub process_aggregation: ProcessAggregation,
+    /// Export only the N process series using the most GPU memory; the rest are summed
+    /// into a series labelled "other".
+    #[arg(long, value_name = "N")]
+    pub process_top_n: Option<usize>,
+    /// Comma-separated labels to keep on process metrics (pid, name, user, container,
+    /// job, device_id, device_uuid). Series that differ only in dropped labels are summed.
+    #[arg(long, value_name = "LABELS", value_delimiter = ',')]
+    pub process_labels: Option<Vec<String>>,
+    /// Seconds without a completed collection cycle (or without data from a reader) after
+    /// which /healthz and /readyz fail. Defaults to ten intervals, at least 30 seconds.
+    #[arg(long, value_name = "SECONDS")]
+    pub stale_after: Option<u64>,
+    /// Seconds a single device reader may take per cycle. A reader that overruns keeps
+    /// serving its last data, marked stale, while the other readers keep updating.
+    #[arg(
+        long,
+        value_name = "SECONDS",
+        default_value_t = 10,
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    pub reader_timeout: u64,
+    /// Label (key=value) added to every exported series and JSON document. Can be
+    /// repeated; overrides the same key from --labels-file.
+    #[arg(long = "label", value_name = "KEY=VALUE")]
+    pub labels: Vec<String>,
+    /// File with labels to add, one key=value per line (# starts a comment).
+    /// Re-read on SIGHUP.
+    #[arg(long, value_name = "PATH")]
+    pub labels_file: Option<String>,
     /// Unix domain socket path for local IPC (Unix only).
     /// When specified without a value, uses platform default:
     /// - Linux: /var/run/all-smi.sock (fallback to /tmp/all-smi.sock if no permission)
@@ -49,6 +95,116 @@ pub struct ApiArgs {
     #[cfg(unix)]
     #[arg(short, long, num_args = 0..=1, default_missing_value = "")]
     pub socket: Option<String>,
+    /// File with accepted bearer tokens, one per line. When set, every request must carry
+    /// `Authorization: Bearer <token>`. The file is re-read when it changes.
+    #[arg(long, value_name = "PATH")]
+    pub auth_token_file: Option<String>,
+    /// PEM certificate chain for serving HTTPS on the TCP listener.
+    #[arg(long, value_name = "PATH", requires = "tls_key")]
+    pub tls_cert: Option<String>,
+    /// PEM private key for --tls-cert.
+    #[arg(long, value_name = "PATH", requires = "tls_cert")]
+    pub tls_key: Option<String>,
+    /// PEM CA bundle for verifying client certificates (mutual TLS). Clients without a
+    /// certificate signed by one of these CAs are refused.
+    #[arg(long, value_name = "PATH", requires = "tls_cert")]
+    pub tls_client_ca: Option<String>,
+    /// InfluxDB v2 write URL to push metrics to in line protocol,
+    /// e.g. http://localhost:8086/api/v2/write?org=myorg&bucket=gpu
+    #[arg(long, value_name = "URL")]
+    pub influxdb_url: Option<String>,
+    /// InfluxDB API token. Falls back to the ALL_SMI_INFLUXDB_TOKEN environment variable.
+    #[arg(long, value_name = "TOKEN")]
+    pub influxdb_token: Option<String>,
+    /// Maximum number of lines to buffer before writing a batch to InfluxDB.
+    #[arg(long, default_value_t = 5000)]
+    pub influxdb_batch_size: usize,
+    /// The interval in seconds at which buffered lines are flushed to InfluxDB.
+    #[arg(long, default_value_t = 10)]
+    pub influxdb_flush_interval: u64,
+    /// Directory for spooling batches while InfluxDB is unreachable; must be private to
+    /// this user. Defaults to influxdb-spool under $STATE_DIRECTORY, /var/lib/all-smi
+    /// as root, or ~/.local/state/all-smi.
+    #[arg(long, value_name = "DIR")]
+    pub influxdb_spool_dir: Option<String>,
+    /// Maximum size of the InfluxDB spool in bytes; the oldest batches are dropped first.
+    #[arg(long, default_value_t = 64 * 1024 * 1024)]
+    pub influxdb_spool_max_bytes: u64,
+    /// StatsD/DogStatsD address (host:port) to send gauges to over UDP.
+    #[arg(long, value_name = "HOST:PORT")]
+    pub statsd_addr: Option<String>,
+    /// Prefix prepended to every StatsD metric name.
+    #[arg(long, default_value = "all_smi")]
+    pub statsd_prefix: String,
+    /// Fraction of StatsD gauges sent on each update, between 0 and 1.
+    #[arg(long, default_value_t = 1.0)]
+    pub statsd_sample_rate: f64,
+    /// Maximum size in bytes of a single StatsD UDP datagram.
+    #[arg(long, default_value_t = 1432)]
+    pub statsd_max_packet_size: usize,
+    /// OpenTelemetry collector endpoint to push OTLP metrics to,
+    /// e.g. http://localhost:4318 (HTTP) or http://localhost:4317 (gRPC).
+    #[arg(long, value_name = "URL")]
+    pub otlp_endpoint: Option<String>,
+    /// Transport used for OTLP metrics push.
+    #[arg(long, value_enum, default_value_t = OtlpProtocol::Http)]
+    pub otlp_protocol: OtlpProtocol,
+    /// Extra header (key=value) sent with every OTLP export. Can be repeated.
+    #[arg(long = "otlp-header", value_name = "KEY=VALUE")]
+    pub otlp_headers: Vec<String>,
+    /// Prometheus remote_write endpoint to send samples to,
+    /// e.g. http://prometheus:9090/api/v1/write
+    #[arg(long, value_name = "URL")]
+    pub remote_write_url: Option<String>,
+    /// Bearer token for remote_write. Falls back to the ALL_SMI_REMOTE_WRITE_TOKEN environment variable.
+    #[arg(long, value_name = "TOKEN")]
+    pub remote_write_token: Option<String>,
+    /// Directory for the remote_write write-ahead queue; must be private to this user.
+    /// Defaults to remote-write-queue under $STATE_DIRECTORY, /var/lib/all-smi as root,
+    /// or ~/.local/state/all-smi.
+    #[arg(long, value_name = "DIR")]
+    pub remote_write_queue_dir: Option<String>,
+    /// Maximum size of the remote_write queue in bytes; the oldest requests are dropped first.
+    #[arg(long, default_value_t = 64 * 1024 * 1024)]
+    pub remote_write_queue_max_bytes: u64,
+}
+
+/// OTLP transport protocol
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OtlpProtocol {
+    /// OTLP/HTTP with protobuf payloads
+    Http,
+    /// OTLP/gRPC
+    Grpc,
+}
+
+/// Grouping of per-process metrics
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ProcessAggregation {
+    /// One series per process
+    #[default]
+    Pid,
+    /// Summed per process name
+    Name,
+    /// Summed per user
+    User,
+    /// Summed per container, from the process's cgroup
+    Container,
+    /// Summed per Slurm job, from the process's cgroup
+    Job,
+}
+
+#[derive(Parser)]
+pub struct ConfigArgs {
+    #[command(subcommand)]
+    pub command: ConfigCommand,
+}
+
+#[derive(Subcommand)]
+pub enum ConfigCommand {
+    /// Print the effective configuration: command-line defaults, environment and config
+    /// files merged.
+    Show,
 }
 
 #[derive(Parser, Clone)]
@@ -58,15 +214,37 @@ pub struct LocalArgs {
     pub interval: Option<u64>,
 }
 
-#[derive(Parser, Clone)]
+#[derive(Parser, Clone, Default)]
 pub struct ViewArgs {
     /// A list of host addresses to connect to for remote monitoring.
+    /// Use unix:///path/to/all-smi.sock for an API server on a Unix socket.
+    /// Slurm-style ranges expand to several hosts: gpu-node[001-128,200]:9090.
     #[arg(long, num_args = 1..)]
     pub hosts: Option<Vec<String>>,
-    /// A file containing a list of host addresses to connect to for remote monitoring.
+    /// A file containing a list of host addresses to connect to for remote monitoring,
+    /// one per line. Accepts the same ranges as --hosts.
     #[arg(long)]
     pub hostfile: Option<String>,
+    /// Port for hosts given without one.
+    #[arg(long)]
+    pub port: Option<u16>,
+    /// Find API servers by probing IPv4 networks, e.g. 10.0.0.0/24:9090 or
+    /// 10.0.0.0/24:9090-9095. The networks are rescanned periodically for new nodes.
+    #[arg(long, value_name = "CIDR:PORT", num_args = 1..)]
+    pub discover: Option<Vec<String>>,
     /// The interval in seconds at which to update the GPU information. If not specified, uses adaptive interval based on node count.
     #[arg(short, long)]
     pub interval: Option<u64>,
+    /// Connect to the hosts over HTTPS. Implied by the other --tls-* options.
+    #[arg(long)]
+    pub tls: bool,
+    /// PEM CA bundle for verifying the servers' certificates, in addition to the system roots.
+    #[arg(long, value_name = "PATH")]
+    pub tls_ca_cert: Option<String>,
+    /// PEM client certificate for servers that require mutual TLS.
+    #[arg(long, value_name = "PATH", requires = "tls_client_key")]
+    pub tls_client_cert: Option<String>,
+    /// PEM (PKCS#8) private key for --tls-client-cert.
+    #[arg(long, value_name = "PATH", requires = "tls_client_cert")]
+    pub tls_client_key: Option<String>,
 }
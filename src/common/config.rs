@@ -12,6 +12,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::sync::{Arc, LazyLock, PoisonError, RwLock};
+
+use serde::{Deserialize, Serialize};
+
 /// Application configuration constants
 #[allow(dead_code)] // Many constants used across modules but clippy may not detect cross-module usage
 pub struct AppConfig;
@@ -28,12 +32,18 @@ impl AppConfig {
     pub const BACKEND_AI_DEFAULT_PORT: u16 = 9090;
     pub const MAX_CONCURRENT_CONNECTIONS: usize = 128;
     pub const CONNECTION_TIMEOUT_SECS: u64 = 5;
+    pub const CONNECT_TIMEOUT_SECS: u64 = 2;
+    pub const BACKOFF_BASE_SECS: u64 = 5;
+    pub const BACKOFF_MAX_SECS: u64 = 300;
     pub const POOL_IDLE_TIMEOUT_SECS: u64 = 60;
     pub const POOL_MAX_IDLE_PER_HOST: usize = 200;
     pub const TCP_KEEPALIVE_SECS: u64 = 30;
     pub const HTTP2_KEEPALIVE_SECS: u64 = 30;
     pub const RETRY_ATTEMPTS: u32 = 3;
     pub const RETRY_BASE_DELAY_MS: u64 = 50;
+    pub const DISCOVERY_PROBE_TIMEOUT_MS: u64 = 1000;
+    pub const DISCOVERY_RESCAN_SECS: u64 = 60;
+    pub const DISCOVERY_MAX_PROBES: usize = 65_536;
 
     // Data Collection
     #[allow(dead_code)] // Future configuration option
@@ -102,37 +112,271 @@ impl EnvConfig {
     }
 }
 
+/// Settings read at runtime from the `[thresholds]`, `[colors]`, `[network]`
+/// and `[readers]` tables of the config file, with environment variables
+/// applied on top
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Settings {
+    pub thresholds: Thresholds,
+    pub colors: Colors,
+    pub network: NetworkSettings,
+    pub readers: ReaderSettings,
+}
+
+static SETTINGS: LazyLock<RwLock<Arc<Settings>>> =
+    LazyLock::new(|| RwLock::new(Arc::new(Settings::default().with_env())));
+
+impl Settings {
+    /// The settings in effect; defaults plus environment until `install`
+    pub fn current() -> Arc<Settings> {
+        SETTINGS
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Replace the settings in effect
+    pub fn install(settings: Settings) {
+        *SETTINGS.write().unwrap_or_else(PoisonError::into_inner) = Arc::new(settings);
+    }
+
+    /// Apply `ALL_SMI_AUTH_TOKEN`, `SUPPRESS_LOCALHOST_WARNING` and
+    /// `SKIP_GPU_DETECTION`/`NO_GPU`, which win over the file
+    pub fn with_env(mut self) -> Self {
+        if let Ok(token) = std::env::var("ALL_SMI_AUTH_TOKEN") {
+            self.network.auth_token = Some(token);
+        }
+        if std::env::var_os("SUPPRESS_LOCALHOST_WARNING").is_some() {
+            self.network.suppress_localhost_warning = true;
+        }
+        if std::env::var_os("SKIP_GPU_DETECTION").is_some() || std::env::var_os("NO_GPU").is_some()
+        {
+            self.readers.skip_gpu_detection = true;
+        }
+        self
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        let t = &self.thresholds;
+        if !(0.0 <= t.low
+            && t.low <= t.normal
+            && t.normal <= t.warning
+            && t.warning <= t.critical
+            && t.critical <= 1.0)
+        {
+            return Err(
+                "thresholds must satisfy 0 <= low <= normal <= warning <= critical <= 1"
+                    .to_string(),
+            );
+        }
+        if self.network.connection_timeout == 0 {
+            return Err("network.connection_timeout must be at least 1 second".to_string());
+        }
+        if self.network.connect_timeout == 0 {
+            return Err("network.connect_timeout must be at least 1 second".to_string());
+        }
+        if self.network.retry_attempts == 0 {
+            return Err("network.retry_attempts must be at least 1".to_string());
+        }
+        if let Some(name) = self
+            .readers
+            .disabled
+            .iter()
+            .find(|name| !GPU_READERS.contains(&name.as_str()))
+        {
+            return Err(format!(
+                "unknown reader '{name}' in readers.disabled (expected one of: {})",
+                GPU_READERS.join(", ")
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Fill ratios at which progress bars change color
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Thresholds {
+    pub critical: f64,
+    pub warning: f64,
+    pub normal: f64,
+    pub low: f64,
+}
+
+impl Default for Thresholds {
+    fn default() -> Self {
+        Self {
+            critical: AppConfig::CRITICAL_THRESHOLD,
+            warning: AppConfig::WARNING_THRESHOLD,
+            normal: AppConfig::NORMAL_THRESHOLD,
+            low: AppConfig::LOW_THRESHOLD,
+        }
+    }
+}
+
+/// Colors for each threshold band, by crossterm color name
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Colors {
+    pub critical: ThemeColor,
+    pub warning: ThemeColor,
+    pub normal: ThemeColor,
+    pub low: ThemeColor,
+    pub idle: ThemeColor,
+}
+
+impl Default for Colors {
+    fn default() -> Self {
+        use crossterm::style::Color;
+
+        Self {
+            critical: ThemeColor(Color::Red),
+            warning: ThemeColor(Color::Yellow),
+            normal: ThemeColor(Color::Green),
+            low: ThemeColor(Color::DarkGreen),
+            idle: ThemeColor(Color::DarkGrey),
+        }
+    }
+}
+
+/// A named terminal color such as `red` or `dark_grey`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct ThemeColor(pub crossterm::style::Color);
+
+const COLOR_NAMES: &[(&str, crossterm::style::Color)] = {
+    use crossterm::style::Color;
+
+    &[
+        ("black", Color::Black),
+        ("dark_grey", Color::DarkGrey),
+        ("red", Color::Red),
+        ("dark_red", Color::DarkRed),
+        ("green", Color::Green),
+        ("dark_green", Color::DarkGreen),
+        ("yellow", Color::Yellow),
+        ("dark_yellow", Color::DarkYellow),
+        ("blue", Color::Blue),
+        ("dark_blue", Color::DarkBlue),
+        ("magenta", Color::Magenta),
+        ("dark_magenta", Color::DarkMagenta),
+        ("cyan", Color::Cyan),
+        ("dark_cyan", Color::DarkCyan),
+        ("white", Color::White),
+        ("grey", Color::Grey),
+    ]
+};
+
+impl TryFrom<String> for ThemeColor {
+    type Error = String;
+
+    fn try_from(name: String) -> Result<Self, Self::Error> {
+        COLOR_NAMES
+            .iter()
+            .find(|(known, _)| known.eq_ignore_ascii_case(&name))
+            .map(|(_, color)| ThemeColor(*color))
+            .ok_or_else(|| format!("unknown color '{name}'"))
+    }
+}
+
+impl From<ThemeColor> for String {
+    fn from(color: ThemeColor) -> Self {
+        COLOR_NAMES
+            .iter()
+            .find(|(_, known)| *known == color.0)
+            .map_or("reset", |(name, _)| name)
+            .to_string()
+    }
+}
+
+/// Remote connection settings for view mode
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct NetworkSettings {
+    /// Seconds before a request to a remote node is abandoned
+    pub connection_timeout: u64,
+    /// Seconds allowed for establishing the connection to a remote node
+    pub connect_timeout: u64,
+    pub retry_attempts: u32,
+    /// Bearer token sent to remote nodes
+    pub auth_token: Option<String>,
+    pub suppress_localhost_warning: bool,
+}
+
+impl Default for NetworkSettings {
+    fn default() -> Self {
+        Self {
+            connection_timeout: AppConfig::CONNECTION_TIMEOUT_SECS,
+            connect_timeout: AppConfig::CONNECT_TIMEOUT_SECS,
+            retry_attempts: AppConfig::RETRY_ATTEMPTS,
+            auth_token: None,
+            suppress_localhost_warning: false,
+        }
+    }
+}
+
+/// Names accepted in `readers.disabled`
+pub const GPU_READERS: &[&str] = &[
+    "nvidia",
+    "nvidia_jetson",
+    "amd",
+    "furiosa",
+    "tenstorrent",
+    "rebellions",
+    "gaudi",
+    "google_tpu",
+    "apple_silicon",
+];
+
+/// Which GPU/NPU readers are created
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ReaderSettings {
+    /// Create no GPU/NPU readers at all
+    pub skip_gpu_detection: bool,
+    /// Readers not to create even when their hardware is present
+    pub disabled: Vec<String>,
+}
+
+impl ReaderSettings {
+    pub fn is_enabled(&self, reader: &str) -> bool {
+        !self.disabled.iter().any(|name| name == reader)
+    }
+}
+
 /// UI Theme configuration
 pub struct ThemeConfig;
 
 impl ThemeConfig {
     pub fn progress_bar_color(fill_ratio: f64) -> crossterm::style::Color {
-        use crossterm::style::Color;
-
-        if fill_ratio > AppConfig::CRITICAL_THRESHOLD {
-            Color::Red
-        } else if fill_ratio > AppConfig::WARNING_THRESHOLD {
-            Color::Yellow
-        } else if fill_ratio > AppConfig::NORMAL_THRESHOLD {
-            Color::Green
-        } else if fill_ratio > AppConfig::LOW_THRESHOLD {
-            Color::DarkGreen
+        let settings = Settings::current();
+        let (thresholds, colors) = (&settings.thresholds, &settings.colors);
+
+        if fill_ratio > thresholds.critical {
+            colors.critical.0
+        } else if fill_ratio > thresholds.warning {
+            colors.warning.0
+        } else if fill_ratio > thresholds.normal {
+            colors.normal.0
+        } else if fill_ratio > thresholds.low {
+            colors.low.0
         } else {
-            Color::DarkGrey
+            colors.idle.0
         }
     }
 
     pub fn utilization_color(utilization: f64) -> crossterm::style::Color {
-        use crossterm::style::Color;
+        let colors = &Settings::current().colors;
 
         if utilization > 80.0 {
-            Color::Red
+            colors.critical.0
         } else if utilization > 50.0 {
-            Color::Yellow
+            colors.warning.0
         } else if utilization > 20.0 {
-            Color::Green
+            colors.normal.0
         } else {
-            Color::DarkGrey
+            colors.idle.0
         }
     }
 }
@@ -0,0 +1,463 @@
+// Copyright 2025 Lablup Inc. and Jeongkyu Shin
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! TOML config file
+//!
+//! The system file (`/etc/all-smi/config.toml`) is read first and the user
+//! file (`$XDG_CONFIG_HOME/all-smi/config.toml`, `%APPDATA%` on Windows) is
+//! merged over it; `--config` or `ALL_SMI_CONFIG` names a single file to read
+//! instead. The `[api]`, `[local]` and `[view]` tables set the options of the
+//! subcommand by name, e.g. `process_top_n = 5`, and are filled in beneath
+//! the command line: an option given on the command line or through its
+//! environment variable wins over the file. The remaining tables are the
+//! runtime [`Settings`].
+
+use std::ffi::OsString;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+use clap::parser::ValueSource;
+use clap::{Arg, ArgAction, ArgMatches, Command, CommandFactory, FromArgMatches, Parser};
+use toml::{Table, Value};
+
+use crate::api::push::influxdb::INFLUXDB_TOKEN_ENV;
+use crate::api::push::remote_write::REMOTE_WRITE_TOKEN_ENV;
+use crate::cli::{Cli, LocalArgs};
+use crate::common::config::Settings;
+
+/// Tables holding subcommand options
+const MODES: &[&str] = &["api", "local", "view"];
+
+/// Options that fall back to an environment variable, which wins over the
+/// file. Their values are secrets and never shown.
+const OPTION_ENV_VARS: &[(&str, &str)] = &[
+    ("influxdb_token", INFLUXDB_TOKEN_ENV),
+    ("remote_write_token", REMOTE_WRITE_TOKEN_ENV),
+];
+
+const REDACTED: &str = "<redacted>";
+
+#[derive(Debug, Default)]
+pub struct ConfigFile {
+    /// Files read, lowest precedence first
+    pub paths: Vec<PathBuf>,
+    table: Table,
+}
+
+impl ConfigFile {
+    /// Read and check the file given by `--config` or `ALL_SMI_CONFIG`, or
+    /// else whichever of the system and user files exist
+    pub fn load(explicit: Option<&Path>) -> Result<Self, String> {
+        let explicit = explicit
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("ALL_SMI_CONFIG").map(PathBuf::from));
+
+        let mut config = Self::default();
+        match explicit {
+            Some(path) => config.merge_file(&path)?,
+            None => {
+                for path in default_paths().into_iter().filter(|path| path.is_file()) {
+                    config.merge_file(&path)?;
+                }
+            }
+        }
+
+        config.settings()?;
+        for mode in MODES {
+            config.file_matches(mode)?;
+        }
+        Ok(config)
+    }
+
+    fn merge_file(&mut self, path: &Path) -> Result<(), String> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("failed to read config file {}: {e}", path.display()))?;
+        let table: Table = content
+            .parse()
+            .map_err(|e| format!("invalid config file {}: {e}", path.display()))?;
+        merge_tables(&mut self.table, table);
+        self.paths.push(path.to_path_buf());
+        Ok(())
+    }
+
+    /// The runtime settings, environment variables applied
+    pub fn settings(&self) -> Result<Settings, String> {
+        let mut table = self.table.clone();
+        for mode in MODES {
+            table.remove(*mode);
+        }
+        let settings: Settings = Value::Table(table)
+            .try_into()
+            .map_err(|e| format!("invalid config file: {e}"))?;
+        let settings = settings.with_env();
+        settings
+            .validate()
+            .map_err(|e| format!("invalid config file: {e}"))?;
+        Ok(settings)
+    }
+
+    /// Options for `local` mode when no subcommand is given
+    pub fn local_args(&self) -> Result<LocalArgs, String> {
+        LocalArgs::from_arg_matches(&self.file_matches("local")?).map_err(clap_error)
+    }
+
+    /// Command-line arguments for the options the `mode` table sets and
+    /// neither `matches` nor the environment does
+    fn mode_args(
+        &self,
+        mode: &str,
+        command: &Command,
+        matches: &ArgMatches,
+    ) -> Result<Vec<OsString>, String> {
+        let Some(options) = self.table.get(mode) else {
+            return Ok(Vec::new());
+        };
+        let Some(options) = options.as_table() else {
+            return Err(format!("invalid config file: [{mode}] must be a table"));
+        };
+
+        let mut args = Vec::new();
+        for (key, value) in options {
+            let Some((arg, long)) = find_option(command, key) else {
+                return Err(format!(
+                    "invalid config file: unknown option '{key}' in [{mode}]"
+                ));
+            };
+            if is_overridden(matches, arg.get_id().as_str()) {
+                continue;
+            }
+            let flag = matches!(arg.get_action(), ArgAction::SetTrue);
+            let items = match value {
+                Value::Array(items) => items.iter().collect(),
+                value => vec![value],
+            };
+            for item in items {
+                let text = match item {
+                    Value::Boolean(set) if flag => {
+                        if *set {
+                            args.push(format!("--{long}").into());
+                        }
+                        continue;
+                    }
+                    Value::String(text) => text.clone(),
+                    Value::Integer(number) => number.to_string(),
+                    Value::Float(number) => number.to_string(),
+                    Value::Boolean(set) => set.to_string(),
+                    _ => {
+                        return Err(format!(
+                            "invalid config file: unsupported value for '{key}' in [{mode}]"
+                        ))
+                    }
+                };
+                args.push(format!("--{long}={text}").into());
+            }
+        }
+        Ok(args)
+    }
+
+    /// The options of `mode` as set by the file and environment alone
+    fn file_matches(&self, mode: &str) -> Result<ArgMatches, String> {
+        let command = mode_command(mode);
+        let defaults = command
+            .clone()
+            .try_get_matches_from([mode])
+            .map_err(clap_error)?;
+        let args = self.mode_args(mode, &command, &defaults)?;
+        command
+            .try_get_matches_from(std::iter::once(OsString::from(mode)).chain(args))
+            .map_err(clap_error)
+    }
+
+    /// The effective configuration as TOML, with each option's source noted
+    /// and secrets redacted
+    pub fn show(&self) -> Result<String, String> {
+        let mut out = String::new();
+        if self.paths.is_empty() {
+            out.push_str("# No config file found\n");
+        }
+        for path in &self.paths {
+            let _ = writeln!(out, "# Read {}", path.display());
+        }
+
+        for mode in MODES {
+            let command = mode_command(mode);
+            let matches = self.file_matches(mode)?;
+            let _ = writeln!(out, "\n[{mode}]");
+            for arg in command
+                .get_arguments()
+                .filter(|arg| arg.get_long().is_some())
+            {
+                let id = arg.get_id().as_str();
+                let _ = writeln!(out, "{}", show_option(arg, &matches, id));
+            }
+        }
+
+        let mut settings = self.settings()?;
+        if settings.network.auth_token.is_some() {
+            settings.network.auth_token = Some(REDACTED.to_string());
+        }
+        let settings = toml::to_string(&settings).map_err(|e| e.to_string())?;
+        let _ = write!(out, "\n{settings}");
+        Ok(out)
+    }
+}
+
+/// Parse the command line, filling in options from the config file
+pub fn parse_cli() -> Result<(Cli, ConfigFile), String> {
+    parse_cli_from(std::env::args_os().collect())
+}
+
+fn parse_cli_from(mut argv: Vec<OsString>) -> Result<(Cli, ConfigFile), String> {
+    let matches = Cli::command()
+        .try_get_matches_from(&argv)
+        .unwrap_or_else(|e| e.exit());
+    let config = ConfigFile::load(matches.get_one::<String>("config").map(Path::new))?;
+    if let Some((mode, sub_matches)) = matches.subcommand() {
+        if MODES.contains(&mode) {
+            argv.extend(config.mode_args(mode, &mode_command(mode), sub_matches)?);
+        }
+    }
+    let cli = Cli::try_parse_from(&argv).map_err(clap_error)?;
+    Ok((cli, config))
+}
+
+fn default_paths() -> Vec<PathBuf> {
+    let mut paths = Vec::new();
+    #[cfg(unix)]
+    paths.push(PathBuf::from("/etc/all-smi/config.toml"));
+    #[cfg(windows)]
+    if let Some(dir) = std::env::var_os("PROGRAMDATA") {
+        paths.push(PathBuf::from(dir).join("all-smi").join("config.toml"));
+    }
+
+    let user_dir = if cfg!(windows) {
+        std::env::var_os("APPDATA").map(PathBuf::from)
+    } else {
+        std::env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+    };
+    if let Some(dir) = user_dir {
+        paths.push(dir.join("all-smi").join("config.toml"));
+    }
+    paths
+}
+
+/// Merge `overlay` into `base`, recursing into tables present in both
+fn merge_tables(base: &mut Table, overlay: Table) {
+    for (key, value) in overlay {
+        match value {
+            Value::Table(overlay) => match base.get_mut(&key) {
+                Some(Value::Table(existing)) => merge_tables(existing, overlay),
+                _ => {
+                    base.insert(key, Value::Table(overlay));
+                }
+            },
+            value => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+fn mode_command(mode: &str) -> Command {
+    Cli::command()
+        .find_subcommand(mode)
+        .cloned()
+        .unwrap_or_else(|| panic!("no '{mode}' subcommand"))
+}
+
+/// An option by id or long name, with `_` standing in for `-`
+fn find_option<'a>(command: &'a Command, key: &str) -> Option<(&'a Arg, &'a str)> {
+    let key = key.replace('-', "_");
+    command.get_arguments().find_map(|arg| {
+        let long = arg.get_long()?;
+        (arg.get_id() == key.as_str() || long.replace('-', "_") == key).then_some((arg, long))
+    })
+}
+
+fn env_var(id: &str) -> Option<&'static str> {
+    OPTION_ENV_VARS
+        .iter()
+        .find(|(option, _)| *option == id)
+        .map(|(_, var)| *var)
+}
+
+fn is_overridden(matches: &ArgMatches, id: &str) -> bool {
+    matches.value_source(id) == Some(ValueSource::CommandLine)
+        || env_var(id).is_some_and(|var| std::env::var_os(var).is_some())
+}
+
+fn show_option(arg: &Arg, matches: &ArgMatches, id: &str) -> String {
+    let from_env = env_var(id).is_some_and(|var| std::env::var_os(var).is_some());
+    let source = match matches.value_source(id) {
+        _ if from_env => "env",
+        Some(ValueSource::CommandLine) => "file",
+        Some(_) => "default",
+        None => return format!("# {id} is not set"),
+    };
+    if env_var(id).is_some() {
+        return format!("{id} = \"{REDACTED}\"  # {source}");
+    }
+
+    let values: Vec<Value> = matches
+        .get_raw(id)
+        .into_iter()
+        .flatten()
+        .map(|raw| show_value(arg, &raw.to_string_lossy()))
+        .collect();
+    let value = if matches!(arg.get_action(), ArgAction::Append) {
+        Value::Array(values)
+    } else {
+        values.into_iter().next().unwrap_or(Value::Boolean(false))
+    };
+    format!("{id} = {value}  # {source}")
+}
+
+fn show_value(arg: &Arg, raw: &str) -> Value {
+    if matches!(arg.get_action(), ArgAction::SetTrue) {
+        return Value::Boolean(raw == "true");
+    }
+    if let Ok(number) = raw.parse() {
+        return Value::Integer(number);
+    }
+    match raw.parse() {
+        Ok(number) if raw.contains('.') => Value::Float(number),
+        _ => Value::String(raw.to_string()),
+    }
+}
+
+/// The first line of a clap error, without its `error: ` prefix
+fn clap_error(e: clap::Error) -> String {
+    let message = e.to_string();
+    let line = message.lines().next().unwrap_or_default();
+    format!(
+        "invalid config file: {}",
+        line.strip_prefix("error: ").unwrap_or(line)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::Commands;
+
+    fn write_config(dir: &tempfile::TempDir, content: &str) -> PathBuf {
+        let path = dir.path().join("config.toml");
+        std::fs::write(&path, content).unwrap();
+        path
+    }
+
+    fn argv(config: &Path, args: &[&str]) -> Vec<OsString> {
+        let mut argv: Vec<OsString> = vec!["all-smi".into(), "--config".into(), config.into()];
+        argv.extend(args.iter().map(OsString::from));
+        argv
+    }
+
+    #[test]
+    fn test_command_line_wins_over_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_config(
+            &dir,
+            "[api]\nport = 9100\ninterval = 7\nprocesses = true\nlabel = [\"rack=r1\"]\n\
+             process-labels = [\"user\", \"name\"]\n",
+        );
+
+        let (cli, _) = parse_cli_from(argv(&path, &["api", "--port", "9200"])).unwrap();
+        let Some(Commands::Api(args)) = cli.command else {
+            panic!("expected api subcommand");
+        };
+        assert_eq!(args.port, 9200);
+        assert_eq!(args.interval, 7);
+        assert!(args.processes);
+        assert_eq!(args.labels, ["rack=r1"]);
+        assert_eq!(
+            args.process_labels,
+            Some(vec!["user".to_string(), "name".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_invalid_files_are_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        for (content, expected) in [
+            ("[api]\nprot = 1\n", "unknown option 'prot' in [api]"),
+            ("[api]\nport = \"high\"\n", "invalid value 'high'"),
+            (
+                "[colors]\ncritical = \"crimson\"\n",
+                "unknown color 'crimson'",
+            ),
+            ("[thresholds]\nwarning = 0.9\n", "thresholds must satisfy"),
+            (
+                "[readers]\ndisabled = [\"voodoo\"]\n",
+                "unknown reader 'voodoo'",
+            ),
+            ("[ui]\n", "unknown field `ui`"),
+        ] {
+            let path = write_config(&dir, content);
+            let err = ConfigFile::load(Some(&path)).unwrap_err();
+            assert!(err.contains(expected), "{content}: {err}");
+        }
+    }
+
+    #[test]
+    fn test_user_file_merges_over_system_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let system = dir.path().join("system.toml");
+        let user = dir.path().join("user.toml");
+        std::fs::write(
+            &system,
+            "[view]\ninterval = 5\ntls = true\n[thresholds]\nlow = 0.1\n",
+        )
+        .unwrap();
+        std::fs::write(&user, "[view]\ninterval = 9\n[colors]\nidle = \"grey\"\n").unwrap();
+
+        let mut config = ConfigFile::default();
+        config.merge_file(&system).unwrap();
+        config.merge_file(&user).unwrap();
+
+        let matches = config.file_matches("view").unwrap();
+        assert_eq!(matches.get_one::<u64>("interval"), Some(&9));
+        assert_eq!(matches.get_one::<bool>("tls"), Some(&true));
+        let settings = config.settings().unwrap();
+        assert_eq!(settings.thresholds.low, 0.1);
+        assert_eq!(settings.colors.idle.0, crossterm::style::Color::Grey);
+    }
+
+    #[test]
+    fn test_show_notes_sources_and_redacts_secrets() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_config(
+            &dir,
+            "[api]\ninterval = 7\nremote_write_token = \"s3cret\"\n\
+             [view]\nhosts = [\"node1:9090\", \"node2:9090\"]\n\
+             [network]\nauth_token = \"t0ken\"\n",
+        );
+        let shown = ConfigFile::load(Some(&path)).unwrap().show().unwrap();
+
+        assert!(shown.contains("interval = 7  # file"), "{shown}");
+        assert!(shown.contains("port = 9090  # default"), "{shown}");
+        assert!(shown.contains("hosts = [\"node1:9090\", \"node2:9090\"]  # file"));
+        assert!(shown.contains("# hostfile is not set"));
+        assert!(
+            !shown.contains("s3cret") && !shown.contains("t0ken"),
+            "{shown}"
+        );
+        assert!(shown.contains("[thresholds]"));
+
+        // The output reads back as the same configuration
+        let reread = write_config(&dir, &shown);
+        assert!(ConfigFile::load(Some(&reread)).is_ok());
+    }
+}
@@ -13,5 +13,6 @@
 // limitations under the License.
 
 pub mod config;
+pub mod config_file;
 pub mod error_handling;
 pub mod progress_bar;
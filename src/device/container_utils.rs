@@ -381,6 +381,50 @@ pub fn format_process_name_with_container_info(process_name: String, pid: u32) -
     }
 }
 
+/// Container and batch job a process belongs to, from its cgroup path
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ProcessWorkload {
+    /// Short (12 character) container ID
+    pub container: Option<String>,
+    /// Slurm job ID
+    pub job: Option<String>,
+}
+
+/// Look up the container and Slurm job of a process. Both are `None` when
+/// `/proc/<pid>/cgroup` cannot be read.
+pub fn get_process_workload(pid: u32) -> ProcessWorkload {
+    fs::read_to_string(format!("/proc/{pid}/cgroup"))
+        .map(|content| parse_cgroup_workload(&content))
+        .unwrap_or_default()
+}
+
+/// Parse the contents of a `/proc/<pid>/cgroup` file. Container IDs are the
+/// 64-digit hex components used by Docker, containerd, CRI-O and Podman
+/// (`/docker/<id>`, `cri-containerd-<id>.scope`, ...); Slurm jobs appear as a
+/// `job_<id>` component.
+pub fn parse_cgroup_workload(content: &str) -> ProcessWorkload {
+    let mut workload = ProcessWorkload::default();
+    for line in content.lines() {
+        // hierarchy-ID:controller-list:cgroup-path
+        let Some(path) = line.splitn(3, ':').nth(2) else {
+            continue;
+        };
+        for component in path.split('/') {
+            let name = component.strip_suffix(".scope").unwrap_or(component);
+            let id = name.rsplit('-').next().unwrap_or(name);
+            if id.len() == 64 && id.bytes().all(|b| b.is_ascii_hexdigit()) {
+                workload.container = Some(id[..12].to_string());
+            }
+            if let Some(job) = name.strip_prefix("job_") {
+                if !job.is_empty() && job.bytes().all(|b| b.is_ascii_digit()) {
+                    workload.job = Some(job.to_string());
+                }
+            }
+        }
+    }
+    workload
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -400,4 +444,30 @@ mod tests {
             assert!(host_pid > 0);
         }
     }
+
+    #[test]
+    fn test_parse_cgroup_workload() {
+        let id = "4f3c2b1a0e9d8c7b6a5f4e3d2c1b0a9f8e7d6c5b4a3f2e1d0c9b8a7f6e5d4c3b";
+
+        let docker = format!("12:memory:/docker/{id}\n0::/docker/{id}\n");
+        assert_eq!(
+            parse_cgroup_workload(&docker).container.as_deref(),
+            Some("4f3c2b1a0e9d")
+        );
+
+        let kubernetes = format!(
+            "0::/kubepods.slice/kubepods-burstable.slice/kubepods-burstable-pod1234.slice/cri-containerd-{id}.scope\n"
+        );
+        let workload = parse_cgroup_workload(&kubernetes);
+        assert_eq!(workload.container.as_deref(), Some("4f3c2b1a0e9d"));
+        assert_eq!(workload.job, None);
+
+        let slurm_v1 = "7:devices:/slurm/uid_1000/job_4242/step_0\n";
+        assert_eq!(parse_cgroup_workload(slurm_v1).job.as_deref(), Some("4242"));
+        let slurm_v2 = "0::/system.slice/slurmstepd.scope/job_77/step_batch/user/task_0\n";
+        assert_eq!(parse_cgroup_workload(slurm_v2).job.as_deref(), Some("77"));
+
+        let host = "0::/user.slice/user-1000.slice/session-3.scope\n";
+        assert_eq!(parse_cgroup_workload(host), ProcessWorkload::default());
+    }
 }
@@ -30,6 +30,7 @@ pub fn get_all_processes(system: &System, gpu_pids: &HashSet<u32>) -> Vec<Proces
 
         // Get process information
         let process_info = ProcessInfo {
+            host_id: String::new(),
             device_id: 0, // Will be set by GPU-specific code if uses_gpu
             device_uuid: if uses_gpu {
                 "GPU".to_string()
@@ -101,6 +102,7 @@ pub fn update_process_cache(
             // New process - create full ProcessInfo entry
             let (priority, nice_value) = get_process_priority_nice(pid_u32);
             let process_info = ProcessInfo {
+                host_id: String::new(),
                 device_id: 0,
                 device_uuid: if uses_gpu {
                     "GPU".to_string()
@@ -12,12 +12,14 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::common::config::Settings;
 use crate::device::{
     platform_detection::{
         get_os_type, has_furiosa, has_gaudi, has_nvidia, has_rebellions, is_jetson,
     },
+    readers::chassis::GenericChassisReader,
     readers::{furiosa, gaudi, nvidia, nvidia_jetson, rebellions},
-    traits::{CpuReader, GpuReader, MemoryReader},
+    traits::{ChassisReader, CpuReader, GpuReader, MemoryReader},
 };
 
 #[cfg(target_os = "linux")]
@@ -32,6 +34,9 @@ use crate::device::{cpu_macos, memory_macos, platform_detection::is_apple_silico
 #[cfg(target_os = "macos")]
 use crate::device::readers::apple_silicon_native;
 
+#[cfg(target_os = "macos")]
+use crate::device::readers::chassis::AppleSiliconNativeChassisReader;
+
 #[cfg(target_os = "linux")]
 use crate::device::{cpu_linux, memory_linux};
 
@@ -48,64 +53,87 @@ use crate::device::platform_detection::has_amd;
 use crate::device::readers::amd;
 
 pub fn get_gpu_readers() -> Vec<Box<dyn GpuReader>> {
-    let mut readers: Vec<Box<dyn GpuReader>> = Vec::new();
+    get_new_gpu_readers(&[])
+        .into_iter()
+        .map(|(_, reader)| reader)
+        .collect()
+}
+
+/// Create the enabled GPU/NPU readers except those named in `running`, each
+/// paired with its name in `readers.disabled`
+pub fn get_new_gpu_readers(running: &[&str]) -> Vec<(&'static str, Box<dyn GpuReader>)> {
+    let mut readers: Vec<(&'static str, Box<dyn GpuReader>)> = Vec::new();
 
     // Check if GPU detection should be skipped (useful for containers)
-    if std::env::var("SKIP_GPU_DETECTION").is_ok() || std::env::var("NO_GPU").is_ok() {
-        eprintln!("GPU detection skipped (SKIP_GPU_DETECTION or NO_GPU environment variable set)");
+    let settings = Settings::current();
+    if settings.readers.skip_gpu_detection {
+        eprintln!(
+            "GPU detection skipped (SKIP_GPU_DETECTION, NO_GPU or readers.skip_gpu_detection set)"
+        );
         return readers;
     }
+    let enabled = |reader: &str| settings.readers.is_enabled(reader) && !running.contains(&reader);
 
     let os_type = get_os_type();
 
     match os_type {
         "linux" => {
             // Only create NVIDIA reader if we actually have NVIDIA GPUs
-            if is_jetson() && has_nvidia() {
-                readers.push(Box::new(nvidia_jetson::NvidiaJetsonGpuReader::new()));
-            } else if has_nvidia() && !is_jetson() {
-                readers.push(Box::new(nvidia::NvidiaGpuReader::new()));
+            if enabled("nvidia_jetson") && is_jetson() && has_nvidia() {
+                readers.push((
+                    "nvidia_jetson",
+                    Box::new(nvidia_jetson::NvidiaJetsonGpuReader::new()),
+                ));
+            } else if enabled("nvidia") && has_nvidia() && !is_jetson() {
+                readers.push(("nvidia", Box::new(nvidia::NvidiaGpuReader::new())));
             }
 
             // Check for Furiosa NPU support
-            if has_furiosa() {
-                readers.push(Box::new(furiosa::FuriosaNpuReader::new()));
+            if enabled("furiosa") && has_furiosa() {
+                readers.push(("furiosa", Box::new(furiosa::FuriosaNpuReader::new())));
             }
 
             // Check for Tenstorrent NPU support
             #[cfg(target_os = "linux")]
-            if has_tenstorrent() {
-                readers.push(Box::new(tenstorrent::TenstorrentReader::new()));
+            if enabled("tenstorrent") && has_tenstorrent() {
+                readers.push((
+                    "tenstorrent",
+                    Box::new(tenstorrent::TenstorrentReader::new()),
+                ));
             }
 
             // Check for Rebellions NPU support
-            if has_rebellions() {
-                readers.push(Box::new(rebellions::RebellionsNpuReader::new()));
+            if enabled("rebellions") && has_rebellions() {
+                readers.push((
+                    "rebellions",
+                    Box::new(rebellions::RebellionsNpuReader::new()),
+                ));
             }
 
             // Check for Intel Gaudi NPU support
-            if has_gaudi() {
-                readers.push(Box::new(gaudi::GaudiNpuReader::new()));
+            if enabled("gaudi") && has_gaudi() {
+                readers.push(("gaudi", Box::new(gaudi::GaudiNpuReader::new())));
             }
 
             // Check for Google TPU support
             #[cfg(target_os = "linux")]
-            if has_google_tpu() {
-                readers.push(Box::new(google_tpu::GoogleTpuReader::new()));
+            if enabled("google_tpu") && has_google_tpu() {
+                readers.push(("google_tpu", Box::new(google_tpu::GoogleTpuReader::new())));
             }
 
             // Check for AMD GPU support (glibc only, not musl))
             #[cfg(all(target_os = "linux", not(target_env = "musl")))]
-            if has_amd() {
-                readers.push(Box::new(amd::AmdGpuReader::new()));
+            if enabled("amd") && has_amd() {
+                readers.push(("amd", Box::new(amd::AmdGpuReader::new())));
             }
         }
         "macos" => {
             #[cfg(target_os = "macos")]
-            if is_apple_silicon() {
+            if enabled("apple_silicon") && is_apple_silicon() {
                 // Use native APIs (no sudo required)
-                readers.push(Box::new(
-                    apple_silicon_native::AppleSiliconNativeGpuReader::new(),
+                readers.push((
+                    "apple_silicon",
+                    Box::new(apple_silicon_native::AppleSiliconNativeGpuReader::new()),
                 ));
             }
         }
@@ -113,13 +141,13 @@ pub fn get_gpu_readers() -> Vec<Box<dyn GpuReader>> {
             #[cfg(target_os = "windows")]
             {
                 // Check for NVIDIA GPU on Windows
-                if has_nvidia() {
-                    readers.push(Box::new(nvidia::NvidiaGpuReader::new()));
+                if enabled("nvidia") && has_nvidia() {
+                    readers.push(("nvidia", Box::new(nvidia::NvidiaGpuReader::new())));
                 }
 
                 // Check for AMD GPU on Windows (including APU)
-                if amd_windows::has_amd_gpu_windows() {
-                    readers.push(Box::new(amd_windows::AmdWindowsGpuReader::new()));
+                if enabled("amd") && amd_windows::has_amd_gpu_windows() {
+                    readers.push(("amd", Box::new(amd_windows::AmdWindowsGpuReader::new())));
                 }
             }
         }
@@ -176,3 +204,21 @@ pub fn get_memory_readers() -> Vec<Box<dyn MemoryReader>> {
 
     readers
 }
+
+pub fn get_chassis_readers() -> Vec<Box<dyn ChassisReader>> {
+    let mut readers: Vec<Box<dyn ChassisReader>> = Vec::new();
+    let os_type = get_os_type();
+
+    match os_type {
+        "macos" => {
+            // Use native APIs (no sudo required)
+            #[cfg(target_os = "macos")]
+            readers.push(Box::new(AppleSiliconNativeChassisReader::new()));
+        }
+        // Aggregates the GPU power fed in by the collection loop
+        "linux" | "windows" => readers.push(Box::new(GenericChassisReader::new())),
+        _ => eprintln!("Chassis monitoring not supported for OS type: {os_type}"),
+    }
+
+    readers
+}
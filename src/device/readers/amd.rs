@@ -605,6 +605,7 @@ impl GpuReader for AmdGpuReader {
             let sys_proc = process_map.get(&gpu_proc.pid);
 
             let process_info = ProcessInfo {
+                host_id: String::new(),
                 device_id: gpu_proc.device_id,
                 device_uuid: gpu_proc.device_uuid,
                 pid: gpu_proc.pid,
@@ -641,14 +642,16 @@ mod tests {
     #[test]
     fn test_max_version_component_validation() {
         // Test that MAX_VERSION_COMPONENT is reasonable for Linux kernel versions
-        assert!(
-            MAX_VERSION_COMPONENT >= 99,
-            "Should support two-digit version components"
-        );
-        assert!(
-            MAX_VERSION_COMPONENT <= 9999,
-            "Should not be excessively large"
-        );
+        const {
+            assert!(
+                MAX_VERSION_COMPONENT >= 99,
+                "Should support two-digit version components"
+            );
+            assert!(
+                MAX_VERSION_COMPONENT <= 9999,
+                "Should not be excessively large"
+            );
+        }
 
         // Common kernel version components should be valid
         let common_versions = vec![
@@ -687,9 +690,9 @@ mod tests {
         ];
 
         for (major, minor, patch) in invalid_versions {
-            let major_valid = major >= 0 && major <= MAX_VERSION_COMPONENT;
-            let minor_valid = minor >= 0 && minor <= MAX_VERSION_COMPONENT;
-            let patch_valid = patch >= 0 && patch <= MAX_VERSION_COMPONENT;
+            let major_valid = (0..=MAX_VERSION_COMPONENT).contains(&major);
+            let minor_valid = (0..=MAX_VERSION_COMPONENT).contains(&minor);
+            let patch_valid = (0..=MAX_VERSION_COMPONENT).contains(&patch);
 
             assert!(
                 !(major_valid && minor_valid && patch_valid),
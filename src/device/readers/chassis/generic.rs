@@ -46,14 +46,6 @@ impl GenericChassisReader {
         }
     }
 
-    /// Update the cached GPU power value
-    /// This should be called from the data collection loop with aggregated GPU power
-    pub fn update_gpu_power(&self, total_gpu_power_watts: f64) {
-        if let Ok(mut power) = self.cached_gpu_power.write() {
-            *power = Some(total_gpu_power_watts);
-        }
-    }
-
     /// Get the cached GPU power value
     fn get_cached_gpu_power(&self) -> Option<f64> {
         self.cached_gpu_power.read().ok().and_then(|p| *p)
@@ -61,6 +53,14 @@ impl GenericChassisReader {
 }
 
 impl ChassisReader for GenericChassisReader {
+    /// Update the cached GPU power value
+    /// This should be called from the data collection loop with aggregated GPU power
+    fn update_gpu_power(&self, total_gpu_power_watts: f64) {
+        if let Ok(mut power) = self.cached_gpu_power.write() {
+            *power = Some(total_gpu_power_watts);
+        }
+    }
+
     fn get_chassis_info(&self) -> Option<ChassisInfo> {
         // Build platform detail
         let detail = {
@@ -32,31 +32,85 @@ pub use apple_silicon_native::AppleSiliconNativeChassisReader;
 #[allow(unused_imports)]
 pub use generic::GenericChassisReader;
 
-use crate::device::ChassisReader;
-
-/// Create a platform-appropriate chassis reader
-pub fn create_chassis_reader() -> Box<dyn ChassisReader> {
-    // On macOS, use native APIs (no sudo required)
-    #[cfg(target_os = "macos")]
-    {
-        Box::new(AppleSiliconNativeChassisReader::new())
-    }
+use crate::device::{ChassisInfo, ChassisReader, GpuInfo};
+
+/// Read chassis info after feeding the readers the GPU power of the same
+/// collection cycle
+pub fn collect_chassis_info(
+    readers: &[Box<dyn ChassisReader>],
+    gpu_info: &[GpuInfo],
+) -> Vec<ChassisInfo> {
+    feed_gpu_power(readers.iter().map(|reader| reader.as_ref()), gpu_info);
+    readers
+        .iter()
+        .filter_map(|reader| reader.get_chassis_info())
+        .collect()
+}
 
-    #[cfg(not(target_os = "macos"))]
-    {
-        // On other platforms, use generic reader that aggregates GPU power
-        Box::new(GenericChassisReader::new())
+/// Pass the total GPU power of the current cycle to the chassis readers
+pub fn feed_gpu_power<'a>(
+    readers: impl IntoIterator<Item = &'a dyn ChassisReader>,
+    gpu_info: &[GpuInfo],
+) {
+    // Without any GPUs there is nothing to aggregate; leave power unknown
+    // rather than reporting 0 W
+    if gpu_info.is_empty() {
+        return;
+    }
+    let total_gpu_power: f64 = gpu_info.iter().map(|gpu| gpu.power_consumption).sum();
+    for reader in readers {
+        reader.update_gpu_power(total_gpu_power);
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::device::get_chassis_readers;
+
+    #[test]
+    fn test_get_chassis_readers() {
+        let readers = get_chassis_readers();
+        // Just verify we can create readers without panicking
+        let _ = collect_chassis_info(&readers, &[]);
+    }
 
     #[test]
-    fn test_create_chassis_reader() {
-        let reader = create_chassis_reader();
-        // Just verify we can create a reader without panicking
-        let _ = reader.get_chassis_info();
+    fn test_collect_chassis_info_aggregates_gpu_power() {
+        let readers: Vec<Box<dyn ChassisReader>> = vec![Box::new(GenericChassisReader::new())];
+        let gpus: Vec<GpuInfo> = [150.0, 200.5]
+            .into_iter()
+            .map(|power| GpuInfo {
+                power_consumption: power,
+                ..test_gpu()
+            })
+            .collect();
+
+        let info = collect_chassis_info(&readers, &gpus);
+        assert_eq!(info.len(), 1);
+        assert_eq!(info[0].total_power_watts, Some(350.5));
+    }
+
+    fn test_gpu() -> GpuInfo {
+        GpuInfo {
+            uuid: String::new(),
+            time: String::new(),
+            name: String::new(),
+            device_type: "GPU".to_string(),
+            host_id: String::new(),
+            hostname: String::new(),
+            instance: String::new(),
+            utilization: 0.0,
+            ane_utilization: 0.0,
+            dla_utilization: None,
+            tensorcore_utilization: None,
+            temperature: 0,
+            used_memory: 0,
+            total_memory: 0,
+            frequency: 0,
+            power_consumption: 0.0,
+            gpu_core_count: None,
+            detail: Default::default(),
+        }
     }
 }
@@ -676,6 +676,7 @@ fn create_process_info_from_ps(proc: &FuriosaPsOutputJson) -> ProcessInfo {
     });
 
     ProcessInfo {
+        host_id: String::new(),
         device_id,
         device_uuid: proc.npu.clone(),
         pid: proc.pid,
@@ -307,6 +307,7 @@ fn create_base_process_info(
     };
 
     ProcessInfo {
+        host_id: String::new(),
         device_id,
         device_uuid,
         pid,
@@ -525,6 +526,7 @@ fn get_gpu_processes_nvidia_smi() -> (Vec<ProcessInfo>, HashSet<u32>) {
             if let Ok(pid) = parts[1].parse::<u32>() {
                 gpu_pids.insert(pid);
                 gpu_processes.push(ProcessInfo {
+                    host_id: String::new(),
                     device_id: 0, // We don't have device index from this query
                     device_uuid: parts[0].to_string(),
                     pid,
@@ -218,6 +218,7 @@ fn get_gpu_processes() -> (Vec<ProcessInfo>, HashSet<u32>) {
                             gpu_pids.insert(pid);
 
                             gpu_processes.push(ProcessInfo {
+                                host_id: String::new(),
                                 device_id: 0,
                                 device_uuid: "JetsonGPU".to_string(),
                                 pid,
@@ -268,6 +269,7 @@ fn get_gpu_processes() -> (Vec<ProcessInfo>, HashSet<u32>) {
                     gpu_pids.insert(pid_u32);
 
                     gpu_processes.push(ProcessInfo {
+                        host_id: String::new(),
                         device_id: 0,
                         device_uuid: "JetsonGPU".to_string(),
                         pid: pid_u32,
@@ -401,6 +401,7 @@ fn create_process_info_from_context(ctx: RblnContext) -> ProcessInfo {
     });
 
     ProcessInfo {
+        host_id: String::new(),
         device_id,
         device_uuid: ctx.npu,
         pid: ctx.pid,
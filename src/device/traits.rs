@@ -17,14 +17,29 @@ use crate::device::{ChassisInfo, CpuInfo, GpuInfo, MemoryInfo, ProcessInfo};
 pub trait GpuReader: Send + Sync {
     fn get_gpu_info(&self) -> Vec<GpuInfo>;
     fn get_process_info(&self) -> Vec<ProcessInfo>;
+
+    /// Reader name used in status reports
+    fn name(&self) -> &'static str {
+        short_type_name(std::any::type_name::<Self>())
+    }
 }
 
 pub trait CpuReader: Send + Sync {
     fn get_cpu_info(&self) -> Vec<CpuInfo>;
+
+    /// Reader name used in status reports
+    fn name(&self) -> &'static str {
+        short_type_name(std::any::type_name::<Self>())
+    }
 }
 
 pub trait MemoryReader: Send + Sync {
     fn get_memory_info(&self) -> Vec<MemoryInfo>;
+
+    /// Reader name used in status reports
+    fn name(&self) -> &'static str {
+        short_type_name(std::any::type_name::<Self>())
+    }
 }
 
 /// Chassis/Node-level reader for system-wide metrics
@@ -32,4 +47,18 @@ pub trait MemoryReader: Send + Sync {
 pub trait ChassisReader: Send + Sync {
     /// Get chassis information for the current node
     fn get_chassis_info(&self) -> Option<ChassisInfo>;
+
+    /// Feed the total GPU power measured in the current collection cycle.
+    /// Readers that measure system power directly ignore it.
+    fn update_gpu_power(&self, _total_gpu_power_watts: f64) {}
+
+    /// Reader name used in status reports
+    fn name(&self) -> &'static str {
+        short_type_name(std::any::type_name::<Self>())
+    }
+}
+
+/// Strip the module path from a type name, e.g. `NvidiaGpuReader`
+fn short_type_name(full: &'static str) -> &'static str {
+    full.rsplit("::").next().unwrap_or(full)
 }
@@ -55,6 +55,8 @@ pub struct GpuInfo {
 
 #[derive(Clone, Serialize, Deserialize)]
 pub struct ProcessInfo {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub host_id: String, // Host identifier in remote mode, empty for local processes
     pub device_id: usize,     // GPU index (internal)
     pub device_uuid: String,  // GPU UUID
     pub pid: u32,             // Process ID
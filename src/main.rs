@@ -27,8 +27,9 @@ mod utils;
 mod view;
 
 use api::run_api_mode;
-use clap::Parser;
-use cli::{Cli, Commands, LocalArgs};
+use cli::{Commands, ConfigCommand};
+use common::config::Settings;
+use common::config_file::parse_cli;
 use tokio::signal;
 use utils::{ensure_sudo_permissions_for_api, RuntimeEnvironment};
 
@@ -63,7 +64,14 @@ async fn main() {
     #[cfg(target_os = "macos")]
     setup_panic_handler();
 
-    let cli = Cli::parse();
+    let (cli, config) = match parse_cli() {
+        Ok(parsed) => parsed,
+        Err(e) => exit_with_error(&e),
+    };
+    match config.settings() {
+        Ok(settings) => Settings::install(settings),
+        Err(e) => exit_with_error(&e),
+    }
 
     // Set up signal handler for clean shutdown
     tokio::spawn(async {
@@ -122,7 +130,7 @@ async fn main() {
 
             // Initialize hlsmi manager for Intel Gaudi on Linux
             #[cfg(target_os = "linux")]
-            if has_gaudi() {
+            if Settings::current().readers.is_enabled("gaudi") && has_gaudi() {
                 if let Err(e) = initialize_hlsmi_manager(args.interval) {
                     eprintln!("Warning: Failed to initialize hlsmi manager: {e}");
                 } else {
@@ -152,7 +160,7 @@ async fn main() {
 
             // Initialize hlsmi manager for Intel Gaudi on Linux
             #[cfg(target_os = "linux")]
-            if has_gaudi() {
+            if Settings::current().readers.is_enabled("gaudi") && has_gaudi() {
                 let interval = args.interval.unwrap_or(2);
                 std::thread::spawn(move || {
                     if let Err(e) = initialize_hlsmi_manager(interval) {
@@ -170,7 +178,7 @@ async fn main() {
             // Remote mode - no sudo required
 
             // Check if we're in Backend.AI environment and no hosts/hostfile provided
-            if args.hosts.is_none() && args.hostfile.is_none() {
+            if args.hosts.is_none() && args.hostfile.is_none() && args.discover.is_none() {
                 let runtime_env = RuntimeEnvironment::detect();
 
                 if let Some(backend_ai_hosts) = runtime_env.get_backend_ai_hosts() {
@@ -181,10 +189,11 @@ async fn main() {
                     }
                     args.hosts = Some(backend_ai_hosts);
                 } else {
-                    eprintln!("Error: Remote view mode requires --hosts or --hostfile");
+                    eprintln!("Error: Remote view mode requires --hosts, --hostfile or --discover");
                     eprintln!(
                         "Usage: all-smi view --hosts <URL>... or all-smi view --hostfile <FILE>"
                     );
+                    eprintln!("   or: all-smi view --discover <CIDR:PORT>...");
                     if runtime_env.is_backend_ai() {
                         eprintln!("\nBackend.AI environment detected but BACKENDAI_CLUSTER_HOSTS is not set.");
                         eprintln!("Set the environment variable with comma-separated host names:");
@@ -194,6 +203,9 @@ async fn main() {
                     std::process::exit(1);
                 }
             }
+            if let Err(e) = view::data_collector::HostSources::from_args(&args).check() {
+                exit_with_error(&e);
+            }
             view::run_view_mode(&args).await;
 
             // Cleanup after view mode exits
@@ -208,6 +220,12 @@ async fn main() {
                 shutdown_hlsmi_manager();
             }
         }
+        Some(Commands::Config(args)) => match args.command {
+            ConfigCommand::Show => match config.show() {
+                Ok(shown) => print!("{shown}"),
+                Err(e) => exit_with_error(&e),
+            },
+        },
         None => {
             // Default to local mode when no command is specified
             // On macOS, no sudo is needed
@@ -218,10 +236,13 @@ async fn main() {
             let has_sudo = ensure_sudo_permissions_with_fallback();
 
             if has_sudo {
+                let args = config.local_args().unwrap_or_else(|e| exit_with_error(&e));
+
                 // Initialize native metrics manager (no sudo required)
                 #[cfg(target_os = "macos")]
                 if is_apple_silicon() {
-                    if let Err(e) = initialize_native_metrics_manager(2000) {
+                    let interval = args.interval.unwrap_or(2);
+                    if let Err(e) = initialize_native_metrics_manager(interval * 1000) {
                         eprintln!("Warning: Failed to initialize native metrics manager: {e}");
                     } else {
                         use std::sync::atomic::Ordering;
@@ -231,9 +252,10 @@ async fn main() {
 
                 // Initialize hlsmi manager for Intel Gaudi on Linux
                 #[cfg(target_os = "linux")]
-                if has_gaudi() {
-                    std::thread::spawn(|| {
-                        if let Err(e) = initialize_hlsmi_manager(2) {
+                if Settings::current().readers.is_enabled("gaudi") && has_gaudi() {
+                    let interval = args.interval.unwrap_or(2);
+                    std::thread::spawn(move || {
+                        if let Err(e) = initialize_hlsmi_manager(interval) {
                             eprintln!("Warning: Failed to initialize hlsmi manager: {e}");
                         } else {
                             use std::sync::atomic::Ordering;
@@ -242,7 +264,7 @@ async fn main() {
                     });
                 }
 
-                view::run_local_mode(&LocalArgs { interval: None }).await;
+                view::run_local_mode(&args).await;
 
                 // Cleanup after local mode exits
                 #[cfg(target_os = "macos")]
@@ -272,6 +294,11 @@ async fn main() {
     }
 }
 
+fn exit_with_error(message: &str) -> ! {
+    eprintln!("Error: {message}");
+    std::process::exit(2);
+}
+
 // Set up a panic handler to ensure cleanup
 #[cfg(target_os = "macos")]
 fn setup_panic_handler() {
@@ -0,0 +1,213 @@
+// Copyright 2025 Lablup Inc. and Jeongkyu Shin
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Per-host backoff for remote hosts
+//!
+//! A host that fails a collection cycle is left alone until its backoff
+//! expires. The delay doubles with every failure up to a cap, with jitter so
+//! that nodes that went down together are not retried in lockstep. When the
+//! delay is over a single probe request is let through (half-open); its
+//! result closes the circuit or opens it again for longer.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use rand::Rng;
+
+use crate::app_state::ConnectionStatus;
+use crate::common::config::AppConfig;
+
+/// What to do with a host this cycle
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Attempt {
+    /// Fetch as usual, with retries
+    Request,
+    /// Backoff is over; send one request without retries
+    Probe,
+    /// Still backing off, or a probe is in flight
+    Skip,
+}
+
+/// State of a failing host; hosts that answer have no entry
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Circuit {
+    Open { retry_at: Instant },
+    HalfOpen,
+}
+
+#[derive(Debug)]
+struct HostBackoff {
+    circuit: Circuit,
+    failures: u32,
+    last_error: Option<String>,
+}
+
+pub struct Backoff {
+    hosts: HashMap<String, HostBackoff>,
+    base: Duration,
+    max: Duration,
+}
+
+impl Backoff {
+    pub fn new() -> Self {
+        Self {
+            hosts: HashMap::new(),
+            base: Duration::from_secs(AppConfig::BACKOFF_BASE_SECS),
+            max: Duration::from_secs(AppConfig::BACKOFF_MAX_SECS),
+        }
+    }
+
+    /// Decide whether to contact a host, moving a due host to half-open
+    pub fn attempt(&mut self, host: &str, now: Instant) -> Attempt {
+        let Some(entry) = self.hosts.get_mut(host) else {
+            return Attempt::Request;
+        };
+        match entry.circuit {
+            Circuit::Open { retry_at } if now >= retry_at => {
+                entry.circuit = Circuit::HalfOpen;
+                Attempt::Probe
+            }
+            Circuit::Open { .. } | Circuit::HalfOpen => Attempt::Skip,
+        }
+    }
+
+    pub fn record_success(&mut self, host: &str) {
+        self.hosts.remove(host);
+    }
+
+    pub fn record_failure(&mut self, host: &str, error: &str, now: Instant) {
+        let failures = self.hosts.get(host).map_or(0, |entry| entry.failures) + 1;
+        let delay = self.delay(failures, rand::rng().random_range(0.5..=1.0));
+        self.hosts.insert(
+            host.to_string(),
+            HostBackoff {
+                circuit: Circuit::Open {
+                    retry_at: now + delay,
+                },
+                failures,
+                last_error: Some(error.to_string()),
+            },
+        );
+    }
+
+    /// Give back a probe that was never sent, so the host is probed next cycle
+    pub fn release_probe(&mut self, host: &str, now: Instant) {
+        if let Some(entry) = self.hosts.get_mut(host) {
+            if entry.circuit == Circuit::HalfOpen {
+                entry.circuit = Circuit::Open { retry_at: now };
+            }
+        }
+    }
+
+    /// Copy a host's failure count, last error and retry time into its status
+    pub fn apply(&self, host: &str, status: &mut ConnectionStatus) {
+        let Some(entry) = self.hosts.get(host) else {
+            return;
+        };
+        status.consecutive_failures = entry.failures;
+        if status.last_error.is_none() {
+            status.last_error = entry.last_error.clone();
+        }
+        status.next_retry = match entry.circuit {
+            Circuit::Open { retry_at } => Some(retry_at),
+            Circuit::HalfOpen => None,
+        };
+    }
+
+    /// Forget hosts no longer monitored
+    pub fn retain(&mut self, hosts: &[String]) {
+        self.hosts.retain(|host, _| hosts.contains(host));
+    }
+
+    /// Delay after `failures` consecutive failures, scaled by `jitter`
+    fn delay(&self, failures: u32, jitter: f64) -> Duration {
+        let exponent = failures.saturating_sub(1).min(16);
+        self.base
+            .saturating_mul(1 << exponent)
+            .min(self.max)
+            .mul_f64(jitter)
+    }
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_delay_doubles_up_to_the_cap() {
+        let backoff = Backoff::new();
+        let base = Duration::from_secs(AppConfig::BACKOFF_BASE_SECS);
+        assert_eq!(backoff.delay(1, 1.0), base);
+        assert_eq!(backoff.delay(2, 1.0), base * 2);
+        assert_eq!(backoff.delay(3, 0.5), base * 2);
+        assert_eq!(
+            backoff.delay(40, 1.0),
+            Duration::from_secs(AppConfig::BACKOFF_MAX_SECS)
+        );
+    }
+
+    #[test]
+    fn test_open_half_open_closed() {
+        let mut backoff = Backoff::new();
+        let host = "node-1:9090";
+        let now = Instant::now();
+        assert_eq!(backoff.attempt(host, now), Attempt::Request);
+
+        backoff.record_failure(host, "connection refused", now);
+        assert_eq!(backoff.attempt(host, now), Attempt::Skip);
+
+        let mut status = ConnectionStatus::new(host.to_string(), host.to_string());
+        backoff.apply(host, &mut status);
+        assert_eq!(status.consecutive_failures, 1);
+        assert_eq!(status.last_error.as_deref(), Some("connection refused"));
+        let retry_at = status.next_retry.unwrap();
+        assert!(retry_at > now);
+
+        // One probe once the delay is over, nothing while it is in flight
+        assert_eq!(backoff.attempt(host, retry_at), Attempt::Probe);
+        assert_eq!(backoff.attempt(host, retry_at), Attempt::Skip);
+
+        backoff.record_failure(host, "connection refused", retry_at);
+        backoff.apply(host, &mut status);
+        assert_eq!(status.consecutive_failures, 2);
+        assert!(status.next_retry.unwrap() > retry_at);
+
+        backoff.record_success(host);
+        assert_eq!(backoff.attempt(host, retry_at), Attempt::Request);
+    }
+
+    #[test]
+    fn test_released_probe_is_retried_without_a_failure() {
+        let mut backoff = Backoff::new();
+        let host = "node-1:9090";
+        let now = Instant::now();
+        backoff.record_failure(host, "connection refused", now);
+
+        let mut status = ConnectionStatus::new(host.to_string(), host.to_string());
+        backoff.apply(host, &mut status);
+        let retry_at = status.next_retry.unwrap();
+        assert_eq!(backoff.attempt(host, retry_at), Attempt::Probe);
+
+        backoff.release_probe(host, retry_at);
+        assert_eq!(backoff.attempt(host, retry_at), Attempt::Probe);
+        backoff.apply(host, &mut status);
+        assert_eq!(status.consecutive_failures, 1);
+    }
+}
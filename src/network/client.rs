@@ -14,25 +14,86 @@
 
 use std::collections::HashMap;
 use std::net::IpAddr;
+use std::path::PathBuf;
 use std::str::FromStr;
 use std::sync::Arc;
 use std::sync::Once;
 use std::time::{Duration, Instant};
 
 use futures_util::stream::{FuturesUnordered, StreamExt};
+use http_body_util::{BodyExt, Empty};
+use hyper::body::Bytes;
 use regex::Regex;
 use tokio::sync::RwLock;
 use url::Url;
 
 use crate::app_state::ConnectionStatus;
-use crate::common::config::{AppConfig, EnvConfig};
-use crate::device::{CpuInfo, GpuInfo, MemoryInfo};
-use crate::storage::info::StorageInfo;
+use crate::cli::ViewArgs;
+use crate::common::config::{AppConfig, EnvConfig, Settings};
+
+use super::backoff::{Attempt, Backoff};
+use super::metrics_parser::ParsedMetrics;
+
+/// Client for API servers listening on a Unix domain socket
+#[cfg(unix)]
+type UnixClient = hyper_util::client::legacy::Client<hyperlocal::UnixConnector, Empty<Bytes>>;
 
 pub struct NetworkClient {
     client: reqwest::Client,
+    #[cfg(unix)]
+    unix_client: UnixClient,
     auth_token: Option<String>,
+    /// Scheme used for hosts given without one
+    scheme: &'static str,
     rate_limiter: Arc<RwLock<RateLimiter>>,
+    backoff: Arc<RwLock<Backoff>>,
+}
+
+/// TLS settings for connecting to API servers over HTTPS
+#[derive(Debug, Clone, Default)]
+pub struct ClientTlsConfig {
+    /// Extra PEM CA bundle trusted in addition to the system roots
+    pub ca_cert: Option<PathBuf>,
+    /// PEM client certificate for servers requiring mutual TLS
+    pub client_cert: Option<PathBuf>,
+    /// PKCS#8 PEM private key for `client_cert`
+    pub client_key: Option<PathBuf>,
+}
+
+impl ClientTlsConfig {
+    /// Build the TLS settings, or `None` when HTTPS was not requested.
+    /// HTTPS is used with `--tls`, any `--tls-*` file option, or an
+    /// `https://` host on the command line.
+    pub fn from_args(args: &ViewArgs) -> Option<Self> {
+        let https_host = args
+            .hosts
+            .iter()
+            .flatten()
+            .any(|host| host.starts_with("https://"));
+        let config = Self {
+            ca_cert: args.tls_ca_cert.as_ref().map(PathBuf::from),
+            client_cert: args.tls_client_cert.as_ref().map(PathBuf::from),
+            client_key: args.tls_client_key.as_ref().map(PathBuf::from),
+        };
+        let has_files =
+            config.ca_cert.is_some() || config.client_cert.is_some() || config.client_key.is_some();
+
+        (args.tls || https_host || has_files).then_some(config)
+    }
+}
+
+/// Where a host's metrics are fetched from
+enum Endpoint {
+    Http(String),
+    #[cfg(unix)]
+    Unix(PathBuf),
+}
+
+/// Why one attempt at fetching metrics failed
+enum FetchError {
+    Status(String),
+    Body(String),
+    Connection(String),
 }
 
 /// Simple rate limiter to prevent DoS attacks
@@ -86,58 +147,188 @@ impl RateLimiter {
 
 impl NetworkClient {
     pub fn new() -> Self {
-        // Validate connection pool limits against system resources
-        let max_idle_per_host = Self::validate_pool_limits(AppConfig::POOL_MAX_IDLE_PER_HOST);
+        let client = Self::client_builder().build().unwrap();
+        Self::from_parts(client, Self::configured_auth_token(), "http")
+    }
 
-        let client = reqwest::Client::builder()
-            .timeout(Duration::from_secs(AppConfig::CONNECTION_TIMEOUT_SECS))
-            .pool_idle_timeout(Duration::from_secs(AppConfig::POOL_IDLE_TIMEOUT_SECS))
-            .pool_max_idle_per_host(max_idle_per_host)
-            .tcp_keepalive(Duration::from_secs(AppConfig::TCP_KEEPALIVE_SECS))
-            .http2_keep_alive_interval(Duration::from_secs(AppConfig::HTTP2_KEEPALIVE_SECS))
-            .build()
-            .unwrap();
+    #[allow(dead_code)]
+    pub fn with_auth_token(auth_token: Option<String>) -> Self {
+        let client = Self::client_builder().build().unwrap();
+        Self::from_parts(client, auth_token, "http")
+    }
 
-        // Check for authentication token in environment variable
-        let auth_token = std::env::var("ALL_SMI_AUTH_TOKEN").ok();
-        if auth_token.is_some() {
-            eprintln!("Using authentication token from ALL_SMI_AUTH_TOKEN environment variable");
+    /// Create a client that connects over HTTPS, optionally trusting an
+    /// extra CA bundle and presenting a client certificate
+    pub fn with_tls(tls: &ClientTlsConfig) -> Result<Self, String> {
+        let mut builder = Self::client_builder();
+
+        if let Some(path) = &tls.ca_cert {
+            let pem = std::fs::read(path)
+                .map_err(|e| format!("Failed to read CA bundle {}: {e}", path.display()))?;
+            let certs = reqwest::Certificate::from_pem_bundle(&pem)
+                .map_err(|e| format!("Invalid CA bundle {}: {e}", path.display()))?;
+            for cert in certs {
+                builder = builder.add_root_certificate(cert);
+            }
+        }
+
+        if let (Some(cert_path), Some(key_path)) = (&tls.client_cert, &tls.client_key) {
+            let cert = std::fs::read(cert_path).map_err(|e| {
+                format!(
+                    "Failed to read client certificate {}: {e}",
+                    cert_path.display()
+                )
+            })?;
+            let key = std::fs::read(key_path)
+                .map_err(|e| format!("Failed to read client key {}: {e}", key_path.display()))?;
+            let identity = reqwest::Identity::from_pkcs8_pem(&cert, &key)
+                .map_err(|e| format!("Invalid client certificate or PKCS#8 key: {e}"))?;
+            builder = builder.identity(identity);
         }
 
+        let client = builder
+            .build()
+            .map_err(|e| format!("Failed to build HTTPS client: {e}"))?;
+
+        Ok(Self::from_parts(
+            client,
+            Self::configured_auth_token(),
+            "https",
+        ))
+    }
+
+    fn from_parts(
+        client: reqwest::Client,
+        auth_token: Option<String>,
+        scheme: &'static str,
+    ) -> Self {
         Self {
             client,
+            #[cfg(unix)]
+            unix_client: {
+                use hyperlocal::UnixClientExt;
+                hyper_util::client::legacy::Client::unix()
+            },
             auth_token,
+            scheme,
             rate_limiter: Arc::new(RwLock::new(RateLimiter::new())),
+            backoff: Arc::new(RwLock::new(Backoff::new())),
         }
     }
 
-    #[allow(dead_code)]
-    pub fn with_auth_token(auth_token: Option<String>) -> Self {
+    fn client_builder() -> reqwest::ClientBuilder {
+        // Validate connection pool limits against system resources
         let max_idle_per_host = Self::validate_pool_limits(AppConfig::POOL_MAX_IDLE_PER_HOST);
 
-        let client = reqwest::Client::builder()
-            .timeout(Duration::from_secs(AppConfig::CONNECTION_TIMEOUT_SECS))
+        reqwest::Client::builder()
+            .timeout(Duration::from_secs(
+                Settings::current().network.connection_timeout,
+            ))
+            .connect_timeout(Duration::from_secs(
+                Settings::current().network.connect_timeout,
+            ))
             .pool_idle_timeout(Duration::from_secs(AppConfig::POOL_IDLE_TIMEOUT_SECS))
             .pool_max_idle_per_host(max_idle_per_host)
             .tcp_keepalive(Duration::from_secs(AppConfig::TCP_KEEPALIVE_SECS))
             .http2_keep_alive_interval(Duration::from_secs(AppConfig::HTTP2_KEEPALIVE_SECS))
-            .build()
-            .unwrap();
+    }
 
-        Self {
-            client,
-            auth_token,
-            rate_limiter: Arc::new(RwLock::new(RateLimiter::new())),
+    fn configured_auth_token() -> Option<String> {
+        // From ALL_SMI_AUTH_TOKEN, or network.auth_token in the config file
+        let auth_token = Settings::current().network.auth_token.clone();
+        if auth_token.is_some() {
+            eprintln!("Using configured authentication token");
+        }
+        auth_token
+    }
+
+    /// Resolve a host to where its metrics are fetched from. Hosts written
+    /// as `unix:///path` (or `unix:/path`) name an API server socket.
+    fn resolve_endpoint(host: &str, scheme: &str) -> Result<Endpoint, String> {
+        let Some(path) = host
+            .strip_prefix("unix://")
+            .or_else(|| host.strip_prefix("unix:"))
+        else {
+            return Self::validate_and_build_url(host, scheme).map(Endpoint::Http);
+        };
+        if path.is_empty() {
+            return Err(format!("Unix socket host '{host}' has no socket path"));
+        }
+        #[cfg(unix)]
+        return Ok(Endpoint::Unix(PathBuf::from(path)));
+        #[cfg(not(unix))]
+        return Err(format!(
+            "Unix socket host '{host}': Unix sockets are not supported on this platform"
+        ));
+    }
+
+    /// Make one attempt at fetching the metrics text of an endpoint
+    async fn fetch_metrics(
+        client: &reqwest::Client,
+        #[cfg(unix)] unix_client: &UnixClient,
+        endpoint: &Endpoint,
+        auth_token: Option<&str>,
+    ) -> Result<String, FetchError> {
+        match endpoint {
+            Endpoint::Http(url) => {
+                let mut request = client.get(url);
+                if let Some(token) = auth_token {
+                    request = request.header("Authorization", format!("Bearer {token}"));
+                }
+                let response = request
+                    .send()
+                    .await
+                    .map_err(|e| FetchError::Connection(e.to_string()))?;
+                if !response.status().is_success() {
+                    return Err(FetchError::Status(response.status().to_string()));
+                }
+                response
+                    .text()
+                    .await
+                    .map_err(|e| FetchError::Body(e.to_string()))
+            }
+            #[cfg(unix)]
+            Endpoint::Unix(path) => {
+                let timeout = Duration::from_secs(Settings::current().network.connection_timeout);
+                let fetch = async {
+                    let mut request = hyper::Request::get(hyperlocal::Uri::new(path, "/metrics"));
+                    if let Some(token) = auth_token {
+                        request = request.header("Authorization", format!("Bearer {token}"));
+                    }
+                    let request = request
+                        .body(Empty::<Bytes>::new())
+                        .map_err(|e| FetchError::Connection(e.to_string()))?;
+                    let response = unix_client
+                        .request(request)
+                        .await
+                        .map_err(|e| FetchError::Connection(e.to_string()))?;
+                    if !response.status().is_success() {
+                        return Err(FetchError::Status(response.status().to_string()));
+                    }
+                    let body = response
+                        .into_body()
+                        .collect()
+                        .await
+                        .map_err(|e| FetchError::Body(e.to_string()))?
+                        .to_bytes();
+                    String::from_utf8(body.to_vec()).map_err(|e| FetchError::Body(e.to_string()))
+                };
+                tokio::time::timeout(timeout, fetch)
+                    .await
+                    .unwrap_or_else(|_| {
+                        Err(FetchError::Connection("request timed out".to_string()))
+                    })
+            }
         }
     }
 
     /// Validate and build a secure URL from the host string
-    fn validate_and_build_url(host: &str) -> Result<String, String> {
+    fn validate_and_build_url(host: &str, scheme: &str) -> Result<String, String> {
         // Prevent SSRF attacks by validating the host
         let base_url = if host.starts_with("http://") || host.starts_with("https://") {
             host.to_string()
         } else {
-            format!("http://{host}")
+            format!("{scheme}://{host}")
         };
 
         // Parse and validate URL
@@ -155,7 +346,7 @@ impl NetworkClient {
             if host_str == "localhost" || host_str == "127.0.0.1" || host_str == "::1" {
                 // Allow localhost for local testing, but log it once unless suppressed
                 static LOCALHOST_WARNING: Once = Once::new();
-                if std::env::var("SUPPRESS_LOCALHOST_WARNING").is_err() {
+                if !Settings::current().network.suppress_localhost_warning {
                     LOCALHOST_WARNING.call_once(|| {
                         eprintln!("Warning: Connecting to localhost address (subsequent warnings suppressed)");
                     });
@@ -168,7 +359,7 @@ impl NetworkClient {
                     IpAddr::V4(ipv4) => {
                         if ipv4.is_private() || ipv4.is_loopback() || ipv4.is_link_local() {
                             static PRIVATE_IP_WARNING: Once = Once::new();
-                            if std::env::var("SUPPRESS_LOCALHOST_WARNING").is_err() {
+                            if !Settings::current().network.suppress_localhost_warning {
                                 PRIVATE_IP_WARNING.call_once(|| {
                                     eprintln!("Warning: Connecting to private/local IP addresses (subsequent warnings suppressed)");
                                 });
@@ -178,7 +369,7 @@ impl NetworkClient {
                     IpAddr::V6(ipv6) => {
                         if ipv6.is_loopback() || ipv6.is_unspecified() {
                             static IPV6_WARNING: Once = Once::new();
-                            if std::env::var("SUPPRESS_LOCALHOST_WARNING").is_err() {
+                            if !Settings::current().network.suppress_localhost_warning {
                                 IPV6_WARNING.call_once(|| {
                                     eprintln!("Warning: Connecting to loopback/unspecified IPv6 addresses (subsequent warnings suppressed)");
                                 });
@@ -262,34 +453,68 @@ impl NetworkClient {
         }
     }
 
+    /// Fetch a host's metrics text once, without retries or backoff, giving
+    /// up after `timeout`
+    pub async fn probe(&self, host: &str, timeout: Duration) -> Result<String, String> {
+        let endpoint = Self::resolve_endpoint(host, self.scheme)?;
+        let fetch = Self::fetch_metrics(
+            &self.client,
+            #[cfg(unix)]
+            &self.unix_client,
+            &endpoint,
+            self.auth_token.as_deref(),
+        );
+        match tokio::time::timeout(timeout, fetch).await {
+            Ok(Ok(text)) => Ok(text),
+            Ok(Err(FetchError::Status(status))) => Err(format!("HTTP {status}")),
+            Ok(Err(FetchError::Body(e) | FetchError::Connection(e))) => Err(e),
+            Err(_) => Err("request timed out".to_string()),
+        }
+    }
+
     pub async fn fetch_remote_data(
         &self,
         hosts: &[String],
         semaphore: &Arc<tokio::sync::Semaphore>,
         re: &Regex,
-    ) -> (
-        Vec<GpuInfo>,
-        Vec<CpuInfo>,
-        Vec<MemoryInfo>,
-        Vec<StorageInfo>,
-        Vec<ConnectionStatus>,
-    ) {
-        let mut all_gpu_info = Vec::new();
-        let mut all_cpu_info = Vec::new();
-        let mut all_memory_info = Vec::new();
-        let mut all_storage_info = Vec::new();
+    ) -> (ParsedMetrics, Vec<ConnectionStatus>) {
+        let mut all_metrics = ParsedMetrics::default();
         let mut connection_statuses = Vec::new();
 
+        // Hosts that are backing off are reported without being contacted;
+        // a host whose backoff is over gets a single probe request
+        let retry_attempts = Settings::current().network.retry_attempts;
+        let mut due_hosts = Vec::with_capacity(hosts.len());
+        {
+            let now = Instant::now();
+            let mut backoff = self.backoff.write().await;
+            backoff.retain(hosts);
+            for host in hosts {
+                match backoff.attempt(host, now) {
+                    Attempt::Request => due_hosts.push((host.clone(), retry_attempts)),
+                    Attempt::Probe => due_hosts.push((host.clone(), 1)),
+                    Attempt::Skip => {
+                        let mut status = ConnectionStatus::new(host.clone(), host.clone());
+                        backoff.apply(host, &mut status);
+                        connection_statuses.push(status);
+                    }
+                }
+            }
+        }
+
         // Parallel data collection with concurrency limiting and retries
-        let total_hosts = hosts.len();
+        let total_hosts = due_hosts.len();
         let mut fetch_futures = FuturesUnordered::new();
 
-        for (i, host) in hosts.iter().enumerate() {
+        for (i, (host, attempts)) in due_hosts.into_iter().enumerate() {
             let client = self.client.clone();
-            let host = host.clone();
+            #[cfg(unix)]
+            let unix_client = self.unix_client.clone();
             let semaphore = semaphore.clone();
             let auth_token = self.auth_token.clone();
+            let scheme = self.scheme;
             let rate_limiter = self.rate_limiter.clone();
+            let backoff = self.backoff.clone();
 
             let future = tokio::spawn(async move {
                 // Stagger connection attempts to avoid overwhelming the listen queue
@@ -299,10 +524,13 @@ impl NetworkClient {
                 // Acquire semaphore permit to limit concurrency
                 let _permit = semaphore.acquire().await.unwrap();
 
-                // Check rate limit before making request
+                // Check rate limit before making request. The host was never
+                // contacted, so this is not recorded as a failure in `backoff`;
+                // a probe that did not go out is handed back instead.
                 {
                     let mut limiter = rate_limiter.write().await;
                     if !limiter.check_rate_limit(&host).await {
+                        backoff.write().await.release_probe(&host, Instant::now());
                         return Some((
                             host,
                             String::new(),
@@ -311,66 +539,62 @@ impl NetworkClient {
                     }
                 }
 
-                // Validate and sanitize the URL
-                let url = match Self::validate_and_build_url(&host) {
-                    Ok(u) => u,
-                    Err(e) => {
-                        return Some((host, String::new(), Some(format!("Invalid URL: {e}"))))
-                    }
-                };
-
-                // Retry logic with exponential backoff
-                for attempt in 1..=AppConfig::RETRY_ATTEMPTS {
-                    // Build request with optional authentication
-                    let mut request = client.get(&url);
-                    if let Some(ref token) = auth_token {
-                        request = request.header("Authorization", format!("Bearer {token}"));
-                    }
+                let fetch = async move {
+                    // Validate and sanitize the URL or socket path
+                    let endpoint = match Self::resolve_endpoint(&host, scheme) {
+                        Ok(endpoint) => endpoint,
+                        Err(e) => {
+                            return Some((host, String::new(), Some(format!("Invalid URL: {e}"))))
+                        }
+                    };
+
+                    // Retry logic with exponential backoff
+                    for attempt in 1..=attempts {
+                        let result = Self::fetch_metrics(
+                            &client,
+                            #[cfg(unix)]
+                            &unix_client,
+                            &endpoint,
+                            auth_token.as_deref(),
+                        )
+                        .await;
 
-                    match request.send().await {
-                        Ok(response) => {
-                            if response.status().is_success() {
-                                match response.text().await {
-                                    Ok(text) => return Some((host, text, None)),
-                                    Err(e) => {
-                                        if attempt == 3 {
-                                            return Some((
-                                                host,
-                                                String::new(),
-                                                Some(format!("Text parse error: {e}")),
-                                            ));
-                                        }
+                        match result {
+                            Ok(text) => return Some((host, text, None)),
+                            Err(e) if attempt == attempts => {
+                                let message = match e {
+                                    FetchError::Status(status) => format!("HTTP {status}"),
+                                    FetchError::Body(e) => format!("Text parse error: {e}"),
+                                    FetchError::Connection(e) => {
+                                        format!("Connection error after {attempt} attempts: {e}")
                                     }
-                                }
-                            } else if attempt == 3 {
-                                return Some((
-                                    host,
-                                    String::new(),
-                                    Some(format!("HTTP {}", response.status())),
-                                ));
-                            }
-                        }
-                        Err(e) => {
-                            if attempt == 3 {
-                                return Some((
-                                    host,
-                                    String::new(),
-                                    Some(format!("Connection error after {attempt} attempts: {e}")),
-                                ));
+                                };
+                                return Some((host, String::new(), Some(message)));
                             }
+                            Err(_) => {}
                         }
+
+                        // Exponential backoff
+                        tokio::time::sleep(Duration::from_millis(EnvConfig::retry_delay(attempt)))
+                            .await;
                     }
 
-                    // Exponential backoff
-                    tokio::time::sleep(Duration::from_millis(EnvConfig::retry_delay(attempt)))
-                        .await;
-                }
+                    Some((
+                        host,
+                        String::new(),
+                        Some("All retry attempts failed".to_string()),
+                    ))
+                };
 
-                Some((
-                    host,
-                    String::new(),
-                    Some("All retry attempts failed".to_string()),
-                ))
+                let result = fetch.await;
+                if let Some((host, _, error)) = &result {
+                    let mut backoff = backoff.write().await;
+                    match error {
+                        Some(error) => backoff.record_failure(host, error, Instant::now()),
+                        None => backoff.record_success(host),
+                    }
+                }
+                result
             });
 
             fetch_futures.push(future);
@@ -386,7 +610,7 @@ impl NetworkClient {
         let timeout_future = tokio::time::sleep(overall_timeout);
         tokio::pin!(timeout_future);
 
-        loop {
+        while responses_received < total_hosts {
             tokio::select! {
                 // Process next result if available
                 Some(task_result) = fetch_futures.next() => {
@@ -401,6 +625,7 @@ impl NetworkClient {
                             if let Some(error_msg) = error {
                                 _failed_connections += 1;
                                 connection_status.mark_failure(error_msg);
+                                self.backoff.read().await.apply(&host, &mut connection_status);
                                 connection_statuses.push(connection_status);
                             } else {
                                 _successful_connections += 1;
@@ -410,24 +635,20 @@ impl NetworkClient {
                                     connection_statuses.push(connection_status);
                                 } else {
                                     let parser = super::metrics_parser::MetricsParser::new();
-                                    let (gpu_info, cpu_info, memory_info, storage_info) =
-                                        parser.parse_metrics(&text, &host, re);
+                                    let metrics = parser.parse_metrics(&text, &host, re);
 
                                     // Extract the instance name from device info if available
-                                    let instance_name = if let Some(first_gpu) = gpu_info.first() {
+                                    let instance_name = if let Some(first_gpu) = metrics.gpu_info.first() {
                                         Some(first_gpu.instance.clone())
-                                    } else if let Some(first_cpu) = cpu_info.first() {
+                                    } else if let Some(first_cpu) = metrics.cpu_info.first() {
                                         Some(first_cpu.instance.clone())
-                                    } else { memory_info.first().map(|first_memory| first_memory.instance.clone()) };
+                                    } else { metrics.memory_info.first().map(|first_memory| first_memory.instance.clone()) };
 
                                     // Store the instance name as actual_hostname for display purposes
                                     connection_status.actual_hostname = instance_name;
                                     connection_statuses.push(connection_status);
 
-                                    all_gpu_info.extend(gpu_info);
-                                    all_cpu_info.extend(cpu_info);
-                                    all_memory_info.extend(memory_info);
-                                    all_storage_info.extend(storage_info);
+                                    all_metrics.extend(metrics);
                                 }
                             }
                         }
@@ -440,11 +661,6 @@ impl NetworkClient {
                             // We don't have host information for Err results, so we can't create a connection status
                         }
                     }
-
-                    // Check if we've received responses from all hosts
-                    if responses_received >= total_hosts {
-                        break;
-                    }
                 }
                 // Timeout reached - return partial results
                 _ = &mut timeout_future => {
@@ -461,13 +677,7 @@ impl NetworkClient {
         //     );
         // }
 
-        (
-            all_gpu_info,
-            all_cpu_info,
-            all_memory_info,
-            all_storage_info,
-            connection_statuses,
-        )
+        (all_metrics, connection_statuses)
     }
 }
 
@@ -476,3 +686,118 @@ impl Default for NetworkClient {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_client_tls_config_from_args() {
+        assert!(ClientTlsConfig::from_args(&ViewArgs::default()).is_none());
+
+        let args = ViewArgs {
+            tls: true,
+            ..Default::default()
+        };
+        assert!(ClientTlsConfig::from_args(&args).is_some());
+
+        let args = ViewArgs {
+            hosts: Some(vec!["https://node-1:9090".to_string()]),
+            ..Default::default()
+        };
+        assert!(ClientTlsConfig::from_args(&args).is_some());
+
+        let args = ViewArgs {
+            tls_ca_cert: Some("/etc/all-smi/ca.pem".to_string()),
+            ..Default::default()
+        };
+        let config = ClientTlsConfig::from_args(&args).unwrap();
+        assert_eq!(config.ca_cert, Some(PathBuf::from("/etc/all-smi/ca.pem")));
+    }
+
+    #[test]
+    fn test_with_tls_reports_unreadable_ca() {
+        let config = ClientTlsConfig {
+            ca_cert: Some(PathBuf::from("/nonexistent/ca.pem")),
+            ..Default::default()
+        };
+        let err = NetworkClient::with_tls(&config).err().unwrap();
+        assert!(err.contains("/nonexistent/ca.pem"));
+    }
+
+    #[test]
+    fn test_validate_and_build_url_uses_scheme() {
+        assert_eq!(
+            NetworkClient::validate_and_build_url("node-1:9090", "https").unwrap(),
+            "https://node-1:9090/metrics"
+        );
+        // An explicit scheme on the host wins
+        assert_eq!(
+            NetworkClient::validate_and_build_url("http://node-1:9090", "https").unwrap(),
+            "http://node-1:9090/metrics"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_resolve_unix_endpoint() {
+        for host in ["unix:///run/all-smi.sock", "unix:/run/all-smi.sock"] {
+            match NetworkClient::resolve_endpoint(host, "https").unwrap() {
+                Endpoint::Unix(path) => assert_eq!(path, PathBuf::from("/run/all-smi.sock")),
+                Endpoint::Http(url) => panic!("{host} resolved to {url}"),
+            }
+        }
+        assert!(NetworkClient::resolve_endpoint("unix://", "http").is_err());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_fetch_over_unix_socket() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("all-smi.sock");
+        let listener = tokio::net::UnixListener::bind(&path).unwrap();
+        let app = axum::Router::new().route(
+            "/metrics",
+            axum::routing::get(|| async {
+                "all_smi_memory_total_bytes{instance=\"node-1\", index=\"0\"} 1024\n"
+            }),
+        );
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        let client = NetworkClient::with_auth_token(None);
+        let host = format!("unix://{}", path.display());
+        let semaphore = Arc::new(tokio::sync::Semaphore::new(1));
+        let re = Regex::new(r"^all_smi_([^\{]+)\{([^}]+)\} ([\d\.]+)$").unwrap();
+        let (metrics, statuses) = client
+            .fetch_remote_data(std::slice::from_ref(&host), &semaphore, &re)
+            .await;
+
+        assert!(statuses[0].is_connected, "{:?}", statuses[0].last_error);
+        assert_eq!(metrics.memory_info.len(), 1);
+        assert_eq!(metrics.memory_info[0].host_id, host);
+        assert_eq!(metrics.memory_info[0].total_bytes, 1024);
+    }
+
+    #[tokio::test]
+    async fn test_failed_host_is_not_contacted_while_backing_off() {
+        let client = NetworkClient::with_auth_token(None);
+        let host = "127.0.0.1:1".to_string();
+        let semaphore = Arc::new(tokio::sync::Semaphore::new(1));
+        let re = Regex::new(r"^all_smi_([^\{]+)\{([^}]+)\} ([\d\.]+)$").unwrap();
+
+        let (_, statuses) = client
+            .fetch_remote_data(std::slice::from_ref(&host), &semaphore, &re)
+            .await;
+        assert!(!statuses[0].is_connected);
+        assert_eq!(statuses[0].consecutive_failures, 1);
+        let next_retry = statuses[0].next_retry.unwrap();
+
+        let started = Instant::now();
+        let (_, statuses) = client
+            .fetch_remote_data(std::slice::from_ref(&host), &semaphore, &re)
+            .await;
+        assert!(started.elapsed() < Duration::from_millis(50));
+        assert_eq!(statuses[0].next_retry, Some(next_retry));
+        assert!(statuses[0].last_error.is_some());
+    }
+}
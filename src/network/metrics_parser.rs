@@ -18,9 +18,34 @@ use crate::parsing::common::sanitize_label_value;
 use chrono::Local;
 use regex::Regex;
 
-use crate::device::{AppleSiliconCpuInfo, CpuInfo, CpuPlatformType, GpuInfo, MemoryInfo};
+use crate::device::{
+    AppleSiliconCpuInfo, ChassisInfo, CpuInfo, CpuPlatformType, FanInfo, GpuInfo, MemoryInfo,
+    ProcessInfo,
+};
 use crate::storage::info::StorageInfo;
 
+/// Devices, chassis and processes rebuilt from one or more metrics payloads
+#[derive(Default)]
+pub struct ParsedMetrics {
+    pub gpu_info: Vec<GpuInfo>,
+    pub cpu_info: Vec<CpuInfo>,
+    pub memory_info: Vec<MemoryInfo>,
+    pub storage_info: Vec<StorageInfo>,
+    pub chassis_info: Vec<ChassisInfo>,
+    pub process_info: Vec<ProcessInfo>,
+}
+
+impl ParsedMetrics {
+    pub fn extend(&mut self, other: ParsedMetrics) {
+        self.gpu_info.extend(other.gpu_info);
+        self.cpu_info.extend(other.cpu_info);
+        self.memory_info.extend(other.memory_info);
+        self.storage_info.extend(other.storage_info);
+        self.chassis_info.extend(other.chassis_info);
+        self.process_info.extend(other.process_info);
+    }
+}
+
 pub struct MetricsParser;
 
 impl MetricsParser {
@@ -28,19 +53,10 @@ impl MetricsParser {
         Self
     }
 
-    pub fn parse_metrics(
-        &self,
-        text: &str,
-        host: &str,
-        re: &Regex,
-    ) -> (
-        Vec<GpuInfo>,
-        Vec<CpuInfo>,
-        Vec<MemoryInfo>,
-        Vec<StorageInfo>,
-    ) {
+    pub fn parse_metrics(&self, text: &str, host: &str, re: &Regex) -> ParsedMetrics {
         // Limit the maximum size of HashMaps to prevent memory exhaustion
         const MAX_DEVICES_PER_TYPE: usize = 256;
+        const MAX_PROCESSES: usize = 4096;
         const MAX_TEXT_SIZE: usize = 10_485_760; // 10MB max input
 
         // Validate input size
@@ -57,6 +73,8 @@ impl MetricsParser {
         let mut cpu_info_map: HashMap<String, CpuInfo> = HashMap::with_capacity(8);
         let mut memory_info_map: HashMap<String, MemoryInfo> = HashMap::with_capacity(8);
         let mut storage_info_map: HashMap<String, StorageInfo> = HashMap::with_capacity(32);
+        let mut chassis_info_map: HashMap<String, ChassisInfo> = HashMap::with_capacity(1);
+        let mut process_info_map: HashMap<String, ProcessInfo> = HashMap::with_capacity(32);
         let mut host_instance_name: Option<String> = None;
 
         for line in text.lines() {
@@ -104,16 +122,28 @@ impl MetricsParser {
                             host,
                         );
                     }
-                } else if (metric_name.starts_with("storage_") || metric_name.starts_with("disk_"))
-                    && storage_info_map.len() < MAX_DEVICES_PER_TYPE
-                {
-                    self.process_storage_metrics(
-                        &mut storage_info_map,
+                } else if metric_name.starts_with("storage_") || metric_name.starts_with("disk_") {
+                    if storage_info_map.len() < MAX_DEVICES_PER_TYPE {
+                        self.process_storage_metrics(
+                            &mut storage_info_map,
+                            &metric_name,
+                            &labels,
+                            value,
+                            host,
+                        );
+                    }
+                } else if metric_name.starts_with("chassis_") {
+                    self.process_chassis_metrics(
+                        &mut chassis_info_map,
                         &metric_name,
                         &labels,
                         value,
                         host,
                     );
+                } else if metric_name == "process_memory_used_bytes"
+                    && process_info_map.len() < MAX_PROCESSES
+                {
+                    self.process_process_metrics(&mut process_info_map, &labels, value, host);
                 }
             }
         }
@@ -129,12 +159,14 @@ impl MetricsParser {
             );
         }
 
-        (
-            gpu_info_map.into_values().collect(),
-            cpu_info_map.into_values().collect(),
-            memory_info_map.into_values().collect(),
-            storage_info_map.into_values().collect(),
-        )
+        ParsedMetrics {
+            gpu_info: gpu_info_map.into_values().collect(),
+            cpu_info: cpu_info_map.into_values().collect(),
+            memory_info: memory_info_map.into_values().collect(),
+            storage_info: storage_info_map.into_values().collect(),
+            chassis_info: chassis_info_map.into_values().collect(),
+            process_info: process_info_map.into_values().collect(),
+        }
     }
 
     fn parse_labels(&self, labels_str: &str) -> HashMap<String, String> {
@@ -514,6 +546,103 @@ impl MetricsParser {
         });
     }
 
+    fn process_chassis_metrics(
+        &self,
+        chassis_info_map: &mut HashMap<String, ChassisInfo>,
+        metric_name: &str,
+        labels: &HashMap<String, String>,
+        value: f64,
+        host: &str,
+    ) {
+        let chassis_info =
+            chassis_info_map
+                .entry(host.to_string())
+                .or_insert_with(|| ChassisInfo {
+                    host_id: host.to_string(),
+                    hostname: crate::get_label_or_default!(labels, "instance", host),
+                    instance: crate::get_label_or_default!(labels, "instance", host),
+                    time: Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+                    ..Default::default()
+                });
+
+        match metric_name {
+            "chassis_power_watts" => chassis_info.total_power_watts = Some(value),
+            "chassis_inlet_temperature_celsius" => chassis_info.inlet_temperature = Some(value),
+            "chassis_outlet_temperature_celsius" => chassis_info.outlet_temperature = Some(value),
+            "chassis_thermal_pressure_info" => {
+                chassis_info.thermal_pressure = labels.get("level").cloned();
+            }
+            "chassis_cpu_power_watts" | "chassis_gpu_power_watts" | "chassis_ane_power_watts" => {
+                let key = metric_name.trim_start_matches("chassis_");
+                chassis_info
+                    .detail
+                    .insert(key.to_string(), value.to_string());
+            }
+            "chassis_fan_speed_rpm" => {
+                let id = crate::get_label_or_default!(labels, "fan_id", "0");
+                chassis_info.fan_speeds.push(FanInfo {
+                    id: id.parse().unwrap_or(0),
+                    name: crate::get_label_or_default!(labels, "fan_name"),
+                    speed_rpm: value as u32,
+                    max_rpm: 0,
+                });
+            }
+            _ => {}
+        }
+    }
+
+    /// One process per `process_memory_used_bytes` series. Aggregated series
+    /// carry only some labels, so the rest are left empty.
+    fn process_process_metrics(
+        &self,
+        process_info_map: &mut HashMap<String, ProcessInfo>,
+        labels: &HashMap<String, String>,
+        value: f64,
+        host: &str,
+    ) {
+        let mut series: Vec<String> = labels.iter().map(|(k, v)| format!("{k}={v}")).collect();
+        series.sort_unstable();
+        let key = format!("{host}:{}", series.join(","));
+
+        // Summed series are named after what they sum over
+        let process_name = ["name", "container", "job"]
+            .iter()
+            .find_map(|label| labels.get(*label))
+            .cloned()
+            .unwrap_or_default();
+
+        process_info_map.insert(
+            key,
+            ProcessInfo {
+                host_id: host.to_string(),
+                device_id: crate::get_label_or_default!(labels, "device_id", "0")
+                    .parse()
+                    .unwrap_or(0),
+                device_uuid: crate::get_label_or_default!(labels, "device_uuid"),
+                pid: crate::get_label_or_default!(labels, "pid", "0")
+                    .parse()
+                    .unwrap_or(0),
+                command: process_name.clone(),
+                process_name,
+                used_memory: value as u64,
+                cpu_percent: 0.0,
+                memory_percent: 0.0,
+                memory_rss: 0,
+                memory_vms: 0,
+                user: crate::get_label_or_default!(labels, "user"),
+                state: String::new(),
+                start_time: String::new(),
+                cpu_time: 0,
+                ppid: 0,
+                threads: 0,
+                uses_gpu: true,
+                priority: 0,
+                nice_value: 0,
+                gpu_utilization: 0.0,
+            },
+        );
+    }
+
     fn ensure_apple_silicon_info(&self, cpu_info: &mut CpuInfo) {
         if cpu_info.apple_silicon_info.is_none() {
             cpu_info.apple_silicon_info = Some(AppleSiliconCpuInfo {
@@ -612,7 +741,7 @@ all_smi_gpu_power_consumption_watts{gpu="NVIDIA H200 141GB HBM3", instance="node
 all_smi_ane_utilization{gpu="NVIDIA H200 141GB HBM3", instance="node-0058", uuid="GPU-12345", index="0"} 15.2
 "#;
 
-        let (gpu_info, _, _, _) = parser.parse_metrics(test_data, host, &re);
+        let ParsedMetrics { gpu_info, .. } = parser.parse_metrics(test_data, host, &re);
 
         assert_eq!(gpu_info.len(), 1);
         let gpu = &gpu_info[0];
@@ -645,7 +774,7 @@ all_smi_cpu_temperature_celsius{cpu_model="Intel Xeon", instance="node-0058", ho
 all_smi_cpu_power_consumption_watts{cpu_model="Intel Xeon", instance="node-0058", hostname="node-0058", index="0"} 125.5
 "#;
 
-        let (_, cpu_info, _, _) = parser.parse_metrics(test_data, host, &re);
+        let ParsedMetrics { cpu_info, .. } = parser.parse_metrics(test_data, host, &re);
 
         assert_eq!(cpu_info.len(), 1);
         let cpu = &cpu_info[0];
@@ -681,7 +810,7 @@ all_smi_cpu_p_core_utilization{cpu_model="Apple M2 Max", instance="node-0058", h
 all_smi_cpu_e_core_utilization{cpu_model="Apple M2 Max", instance="node-0058", hostname="node-0058", index="0"} 10.8
 "#;
 
-        let (_, cpu_info, _, _) = parser.parse_metrics(test_data, host, &re);
+        let ParsedMetrics { cpu_info, .. } = parser.parse_metrics(test_data, host, &re);
 
         assert_eq!(cpu_info.len(), 1);
         let cpu = &cpu_info[0];
@@ -712,7 +841,7 @@ all_smi_memory_available_bytes{instance="node-0058", hostname="node-0058", index
 all_smi_memory_utilization{instance="node-0058", hostname="node-0058", index="0"} 50.0
 "#;
 
-        let (_, _, memory_info, _) = parser.parse_metrics(test_data, host, &re);
+        let ParsedMetrics { memory_info, .. } = parser.parse_metrics(test_data, host, &re);
 
         assert_eq!(memory_info.len(), 1);
         let memory = &memory_info[0];
@@ -738,7 +867,7 @@ all_smi_disk_total_bytes{instance="node-0058", mount_point="/home", index="1"} 1
 all_smi_disk_available_bytes{instance="node-0058", mount_point="/home", index="1"} 549755813888
 "#;
 
-        let (_, _, _, storage_info) = parser.parse_metrics(test_data, host, &re);
+        let ParsedMetrics { storage_info, .. } = parser.parse_metrics(test_data, host, &re);
 
         assert_eq!(storage_info.len(), 2);
 
@@ -773,8 +902,13 @@ all_smi_memory_total_bytes{instance="node-0001", hostname="node-0001", index="0"
 all_smi_disk_total_bytes{instance="node-0001", mount_point="/", index="0"} 2199023255552
 "#;
 
-        let (gpu_info, cpu_info, memory_info, storage_info) =
-            parser.parse_metrics(test_data, host, &re);
+        let ParsedMetrics {
+            gpu_info,
+            cpu_info,
+            memory_info,
+            storage_info,
+            ..
+        } = parser.parse_metrics(test_data, host, &re);
 
         assert_eq!(gpu_info.len(), 1);
         assert_eq!(cpu_info.len(), 1);
@@ -810,13 +944,21 @@ all_smi_gpu_utilization{malformed labels} invalid_value
 all_smi_unknown_metric{instance="test"} 42.0
 "#;
 
-        let (gpu_info, cpu_info, memory_info, storage_info) =
-            parser.parse_metrics(test_data, host, &re);
+        let ParsedMetrics {
+            gpu_info,
+            cpu_info,
+            memory_info,
+            storage_info,
+            chassis_info,
+            process_info,
+        } = parser.parse_metrics(test_data, host, &re);
 
         assert!(gpu_info.is_empty());
         assert!(cpu_info.is_empty());
         assert!(memory_info.is_empty());
         assert!(storage_info.is_empty());
+        assert!(chassis_info.is_empty());
+        assert!(process_info.is_empty());
     }
 
     #[test]
@@ -825,12 +967,21 @@ all_smi_unknown_metric{instance="test"} 42.0
         let re = create_test_regex();
         let host = "127.0.0.1:10058";
 
-        let (gpu_info, cpu_info, memory_info, storage_info) = parser.parse_metrics("", host, &re);
+        let ParsedMetrics {
+            gpu_info,
+            cpu_info,
+            memory_info,
+            storage_info,
+            chassis_info,
+            process_info,
+        } = parser.parse_metrics("", host, &re);
 
         assert!(gpu_info.is_empty());
         assert!(cpu_info.is_empty());
         assert!(memory_info.is_empty());
         assert!(storage_info.is_empty());
+        assert!(chassis_info.is_empty());
+        assert!(process_info.is_empty());
     }
 
     #[test]
@@ -844,7 +995,9 @@ all_smi_gpu_utilization{gpu="Tesla V100", instance="production-node-42", uuid="G
 all_smi_cpu_utilization{cpu_model="Intel Xeon", instance="production-node-42", hostname="node-0058", index="0"} 55.0
 "#;
 
-        let (gpu_info, cpu_info, _, _) = parser.parse_metrics(test_data, host, &re);
+        let ParsedMetrics {
+            gpu_info, cpu_info, ..
+        } = parser.parse_metrics(test_data, host, &re);
 
         assert_eq!(gpu_info[0].host_id, host);
         assert_eq!(gpu_info[0].hostname, "production-node-42");
@@ -875,7 +1028,7 @@ all_smi_cpu_utilization{cpu_model="Intel Xeon", instance="production-node-42", h
                 r#"all_smi_cpu_utilization{{cpu_model="{cpu_model}", instance="test", hostname="test", index="0"}} 50.0"#
             );
 
-            let (_, cpu_info, _, _) = parser.parse_metrics(&test_data, host, &re);
+            let ParsedMetrics { cpu_info, .. } = parser.parse_metrics(&test_data, host, &re);
             assert_eq!(cpu_info.len(), 1);
 
             match (&cpu_info[0].platform_type, &expected_type) {
@@ -910,9 +1063,78 @@ all_smi_gpu_utilization{instance="node-0058", index="0"} 25.5
 all_smi_disk_total_bytes{instance="node-0058", index="0"} 1000000000
 "#;
 
-        let (gpu_info, _, _, storage_info) = parser.parse_metrics(test_data, host, &re);
+        let ParsedMetrics {
+            gpu_info,
+            storage_info,
+            ..
+        } = parser.parse_metrics(test_data, host, &re);
 
         assert!(gpu_info.is_empty());
         assert!(storage_info.is_empty());
     }
+
+    #[test]
+    fn test_parse_chassis_metrics() {
+        let parser = create_test_parser();
+        let re = create_test_regex();
+        let host = "127.0.0.1:10058";
+
+        let test_data = r#"
+all_smi_chassis_power_watts{hostname="node-0058", instance="node-0058"} 812.50
+all_smi_chassis_thermal_pressure_info{hostname="node-0058", instance="node-0058", level="Nominal"} 1
+all_smi_chassis_cpu_power_watts{hostname="node-0058", instance="node-0058"} 12.25
+all_smi_chassis_inlet_temperature_celsius{hostname="node-0058", instance="node-0058"} 24.0
+all_smi_chassis_fan_speed_rpm{hostname="node-0058", instance="node-0058", fan_id="2", fan_name="FAN2"} 5400
+"#;
+
+        let ParsedMetrics { chassis_info, .. } = parser.parse_metrics(test_data, host, &re);
+
+        assert_eq!(chassis_info.len(), 1);
+        let chassis = &chassis_info[0];
+        assert_eq!(chassis.host_id, host);
+        assert_eq!(chassis.hostname, "node-0058");
+        assert_eq!(chassis.total_power_watts, Some(812.5));
+        assert_eq!(chassis.thermal_pressure.as_deref(), Some("Nominal"));
+        assert_eq!(chassis.detail["cpu_power_watts"], "12.25");
+        assert_eq!(chassis.inlet_temperature, Some(24.0));
+        assert_eq!(chassis.outlet_temperature, None);
+        assert_eq!(chassis.fan_speeds.len(), 1);
+        assert_eq!(chassis.fan_speeds[0].id, 2);
+        assert_eq!(chassis.fan_speeds[0].name, "FAN2");
+        assert_eq!(chassis.fan_speeds[0].speed_rpm, 5400);
+    }
+
+    #[test]
+    fn test_parse_process_metrics() {
+        let parser = create_test_parser();
+        let re = create_test_regex();
+        let host = "127.0.0.1:10058";
+
+        let test_data = r#"
+all_smi_process_memory_used_bytes{pid="100", name="python", device_id="1", device_uuid="GPU-1"} 4000
+all_smi_process_memory_used_bytes{pid="101", name="python", device_id="1", device_uuid="GPU-1"} 2500
+all_smi_process_memory_used_bytes{user="alice"} 6500
+all_smi_processes{user="alice"} 3
+"#;
+
+        let ParsedMetrics {
+            mut process_info, ..
+        } = parser.parse_metrics(test_data, host, &re);
+        process_info.sort_by_key(|p| p.used_memory);
+
+        assert_eq!(process_info.len(), 3);
+        let process = &process_info[1];
+        assert_eq!(process.host_id, host);
+        assert_eq!(process.pid, 100);
+        assert_eq!(process.process_name, "python");
+        assert_eq!(process.device_id, 1);
+        assert_eq!(process.device_uuid, "GPU-1");
+        assert_eq!(process.used_memory, 4000);
+        assert!(process.uses_gpu);
+
+        let summed = &process_info[2];
+        assert_eq!(summed.pid, 0);
+        assert_eq!(summed.user, "alice");
+        assert_eq!(summed.used_memory, 6500);
+    }
 }
@@ -12,7 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+pub mod backoff;
 pub mod client;
 pub mod metrics_parser;
 
-pub use client::NetworkClient;
+pub use client::{ClientTlsConfig, NetworkClient};
@@ -32,9 +32,9 @@ pub use collector::{
 
 pub use exporter::{
     BoxedCompositeExporter, CompositeExporter, CpuMetricsExporter, ExportFormat, ExporterBuilder,
-    ExporterError, ExporterFactory, ExporterResult, GpuMetricsExporter, MemoryMetricsExporter,
-    MetricCollection, MetricMetadata, MetricType, MetricValue, MetricsExporter,
-    StorageMetricsExporter,
+    ExporterError, ExporterFactory, ExporterResult, GpuMetricsExporter, Histogram,
+    MemoryMetricsExporter, MetricCollection, MetricMetadata, MetricType, MetricValue,
+    MetricsExporter, StorageMetricsExporter,
 };
 
 pub use mock_generator::{
@@ -232,6 +232,8 @@ mod tests {
             is_local_mode: false, // Test state assumes remote mode
             runtime_environment: crate::utils::RuntimeEnvironment::detect(),
             data_version: 0,
+            reader_status: Vec::new(),
+            node_labels: std::collections::BTreeMap::new(),
         }
     }
 
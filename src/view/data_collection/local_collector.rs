@@ -28,12 +28,11 @@ use crate::app_state::AppState;
 #[cfg(target_os = "linux")]
 use crate::device::platform_detection::has_tenstorrent;
 use crate::device::{
-    create_chassis_reader, get_cpu_readers, get_gpu_readers, get_memory_readers,
-    get_nvml_status_message,
+    collect_chassis_info, get_chassis_readers, get_cpu_readers, get_gpu_readers,
+    get_memory_readers, get_nvml_status_message,
     platform_detection::has_nvidia,
     process_list::{merge_gpu_processes, update_process_cache},
-    ChassisInfo, ChassisReader, CpuInfo, CpuReader, GpuInfo, GpuReader, MemoryInfo, MemoryReader,
-    ProcessInfo,
+    ChassisReader, CpuInfo, CpuReader, GpuInfo, GpuReader, MemoryInfo, MemoryReader, ProcessInfo,
 };
 
 #[cfg(target_os = "linux")]
@@ -63,7 +62,7 @@ pub struct LocalCollector {
     gpu_readers: Arc<RwLock<Vec<Box<dyn GpuReader>>>>,
     cpu_readers: Arc<RwLock<Vec<Box<dyn CpuReader>>>>,
     memory_readers: Arc<RwLock<Vec<Box<dyn MemoryReader>>>>,
-    chassis_reader: Arc<RwLock<Option<Box<dyn ChassisReader>>>>,
+    chassis_readers: Arc<RwLock<Vec<Box<dyn ChassisReader>>>>,
     aggregator: DataAggregator,
     initialized: Arc<Mutex<bool>>,
     /// PIDs of processes from the previous collection cycle (top N by CPU usage).
@@ -83,7 +82,7 @@ impl LocalCollector {
             gpu_readers: Arc::new(RwLock::new(Vec::new())),
             cpu_readers: Arc::new(RwLock::new(Vec::new())),
             memory_readers: Arc::new(RwLock::new(Vec::new())),
-            chassis_reader: Arc::new(RwLock::new(None)),
+            chassis_readers: Arc::new(RwLock::new(Vec::new())),
             aggregator: DataAggregator::new(),
             initialized: Arc::new(Mutex::new(false)),
             tracked_pids: Arc::new(RwLock::new(Vec::new())),
@@ -143,8 +142,7 @@ impl LocalCollector {
 
         let memory_readers = get_memory_readers();
 
-        // Create chassis reader
-        let chassis_reader = create_chassis_reader();
+        let chassis_readers = get_chassis_readers();
 
         // Store the readers in self using RwLock with timeout
         {
@@ -176,11 +174,11 @@ impl LocalCollector {
         }
         {
             if let Ok(mut chassis_lock) =
-                timeout(Duration::from_secs(2), self.chassis_reader.write()).await
+                timeout(Duration::from_secs(2), self.chassis_readers.write()).await
             {
-                *chassis_lock = Some(chassis_reader);
+                *chassis_lock = chassis_readers;
             } else {
-                eprintln!("Warning: Timeout acquiring chassis reader lock");
+                eprintln!("Warning: Timeout acquiring chassis readers lock");
             }
         }
 
@@ -234,7 +232,6 @@ impl LocalCollector {
         let gpu_readers_2 = Arc::clone(&self.gpu_readers);
         let cpu_readers = Arc::clone(&self.cpu_readers);
         let memory_readers = Arc::clone(&self.memory_readers);
-        let chassis_reader = Arc::clone(&self.chassis_reader);
         let process_cache = Arc::clone(&self.process_cache);
 
         let (
@@ -244,7 +241,6 @@ impl LocalCollector {
             gpu_processes,
             all_processes,
             all_storage_info,
-            all_chassis_info,
         ) = {
             let status_tx_gpu = status_tx.clone();
             let status_tx_cpu = status_tx.clone();
@@ -340,20 +336,14 @@ impl LocalCollector {
                         .send((4, "✓ Storage information collected".to_string()))
                         .await;
                     storage_info
-                },
-                // Chassis info collection
-                async move {
-                    let reader = chassis_reader.read().await;
-                    let info: Vec<ChassisInfo> = reader
-                        .as_ref()
-                        .and_then(|r| r.get_chassis_info())
-                        .into_iter()
-                        .collect();
-                    info
                 }
             )
         };
 
+        // Chassis power aggregates GPU power, so read it after the GPUs
+        let all_chassis_info =
+            collect_chassis_info(&self.chassis_readers.read().await, &all_gpu_info);
+
         // Close the channel and wait for status handler to finish
         drop(status_tx);
         let _ = status_handler.await;
@@ -482,13 +472,9 @@ impl LocalCollector {
 
         let all_storage_info = Self::collect_storage_info();
 
-        // Collect chassis info
-        let chassis_reader = self.chassis_reader.read().await;
-        let all_chassis_info: Vec<ChassisInfo> = chassis_reader
-            .as_ref()
-            .and_then(|r| r.get_chassis_info())
-            .into_iter()
-            .collect();
+        // Collect chassis info, feeding in this cycle's GPU power
+        let all_chassis_info =
+            collect_chassis_info(&self.chassis_readers.read().await, &all_gpu_info);
 
         CollectionData {
             gpu_info: all_gpu_info,
@@ -18,5 +18,5 @@ pub mod remote_collector;
 pub mod strategy;
 
 pub use local_collector::LocalCollector;
-pub use remote_collector::RemoteCollectorBuilder;
+pub use remote_collector::{RemoteCollector, RemoteCollectorBuilder};
 pub use strategy::{CollectionConfig, DataCollectionStrategy};
@@ -16,12 +16,14 @@ use async_trait::async_trait;
 use regex::{Regex, RegexBuilder};
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::Mutex;
 
 use crate::app_state::{AppState, ConnectionStatus};
-use crate::common::config::EnvConfig;
-use crate::network::NetworkClient;
+use crate::common::config::{AppConfig, EnvConfig};
+use crate::network::{ClientTlsConfig, NetworkClient};
 use crate::storage::info::StorageInfo;
+use crate::view::discovery::{self, DiscoveryTarget};
 
 use super::aggregator::DataAggregator;
 use super::strategy::{
@@ -62,6 +64,10 @@ pub struct RemoteCollector {
 
 impl RemoteCollector {
     pub fn new(max_connections: usize) -> Self {
+        Self::with_network_client(max_connections, NetworkClient::new())
+    }
+
+    pub fn with_network_client(max_connections: usize, network_client: NetworkClient) -> Self {
         // Use simpler quantifiers to avoid DFA explosion
         // The + quantifier is much more efficient than bounded quantifiers
         let regex = RegexBuilder::new(r"^all_smi_([^\{]+)\{([^}]+)\} ([\d\.]+)$")
@@ -71,7 +77,7 @@ impl RemoteCollector {
             .expect("Failed to compile metrics regex");
 
         Self {
-            network_client: NetworkClient::new(),
+            network_client,
             semaphore: Arc::new(tokio::sync::Semaphore::new(max_connections)),
             regex,
             aggregator: DataAggregator::new(),
@@ -84,6 +90,28 @@ impl RemoteCollector {
         Self::new(max_connections)
     }
 
+    /// Probe every address of the discovery targets and return the
+    /// `(host, identity)` of each endpoint that is an all-smi API server
+    pub async fn discover(&self, targets: &[DiscoveryTarget]) -> Vec<(String, String)> {
+        use futures_util::stream::{self, StreamExt};
+
+        let candidates: Vec<String> = targets.iter().flat_map(DiscoveryTarget::hosts).collect();
+        let concurrency = EnvConfig::max_concurrent_connections(candidates.len()).max(1);
+        let timeout = Duration::from_millis(AppConfig::DISCOVERY_PROBE_TIMEOUT_MS);
+
+        // Results come back in address order so a node seen on several
+        // addresses is kept at its lowest one
+        stream::iter(candidates)
+            .map(|host| async move {
+                let metrics = self.network_client.probe(&host, timeout).await.ok()?;
+                discovery::identify(&metrics).map(|identity| (host, identity))
+            })
+            .buffered(concurrency)
+            .filter_map(|found| async move { found })
+            .collect()
+            .await
+    }
+
     fn deduplicate_storage_info(storage_info: Vec<StorageInfo>) -> Vec<StorageInfo> {
         let mut deduplicated_storage: HashMap<String, StorageInfo> = HashMap::new();
         for storage in storage_info {
@@ -109,6 +137,10 @@ impl RemoteCollector {
         // Initialize known hosts if not already set
         if state.known_hosts.is_empty() {
             state.known_hosts = hosts.iter().map(|h| extract_host_identifier(h)).collect();
+            // Forget hosts a reload removed
+            state
+                .connection_status
+                .retain(|host_id, _| state.known_hosts.contains(host_id));
         }
 
         // Clear the reverse lookup map before rebuilding it
@@ -168,20 +200,20 @@ impl DataCollectionStrategy for RemoteCollector {
             return Err(CollectionError::Other("No hosts configured".to_string()));
         }
 
-        let (gpu_info, cpu_info, memory_info, storage_info, connection_statuses) = self
+        let (metrics, connection_statuses) = self
             .network_client
             .fetch_remote_data(&config.hosts, &self.semaphore, &self.regex)
             .await;
 
-        let deduplicated_storage = Self::deduplicate_storage_info(storage_info);
+        let deduplicated_storage = Self::deduplicate_storage_info(metrics.storage_info);
 
         Ok(CollectionData {
-            gpu_info,
-            cpu_info,
-            memory_info,
-            process_info: Vec::new(), // No process info in remote mode
+            gpu_info: metrics.gpu_info,
+            cpu_info: metrics.cpu_info,
+            memory_info: metrics.memory_info,
+            process_info: metrics.process_info, // Only from hosts exporting --processes
             storage_info: deduplicated_storage,
-            chassis_info: Vec::new(), // TODO: Parse chassis info from remote metrics
+            chassis_info: metrics.chassis_info,
             connection_statuses,
         })
     }
@@ -206,6 +238,15 @@ impl DataCollectionStrategy for RemoteCollector {
         state.cpu_info = data.cpu_info;
         state.memory_info = data.memory_info;
         state.storage_info = data.storage_info;
+        state.chassis_info = data.chassis_info;
+
+        let mut sorted_processes = data.process_info;
+        sorted_processes.sort_by(|a, b| {
+            state
+                .sort_criteria
+                .sort_processes(a, b, state.sort_direction)
+        });
+        state.process_info = sorted_processes;
 
         // Update connection status and maintain known hosts
         Self::update_connection_status(&mut state, data.connection_statuses, &config.hosts);
@@ -216,7 +257,6 @@ impl DataCollectionStrategy for RemoteCollector {
         // Update tabs from all device hostnames (including disconnected ones)
         Self::update_remote_tabs(&mut state);
 
-        state.process_info = Vec::new(); // No process info in remote mode
         state.loading = false;
 
         // Mark data as changed to trigger UI update
@@ -231,6 +271,7 @@ impl DataCollectionStrategy for RemoteCollector {
 pub struct RemoteCollectorBuilder {
     hosts: Vec<String>,
     max_connections: Option<usize>,
+    network_client: Option<NetworkClient>,
 }
 
 impl RemoteCollectorBuilder {
@@ -238,106 +279,37 @@ impl RemoteCollectorBuilder {
         Self {
             hosts: Vec::new(),
             max_connections: None,
+            network_client: None,
         }
     }
 
+    /// Connect to the hosts over HTTPS with the given TLS settings
+    pub fn with_tls(mut self, tls: &ClientTlsConfig) -> Result<Self, String> {
+        self.network_client = Some(NetworkClient::with_tls(tls)?);
+        Ok(self)
+    }
+
     pub fn with_hosts(mut self, hosts: Vec<String>) -> Self {
         self.hosts = hosts;
         self
     }
 
-    #[allow(dead_code)]
     pub fn with_max_connections(mut self, max_connections: usize) -> Self {
         self.max_connections = Some(max_connections);
         self
     }
 
-    pub fn load_hosts_from_file(mut self, file_path: &str) -> Result<Self, std::io::Error> {
-        use std::path::Path;
-
-        // Sanitize and validate file path
-        let path = Path::new(file_path);
-
-        // Resolve to absolute path and check it exists
-        let canonical_path = path.canonicalize().map_err(|e| {
-            std::io::Error::new(
-                std::io::ErrorKind::NotFound,
-                format!("Invalid hostfile path: {e}"),
-            )
-        })?;
-
-        // Ensure it's a file, not a directory
-        if !canonical_path.is_file() {
-            return Err(std::io::Error::new(
-                std::io::ErrorKind::InvalidInput,
-                "Hostfile path is not a regular file",
-            ));
-        }
-
-        // Check file size to prevent loading huge files
-        let metadata = std::fs::metadata(&canonical_path)?;
-        const MAX_FILE_SIZE: u64 = 10 * 1024 * 1024; // 10MB max
-        if metadata.len() > MAX_FILE_SIZE {
-            return Err(std::io::Error::new(
-                std::io::ErrorKind::InvalidInput,
-                format!(
-                    "Hostfile too large: {} bytes (max: {MAX_FILE_SIZE} bytes)",
-                    metadata.len()
-                ),
-            ));
-        }
-
-        let content = std::fs::read_to_string(&canonical_path)?;
-
-        // Limit number of hosts to prevent memory exhaustion
-        const MAX_HOSTS: usize = 1000;
-        let mut host_count = 0;
-
-        let file_hosts: Vec<String> = content
-            .lines()
-            .map(|s| s.trim())
-            .filter(|s| !s.is_empty())
-            .filter(|s| !s.starts_with('#'))
-            .take(MAX_HOSTS)
-            .filter_map(|s| {
-                host_count += 1;
-                if host_count > MAX_HOSTS {
-                    eprintln!("Warning: Hostfile contains more than {MAX_HOSTS} hosts, truncating");
-                    return None;
-                }
-
-                // Validate host format (basic validation)
-                let host = if let Some(stripped) = s.strip_prefix("http://") {
-                    stripped.to_string()
-                } else if let Some(stripped) = s.strip_prefix("https://") {
-                    stripped.to_string()
-                } else {
-                    s.to_string()
-                };
-
-                // Basic validation: must contain valid characters
-                if host
-                    .chars()
-                    .all(|c| c.is_ascii() && (c.is_alphanumeric() || ".-:_".contains(c)))
-                {
-                    Some(host)
-                } else {
-                    eprintln!("Warning: Invalid host format skipped: {s}");
-                    None
-                }
-            })
-            .collect();
-
-        self.hosts.extend(file_hosts);
-        Ok(self)
-    }
-
     pub fn build(self) -> RemoteCollector {
         let max_connections = self
             .max_connections
             .unwrap_or_else(|| EnvConfig::max_concurrent_connections(self.hosts.len()));
 
-        RemoteCollector::new(max_connections)
+        match self.network_client {
+            Some(network_client) => {
+                RemoteCollector::with_network_client(max_connections, network_client)
+            }
+            None => RemoteCollector::new(max_connections),
+        }
     }
 }
 
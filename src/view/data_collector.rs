@@ -14,17 +14,71 @@
 
 use std::sync::Arc;
 use std::time::Duration;
-use tokio::sync::Mutex;
+use tokio::sync::{mpsc, watch, Mutex};
+use tokio::task::JoinHandle;
 
 use crate::app_state::AppState;
 use crate::cli::ViewArgs;
-use crate::common::config::EnvConfig;
+use crate::common::config::{AppConfig, EnvConfig};
+use crate::network::ClientTlsConfig;
+use crate::view::data_collection::RemoteCollector;
+use crate::view::discovery::{self, DiscoveredNodes, DiscoveryTarget};
+use crate::view::hostlist;
 
 // Re-export for backward compatibility
 pub use super::data_collection::{
     CollectionConfig, DataCollectionStrategy, LocalCollector, RemoteCollectorBuilder,
 };
 
+/// Where remote mode takes its hosts from; replaced on reload
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct HostSources {
+    pub hosts: Vec<String>,
+    pub hostfile: Option<String>,
+    /// Port for hosts given without one
+    pub port: Option<u16>,
+    /// `--discover` ranges to scan for more hosts
+    pub discover: Vec<String>,
+}
+
+impl HostSources {
+    pub fn from_args(args: &ViewArgs) -> Self {
+        Self {
+            hosts: args.hosts.clone().unwrap_or_default(),
+            hostfile: args.hostfile.clone(),
+            port: args.port,
+            discover: args.discover.clone().unwrap_or_default(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.hosts.is_empty() && self.hostfile.is_none() && self.discover.is_empty()
+    }
+
+    /// Check the host list and discovery ranges without scanning
+    pub fn check(&self) -> Result<(), String> {
+        self.resolve()?;
+        self.discovery_targets()?;
+        Ok(())
+    }
+
+    pub fn discovery_targets(&self) -> Result<Vec<DiscoveryTarget>, String> {
+        discovery::parse_targets(&self.discover, self.port)
+    }
+
+    /// Read the hostfile and expand everything into the list of hosts
+    pub fn resolve(&self) -> Result<Vec<String>, String> {
+        let file_hosts = match &self.hostfile {
+            Some(path) => hostlist::read_hostfile(path)?,
+            None => Vec::new(),
+        };
+        hostlist::resolve(
+            self.hosts.iter().chain(&file_hosts).map(String::as_str),
+            self.port,
+        )
+    }
+}
+
 pub struct DataCollector {
     app_state: Arc<Mutex<AppState>>,
 }
@@ -97,94 +151,96 @@ impl DataCollector {
         }
     }
 
-    pub async fn run_remote_mode(
-        &self,
-        args: ViewArgs,
-        mut hosts: Vec<String>,
-        hostfile: Option<String>,
-    ) {
-        // Strip protocol prefix from command line hosts
-        hosts = hosts
-            .into_iter()
-            .map(|host| {
-                if let Some(stripped) = host.strip_prefix("http://") {
-                    stripped.to_string()
-                } else if let Some(stripped) = host.strip_prefix("https://") {
-                    stripped.to_string()
-                } else {
-                    host
-                }
-            })
-            .collect();
+    pub async fn run_remote_mode(&self, args: ViewArgs, mut sources: watch::Receiver<HostSources>) {
+        let mut current = sources.borrow_and_update().clone();
+        let mut hosts_list = match current.resolve() {
+            Ok(hosts) => hosts,
+            Err(e) => {
+                eprintln!("Error loading hosts: {e}");
+                return;
+            }
+        };
 
-        // Load hosts from file if specified
-        let mut builder = RemoteCollectorBuilder::new().with_hosts(hosts.clone());
+        let targets = current.discovery_targets().unwrap_or_default();
 
-        if let Some(ref file_path) = hostfile {
-            match builder.load_hosts_from_file(file_path) {
-                Ok(b) => builder = b,
-                Err(e) => {
-                    eprintln!("Error loading hosts from file {file_path}: {e}");
-                    return;
-                }
+        // Discovered nodes join later, so leave room for as many as could be found
+        let probes: usize = targets.iter().map(|target| target.hosts().count()).sum();
+        let max_connections = EnvConfig::max_concurrent_connections(hosts_list.len() + probes);
+        let collector = match remote_collector(&args, hosts_list.clone(), max_connections) {
+            Ok(collector) => collector,
+            Err(e) => {
+                eprintln!("{e}");
+                return;
             }
-        }
+        };
 
-        let collector = builder.build();
+        let mut last_error: Option<String> = None;
+        let mut discovered = DiscoveredNodes::default();
+        let mut discovery = Discovery::start(&args, targets);
 
         loop {
-            // Get the current hosts from builder with validation
-            let hosts_list = if let Some(file_path) = &hostfile {
-                let mut hosts_vec = hosts.clone();
-
-                // Validate file path
-                match std::fs::metadata(file_path) {
-                    Ok(metadata) => {
-                        const MAX_FILE_SIZE: u64 = 10 * 1024 * 1024; // 10MB
-                        if metadata.len() > MAX_FILE_SIZE {
-                            eprintln!("Warning: Hostfile too large, skipping reload");
-                            hosts_vec
-                        } else if let Ok(content) = std::fs::read_to_string(file_path) {
-                            const MAX_HOSTS: usize = 1000;
-                            let file_hosts: Vec<String> = content
-                                .lines()
-                                .map(|s| s.trim())
-                                .filter(|s| !s.is_empty())
-                                .filter(|s| !s.starts_with('#'))
-                                .take(MAX_HOSTS)
-                                .filter_map(|s| {
-                                    let host = if let Some(stripped) = s.strip_prefix("http://") {
-                                        stripped.to_string()
-                                    } else if let Some(stripped) = s.strip_prefix("https://") {
-                                        stripped.to_string()
-                                    } else {
-                                        s.to_string()
-                                    };
-
-                                    // Basic host validation
-                                    if host.chars().all(|c| {
-                                        c.is_ascii() && (c.is_alphanumeric() || ".-:_".contains(c))
-                                    }) {
-                                        Some(host)
-                                    } else {
-                                        None
-                                    }
-                                })
-                                .collect();
-                            hosts_vec.extend(file_hosts);
-                            hosts_vec
-                        } else {
-                            hosts_vec
-                        }
-                    }
-                    Err(e) => {
-                        eprintln!("Warning: Cannot access hostfile: {e}");
-                        hosts_vec
+            if sources.has_changed().unwrap_or(false) {
+                current = sources.borrow_and_update().clone();
+                // Reloads are checked before they are sent; the previous
+                // scan is stopped when its task is replaced
+                discovery =
+                    Discovery::start(&args, current.discovery_targets().unwrap_or_default());
+                discovered.clear();
+                // Tabs and connection states are rebuilt from the new list
+                self.app_state.lock().await.known_hosts.clear();
+            }
+
+            // The hostfile is re-read every cycle; while it is invalid the
+            // previous list is kept and the error shown once
+            match current.resolve() {
+                Ok(hosts) => {
+                    hosts_list = hosts;
+                    last_error = None;
+                }
+                Err(e) => {
+                    if last_error.as_ref() != Some(&e) {
+                        // A message over the notification length limit is not shown
+                        let _ = self
+                            .app_state
+                            .lock()
+                            .await
+                            .notifications
+                            .error(format!("Keeping previous host list: {e}"));
+                        last_error = Some(e);
                     }
                 }
-            } else {
-                hosts.clone()
-            };
+            }
+
+            // Scans run on their own task and are picked up when they finish
+            let mut added = 0;
+            if let Some(discovery) = discovery.as_mut() {
+                while let Ok(found) = discovery.found.try_recv() {
+                    added += discovered.merge(found);
+                }
+            }
+            if added > 0 {
+                let mut state = self.app_state.lock().await;
+                // Tabs and connection states are rebuilt with the new nodes
+                state.known_hosts.clear();
+                let _ = state
+                    .notifications
+                    .info(format!("Discovered {added} new node(s)"));
+            }
+            for host in discovered.hosts() {
+                if !hosts_list.contains(host) {
+                    hosts_list.push(host.clone());
+                }
+            }
+
+            // Nothing to collect until discovery finds a node
+            if hosts_list.is_empty() {
+                tokio::time::sleep(Duration::from_secs(
+                    args.interval
+                        .unwrap_or_else(|| EnvConfig::adaptive_interval(0)),
+                ))
+                .await;
+                continue;
+            }
 
             let config = CollectionConfig {
                 interval: args
@@ -213,3 +269,67 @@ impl DataCollector {
         }
     }
 }
+
+/// Remote collector for the view arguments, over HTTPS when TLS is configured
+fn remote_collector(
+    args: &ViewArgs,
+    hosts: Vec<String>,
+    max_connections: usize,
+) -> Result<RemoteCollector, String> {
+    let mut builder = RemoteCollectorBuilder::new()
+        .with_hosts(hosts)
+        .with_max_connections(max_connections);
+    if let Some(tls) = ClientTlsConfig::from_args(args) {
+        builder = builder
+            .with_tls(&tls)
+            .map_err(|e| format!("Error configuring TLS: {e}"))?;
+    }
+    Ok(builder.build())
+}
+
+/// Background scan of the `--discover` ranges, stopped when dropped
+struct Discovery {
+    task: JoinHandle<()>,
+    /// Nodes found by each completed scan, as `(host, identity)`
+    found: mpsc::UnboundedReceiver<Vec<(String, String)>>,
+}
+
+impl Discovery {
+    /// Scan the targets now and again [`AppConfig::DISCOVERY_RESCAN_SECS`]
+    /// after each scan finishes, on a collector of its own so that a long
+    /// scan never holds up the collection cycle
+    fn start(args: &ViewArgs, targets: Vec<DiscoveryTarget>) -> Option<Self> {
+        if targets.is_empty() {
+            return None;
+        }
+        let probes: usize = targets.iter().map(|target| target.hosts().count()).sum();
+        let collector = match remote_collector(
+            args,
+            Vec::new(),
+            EnvConfig::max_concurrent_connections(probes),
+        ) {
+            Ok(collector) => collector,
+            Err(e) => {
+                eprintln!("{e}");
+                return None;
+            }
+        };
+
+        let (tx, found) = mpsc::unbounded_channel();
+        let task = tokio::spawn(async move {
+            loop {
+                if tx.send(collector.discover(&targets).await).is_err() {
+                    return;
+                }
+                tokio::time::sleep(Duration::from_secs(AppConfig::DISCOVERY_RESCAN_SECS)).await;
+            }
+        });
+        Some(Self { task, found })
+    }
+}
+
+impl Drop for Discovery {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
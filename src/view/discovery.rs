@@ -0,0 +1,298 @@
+// Copyright 2025 Lablup Inc. and Jeongkyu Shin
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Discovery of API servers by scanning address ranges
+//!
+//! `--discover 10.0.0.0/24:9090-9091` probes every address and port of the
+//! range with a short timeout. An endpoint counts as an all-smi node when it
+//! answers with `all_smi_` metrics naming an instance (or, failing that, a
+//! container runtime hostname); the same node seen on several addresses is
+//! kept once. The ranges are scanned again periodically and new nodes are
+//! added to the ones already found.
+
+use std::collections::BTreeMap;
+use std::net::Ipv4Addr;
+use std::ops::RangeInclusive;
+
+use crate::common::config::AppConfig;
+
+/// One `--discover` range: an IPv4 network and a port range
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiscoveryTarget {
+    network: u32,
+    prefix: u8,
+    ports: RangeInclusive<u16>,
+}
+
+impl DiscoveryTarget {
+    /// Parse `CIDR[:PORT[-PORT]]`; without a port `default_port` is used
+    pub fn parse(target: &str, default_port: Option<u16>) -> Result<Self, String> {
+        let invalid = |reason: &str| format!("invalid discovery target '{target}': {reason}");
+
+        let (network, ports) = match target.split_once(':') {
+            Some((network, ports)) => (network, Some(ports)),
+            None => (target, None),
+        };
+        let (address, prefix) = match network.split_once('/') {
+            Some((address, prefix)) => (
+                address,
+                prefix
+                    .parse::<u8>()
+                    .ok()
+                    .filter(|prefix| *prefix <= 32)
+                    .ok_or_else(|| invalid("prefix must be 0-32"))?,
+            ),
+            None => (network, 32),
+        };
+        let address = address
+            .parse::<Ipv4Addr>()
+            .map_err(|_| invalid("expected an IPv4 network"))?;
+
+        let parse_port = |port: &str| {
+            port.parse::<u16>()
+                .ok()
+                .filter(|port| *port != 0)
+                .ok_or_else(|| invalid(&format!("invalid port '{port}'")))
+        };
+        let ports = match ports {
+            Some(ports) => match ports.split_once('-') {
+                Some((start, end)) => {
+                    let (start, end) = (parse_port(start)?, parse_port(end)?);
+                    if start > end {
+                        return Err(invalid("port range runs backwards"));
+                    }
+                    start..=end
+                }
+                None => {
+                    let port = parse_port(ports)?;
+                    port..=port
+                }
+            },
+            None => {
+                let port = default_port.ok_or_else(|| invalid("no port given and no --port"))?;
+                port..=port
+            }
+        };
+
+        let mask = u32::MAX.checked_shl(32 - u32::from(prefix)).unwrap_or(0);
+        let parsed = Self {
+            network: u32::from(address) & mask,
+            prefix,
+            ports,
+        };
+        if parsed.probe_count() > AppConfig::DISCOVERY_MAX_PROBES as u64 {
+            return Err(invalid(&format!(
+                "more than {} address and port combinations",
+                AppConfig::DISCOVERY_MAX_PROBES
+            )));
+        }
+        Ok(parsed)
+    }
+
+    /// Host addresses of the network; the network and broadcast addresses
+    /// are left out of ranges larger than two addresses
+    fn addresses(&self) -> RangeInclusive<u32> {
+        let size = 1u64 << (32 - u32::from(self.prefix));
+        let last = (u64::from(self.network) + size - 1) as u32;
+        if size > 2 {
+            self.network + 1..=last - 1
+        } else {
+            self.network..=last
+        }
+    }
+
+    fn probe_count(&self) -> u64 {
+        let addresses = self.addresses();
+        (u64::from(*addresses.end()) - u64::from(*addresses.start()) + 1)
+            * (u64::from(*self.ports.end()) - u64::from(*self.ports.start()) + 1)
+    }
+
+    /// Every `address:port` to probe
+    pub fn hosts(&self) -> impl Iterator<Item = String> + '_ {
+        self.addresses().flat_map(move |address| {
+            let address = Ipv4Addr::from(address);
+            self.ports
+                .clone()
+                .map(move |port| format!("{address}:{port}"))
+        })
+    }
+}
+
+/// Parse every `--discover` target, rejecting lists too large to scan
+pub fn parse_targets(
+    targets: &[String],
+    default_port: Option<u16>,
+) -> Result<Vec<DiscoveryTarget>, String> {
+    let targets = targets
+        .iter()
+        .map(|target| DiscoveryTarget::parse(target, default_port))
+        .collect::<Result<Vec<_>, _>>()?;
+    let total: u64 = targets.iter().map(DiscoveryTarget::probe_count).sum();
+    if total > AppConfig::DISCOVERY_MAX_PROBES as u64 {
+        return Err(format!(
+            "discovery targets cover more than {} address and port combinations",
+            AppConfig::DISCOVERY_MAX_PROBES
+        ));
+    }
+    Ok(targets)
+}
+
+/// Name of the node behind a metrics response, or `None` if it is not an
+/// all-smi API server
+pub fn identify(metrics: &str) -> Option<String> {
+    let lines = || metrics.lines().filter(|line| line.starts_with("all_smi_"));
+    lines()
+        .find_map(|line| label_value(line, "instance"))
+        .or_else(|| {
+            lines()
+                .filter(|line| line.starts_with("all_smi_container_runtime_info{"))
+                .find_map(|line| label_value(line, "hostname"))
+        })
+        .filter(|name| !name.is_empty())
+        .map(str::to_string)
+}
+
+fn label_value<'a>(line: &'a str, name: &str) -> Option<&'a str> {
+    let labels = &line[line.find('{')? + 1..line.find('}')?];
+    labels.split(',').find_map(|pair| {
+        let (key, value) = pair.split_once('=')?;
+        (key.trim() == name).then(|| value.trim().trim_matches('"'))
+    })
+}
+
+/// Nodes found so far, by identity, with the address each was first seen at
+#[derive(Debug, Default)]
+pub struct DiscoveredNodes {
+    nodes: BTreeMap<String, String>,
+}
+
+impl DiscoveredNodes {
+    /// Add the results of a scan; returns how many nodes are new
+    pub fn merge(&mut self, found: Vec<(String, String)>) -> usize {
+        let before = self.nodes.len();
+        for (host, identity) in found {
+            self.nodes.entry(identity).or_insert(host);
+        }
+        self.nodes.len() - before
+    }
+
+    pub fn hosts(&self) -> impl Iterator<Item = &String> {
+        self.nodes.values()
+    }
+
+    pub fn clear(&mut self) {
+        self.nodes.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_targets() {
+        let target = DiscoveryTarget::parse("127.0.0.0/29:10001-10002", None).unwrap();
+        let hosts: Vec<String> = target.hosts().collect();
+        assert_eq!(hosts.len(), 12);
+        assert_eq!(hosts[0], "127.0.0.1:10001");
+        assert_eq!(hosts[1], "127.0.0.1:10002");
+        assert_eq!(hosts[11], "127.0.0.6:10002");
+
+        // Host bits are masked off; single addresses and /31 keep every address
+        let target = DiscoveryTarget::parse("10.0.0.5/31", Some(9090)).unwrap();
+        assert_eq!(
+            target.hosts().collect::<Vec<_>>(),
+            vec!["10.0.0.4:9090", "10.0.0.5:9090"]
+        );
+        let target = DiscoveryTarget::parse("10.0.0.5:9090", None).unwrap();
+        assert_eq!(target.hosts().collect::<Vec<_>>(), vec!["10.0.0.5:9090"]);
+
+        assert!(DiscoveryTarget::parse("10.0.0.0/24", None).is_err());
+        assert!(DiscoveryTarget::parse("10.0.0.0/33:9090", None).is_err());
+        assert!(DiscoveryTarget::parse("::1/128:9090", None).is_err());
+        assert!(DiscoveryTarget::parse("10.0.0.0/24:9091-9090", None).is_err());
+        assert!(DiscoveryTarget::parse("10.0.0.0/8:9090", None).is_err());
+        assert!(parse_targets(
+            &[
+                "10.0.0.0/16:9090".to_string(),
+                "10.1.0.0/24:9090".to_string()
+            ],
+            None
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_identify() {
+        let metrics = "# HELP all_smi_gpu_utilization GPU utilization\n\
+                       all_smi_gpu_utilization{gpu=\"H100\", instance=\"node-0001\", index=\"0\"} 42\n";
+        assert_eq!(identify(metrics).as_deref(), Some("node-0001"));
+
+        let runtime = "all_smi_container_runtime_info{hostname=\"pod-7\", runtime=\"docker\"} 1\n";
+        assert_eq!(identify(runtime).as_deref(), Some("pod-7"));
+
+        assert_eq!(identify("node_cpu_seconds_total{cpu=\"0\"} 1\n"), None);
+        assert_eq!(identify("<html>not found</html>"), None);
+    }
+
+    #[test]
+    fn test_merge_keeps_first_address_per_node() {
+        let mut nodes = DiscoveredNodes::default();
+        let found = vec![
+            ("10.0.0.1:9090".to_string(), "node-1".to_string()),
+            ("10.0.1.1:9090".to_string(), "node-1".to_string()),
+            ("10.0.0.2:9090".to_string(), "node-2".to_string()),
+        ];
+        assert_eq!(nodes.merge(found), 2);
+        assert_eq!(
+            nodes.merge(vec![("10.0.0.2:9090".to_string(), "node-2".to_string())]),
+            0
+        );
+        assert_eq!(
+            nodes.hosts().collect::<Vec<_>>(),
+            vec!["10.0.0.1:9090", "10.0.0.2:9090"]
+        );
+    }
+
+    async fn serve(body: &'static str) -> u16 {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let app =
+            axum::Router::new().route("/metrics", axum::routing::get(move || async move { body }));
+        tokio::spawn(async move { axum::serve(listener, app).await });
+        port
+    }
+
+    #[tokio::test]
+    async fn test_discover_keeps_only_all_smi_endpoints() {
+        use crate::view::data_collection::RemoteCollectorBuilder;
+
+        let node = serve("all_smi_cpu_utilization{instance=\"node-0001\"} 12\n").await;
+        let other = serve("node_load1 0.5\n").await;
+        let targets = parse_targets(
+            &[format!("127.0.0.0/29:{node}"), format!("127.0.0.1:{other}")],
+            None,
+        )
+        .unwrap();
+
+        let collector = RemoteCollectorBuilder::new()
+            .with_max_connections(8)
+            .build();
+        let found = collector.discover(&targets).await;
+        assert_eq!(
+            found,
+            vec![(format!("127.0.0.1:{node}"), "node-0001".to_string())]
+        );
+    }
+}
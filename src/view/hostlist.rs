@@ -0,0 +1,297 @@
+// Copyright 2025 Lablup Inc. and Jeongkyu Shin
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Slurm-style hostlists for `--hosts` and hostfiles
+//!
+//! A bracket group expands to a list of values and ranges, keeping the
+//! zero-padding of the lower bound: `gpu-node[001-003,010]:9090` names four
+//! hosts. Several groups expand to every combination, and commas outside
+//! brackets separate entries. Hosts are validated after expansion, given
+//! `--port` when they have no port of their own, and must not repeat.
+
+use std::collections::HashSet;
+use std::path::Path;
+
+/// Upper bound on the expanded host list
+pub const MAX_HOSTS: usize = 10_000;
+
+/// Hostfiles larger than this are refused
+const MAX_FILE_SIZE: u64 = 10 * 1024 * 1024;
+
+/// Expand, validate and deduplicate host entries
+pub fn resolve<'a>(
+    entries: impl IntoIterator<Item = &'a str>,
+    default_port: Option<u16>,
+) -> Result<Vec<String>, String> {
+    let mut hosts = Vec::new();
+    let mut seen = HashSet::new();
+    for entry in entries {
+        for host in resolve_entry(entry, default_port)? {
+            if !seen.insert(host.clone()) {
+                return Err(format!("host '{host}' is listed more than once"));
+            }
+            hosts.push(host);
+            if hosts.len() > MAX_HOSTS {
+                return Err(format!("more than {MAX_HOSTS} hosts"));
+            }
+        }
+    }
+    Ok(hosts)
+}
+
+/// Entries of a hostfile, one per line; blank lines and `#` comments are
+/// skipped
+pub fn read_hostfile(path: &str) -> Result<Vec<String>, String> {
+    let canonical = Path::new(path)
+        .canonicalize()
+        .map_err(|e| format!("invalid hostfile path {path}: {e}"))?;
+    if !canonical.is_file() {
+        return Err(format!("hostfile {path} is not a regular file"));
+    }
+    let size = std::fs::metadata(&canonical)
+        .map_err(|e| format!("cannot access hostfile {path}: {e}"))?
+        .len();
+    if size > MAX_FILE_SIZE {
+        return Err(format!(
+            "hostfile {path} is too large: {size} bytes (max: {MAX_FILE_SIZE} bytes)"
+        ));
+    }
+    let content = std::fs::read_to_string(&canonical)
+        .map_err(|e| format!("failed to read hostfile {path}: {e}"))?;
+    Ok(content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_string)
+        .collect())
+}
+
+/// Expand one `--hosts` value or hostfile line into validated hosts
+fn resolve_entry(entry: &str, default_port: Option<u16>) -> Result<Vec<String>, String> {
+    // Socket paths are taken as written
+    if entry.starts_with("unix:") {
+        if !entry.chars().all(|c| c.is_ascii_graphic()) {
+            return Err(format!("invalid host '{entry}'"));
+        }
+        return Ok(vec![entry.to_string()]);
+    }
+
+    let entry = entry
+        .strip_prefix("http://")
+        .or_else(|| entry.strip_prefix("https://"))
+        .unwrap_or(entry)
+        .trim_end_matches('/');
+
+    let mut hosts = Vec::new();
+    for part in split_top_level(entry) {
+        for host in expand(part)? {
+            hosts.push(with_port(host, default_port)?);
+        }
+    }
+    Ok(hosts)
+}
+
+/// Split on commas that are not inside brackets
+fn split_top_level(entry: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+    for (idx, c) in entry.char_indices() {
+        match c {
+            '[' => depth += 1,
+            ']' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                parts.push(&entry[start..idx]);
+                start = idx + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&entry[start..]);
+    parts
+}
+
+/// Expand the bracket groups of a single host pattern
+pub fn expand(pattern: &str) -> Result<Vec<String>, String> {
+    let invalid = |reason: &str| format!("invalid hostlist '{pattern}': {reason}");
+
+    let Some(open) = pattern.find('[') else {
+        if pattern.contains(']') {
+            return Err(invalid("unbalanced ']'"));
+        }
+        return Ok(vec![pattern.to_string()]);
+    };
+    let prefix = &pattern[..open];
+    if prefix.contains(']') {
+        return Err(invalid("unbalanced ']'"));
+    }
+    let close = pattern[open..]
+        .find(']')
+        .map(|idx| open + idx)
+        .ok_or_else(|| invalid("missing ']'"))?;
+    let group = &pattern[open + 1..close];
+    if group.contains('[') {
+        return Err(invalid("nested '['"));
+    }
+
+    let values = expand_group(group).map_err(|reason| invalid(&reason))?;
+    let suffixes = expand(&pattern[close + 1..])?;
+    if values.len().saturating_mul(suffixes.len()) > MAX_HOSTS {
+        return Err(invalid(&format!("expands to more than {MAX_HOSTS} hosts")));
+    }
+
+    let mut hosts = Vec::with_capacity(values.len() * suffixes.len());
+    for value in &values {
+        for suffix in &suffixes {
+            hosts.push(format!("{prefix}{value}{suffix}"));
+        }
+    }
+    Ok(hosts)
+}
+
+/// Values of one bracket group: `1-4,7,10-12`
+fn expand_group(group: &str) -> Result<Vec<String>, String> {
+    let mut values = Vec::new();
+    for item in group.split(',') {
+        let Some((low, high)) = item.split_once('-') else {
+            if item.is_empty() || !item.chars().all(|c| c.is_ascii_alphanumeric()) {
+                return Err(format!("invalid value '{item}'"));
+            }
+            values.push(item.to_string());
+            continue;
+        };
+
+        let is_number = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_digit());
+        if !is_number(low) || !is_number(high) {
+            return Err(format!("invalid range '{item}'"));
+        }
+        let parse = |s: &str| {
+            s.parse::<u64>()
+                .map_err(|_| format!("invalid range '{item}'"))
+        };
+        let (start, end) = (parse(low)?, parse(high)?);
+        if start > end {
+            return Err(format!("range '{item}' runs backwards"));
+        }
+        if end - start >= MAX_HOSTS as u64 {
+            return Err(format!("range '{item}' is larger than {MAX_HOSTS} hosts"));
+        }
+        let width = low.len();
+        values.extend((start..=end).map(|n| format!("{n:0width$}")));
+    }
+    Ok(values)
+}
+
+/// Check an expanded host and give it the default port if it has none
+fn with_port(host: String, default_port: Option<u16>) -> Result<String, String> {
+    if host.is_empty()
+        || !host
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || ".-:_".contains(c))
+    {
+        return Err(format!("invalid host '{host}'"));
+    }
+    match host.rsplit_once(':') {
+        Some((name, port)) => {
+            if name.is_empty() || port.parse::<u16>().map_or(true, |port| port == 0) {
+                return Err(format!("invalid port in host '{host}'"));
+            }
+            Ok(host)
+        }
+        None => Ok(match default_port {
+            Some(port) => format!("{host}:{port}"),
+            None => host,
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand_ranges_and_lists() {
+        assert_eq!(expand("node1").unwrap(), vec!["node1"]);
+        assert_eq!(
+            expand("gpu-node[001-003,010]:9090").unwrap(),
+            vec![
+                "gpu-node001:9090",
+                "gpu-node002:9090",
+                "gpu-node003:9090",
+                "gpu-node010:9090",
+            ]
+        );
+        assert_eq!(
+            expand("rack[1-2]-n[a,b]").unwrap(),
+            vec!["rack1-na", "rack1-nb", "rack2-na", "rack2-nb"]
+        );
+        assert_eq!(expand("n[8-10]").unwrap(), vec!["n8", "n9", "n10"]);
+
+        assert!(expand("n[1-3").is_err());
+        assert!(expand("n1-3]").is_err());
+        assert!(expand("n[3-1]").is_err());
+        assert!(expand("n[1-a]").is_err());
+        assert!(expand("n[]").is_err());
+        assert!(expand("n[[1-2]]").is_err());
+        assert!(expand("n[0-99999999]").is_err());
+    }
+
+    #[test]
+    fn test_resolve_hosts() {
+        let hosts = resolve(
+            [
+                "http://node[1-2]",
+                "node3:9091,node4",
+                "unix:///run/all-smi.sock",
+            ],
+            Some(9090),
+        )
+        .unwrap();
+        assert_eq!(
+            hosts,
+            vec![
+                "node1:9090",
+                "node2:9090",
+                "node3:9091",
+                "node4:9090",
+                "unix:///run/all-smi.sock",
+            ]
+        );
+        assert_eq!(resolve(["node1"], None).unwrap(), vec!["node1"]);
+
+        let err = resolve(["node[1-3]:9090", "node2:9090"], None).unwrap_err();
+        assert!(
+            err.contains("'node2:9090' is listed more than once"),
+            "{err}"
+        );
+        // Duplicates are found after the default port is applied
+        assert!(resolve(["node1", "node1:9090"], Some(9090)).is_err());
+
+        assert!(resolve(["node 1"], None).is_err());
+        assert!(resolve(["node1:http"], None).is_err());
+        assert!(resolve(["node1:0"], None).is_err());
+        assert!(resolve(["n[1-6000]", "m[1-6000]"], None).is_err());
+    }
+
+    #[test]
+    fn test_read_hostfile() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("hosts");
+        std::fs::write(&path, "# cluster A\ngpu[01-02]:9090\n\n  node5:9090  \n").unwrap();
+
+        let entries = read_hostfile(path.to_str().unwrap()).unwrap();
+        assert_eq!(entries, vec!["gpu[01-02]:9090", "node5:9090"]);
+        assert!(read_hostfile(dir.path().to_str().unwrap()).is_err());
+    }
+}
@@ -14,7 +14,9 @@
 
 pub mod data_collection;
 pub mod data_collector;
+pub mod discovery;
 pub mod event_handler;
+pub mod hostlist;
 pub mod runner;
 pub mod terminal_manager;
 pub mod ui_loop;
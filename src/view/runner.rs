@@ -14,12 +14,14 @@
 
 use std::sync::Arc;
 
-use tokio::sync::Mutex;
+use tokio::sync::{watch, Mutex};
 
 use crate::app_state::AppState;
 use crate::cli::{LocalArgs, ViewArgs};
 use crate::view::{
-    data_collector::DataCollector, terminal_manager::TerminalManager, ui_loop::UiLoop,
+    data_collector::{DataCollector, HostSources},
+    terminal_manager::TerminalManager,
+    ui_loop::UiLoop,
 };
 
 pub async fn run_local_mode(args: &LocalArgs) {
@@ -48,6 +50,7 @@ pub async fn run_local_mode(args: &LocalArgs) {
         hosts: None,
         hostfile: None,
         interval: args.interval,
+        ..Default::default()
     };
     tokio::spawn(async move {
         data_collector.run_local_mode(view_args).await;
@@ -70,6 +73,7 @@ pub async fn run_local_mode(args: &LocalArgs) {
         hosts: None,
         hostfile: None,
         interval: args.interval,
+        ..Default::default()
     };
     if let Err(e) = ui_loop.run(&view_args).await {
         eprintln!("UI loop error: {e}");
@@ -96,15 +100,15 @@ pub async fn run_view_mode(args: &ViewArgs) {
     // Start data collection in background
     let data_collector = DataCollector::new(Arc::clone(&app_state));
     let args_clone = args.clone();
+    let (sources_tx, sources_rx) = watch::channel(HostSources::from_args(args));
     tokio::spawn(async move {
-        let hosts = args_clone.hosts.clone().unwrap_or_default();
-        let hostfile = args_clone.hostfile.clone();
-
         // Remote mode
-        data_collector
-            .run_remote_mode(args_clone, hosts, hostfile)
-            .await;
+        data_collector.run_remote_mode(args_clone, sources_rx).await;
     });
+    #[cfg(unix)]
+    tokio::spawn(reload_on_hangup(sources_tx, app_state.clone()));
+    #[cfg(not(unix))]
+    drop(sources_tx);
 
     // Run UI loop
     let mut ui_loop = match UiLoop::new(app_state) {
@@ -121,3 +125,49 @@ pub async fn run_view_mode(args: &ViewArgs) {
 
     // Terminal cleanup is handled by TerminalManager's Drop trait
 }
+
+/// Re-read the configuration on every SIGHUP and switch to its host list.
+/// Hosts auto-discovered at startup are kept when the configuration names
+/// none.
+#[cfg(unix)]
+async fn reload_on_hangup(sources: watch::Sender<HostSources>, app_state: Arc<Mutex<AppState>>) {
+    use crate::cli::Commands;
+    use crate::common::config::Settings;
+    use crate::common::config_file::parse_cli;
+    use tokio::signal::unix::{signal, SignalKind};
+
+    let Ok(mut hangup) = signal(SignalKind::hangup()) else {
+        return;
+    };
+    while hangup.recv().await.is_some() {
+        let reloaded = parse_cli().and_then(|(cli, config)| match cli.command {
+            Some(Commands::View(args)) => {
+                let reloaded = HostSources::from_args(&args);
+                reloaded.check()?;
+                Ok((reloaded, config.settings()?))
+            }
+            _ => Err("command line no longer selects view mode".to_string()),
+        });
+
+        let mut state = app_state.lock().await;
+        // A message over the notification length limit is not shown
+        let _ = match reloaded {
+            Ok((reloaded, settings)) => {
+                Settings::install(settings);
+                if !reloaded.is_empty() {
+                    sources.send_if_modified(|current| {
+                        let changed = *current != reloaded;
+                        *current = reloaded;
+                        changed
+                    });
+                }
+                state
+                    .notifications
+                    .info("Configuration reloaded".to_string())
+            }
+            Err(e) => state
+                .notifications
+                .error(format!("Configuration reload failed: {e}")),
+        };
+    }
+}
@@ -15,7 +15,7 @@
 use std::collections::HashSet;
 use std::io::{stdout, Write};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use chrono::Local;
 use crossterm::{
@@ -30,6 +30,7 @@ use tokio::sync::Mutex;
 use crate::app_state::AppState;
 use crate::cli::ViewArgs;
 use crate::common::config::AppConfig;
+use crate::device::ProcessInfo;
 use crate::ui::buffer::{BufferWriter, DifferentialRenderer};
 use crate::ui::dashboard::{draw_dashboard_items, draw_system_view};
 use crate::ui::layout::LayoutCalculator;
@@ -578,26 +579,20 @@ impl UiLoop {
             let current_hostname = &state.tabs[state.current_tab];
 
             // Check connection status for the current node
-            let is_connected =
+            let (status, connected_by_default) =
                 if let Some(host_id) = state.hostname_to_host_id.get(current_hostname) {
                     // Found in reverse lookup, get the connection status
-                    state
-                        .connection_status
-                        .get(host_id)
-                        .map(|status| status.is_connected)
-                        .unwrap_or(false)
+                    (state.connection_status.get(host_id), false)
                 } else {
-                    // Direct lookup by host_id
-                    state
-                        .connection_status
-                        .get(current_hostname)
-                        .map(|status| status.is_connected)
-                        .unwrap_or(true) // Default to connected for local mode
+                    // Direct lookup by host_id, defaulting to connected for local mode
+                    (state.connection_status.get(current_hostname), true)
                 };
+            let is_connected = status.map_or(connected_by_default, |status| status.is_connected);
 
             if !is_connected {
                 // Show elegant disconnection notification
-                self.render_disconnection_notification(buffer, current_hostname, width);
+                let next_retry = status.and_then(|status| status.next_retry);
+                self.render_disconnection_notification(buffer, current_hostname, next_retry, width);
                 return;
             }
 
@@ -667,6 +662,22 @@ impl UiLoop {
                     .unwrap_or(0);
                 print_storage_info(buffer, i, storage_info, width, hostname_scroll_offset);
             }
+
+            // GPU processes for specific host, when it exports them
+            let processes_to_display: Vec<ProcessInfo> = state
+                .process_info
+                .iter()
+                .filter(|info| info.host_id == *current_hostname)
+                .cloned()
+                .collect();
+
+            // Title, header, separator and footer take six rows; skip the
+            // list when the devices leave no room for a single process
+            let (_, rows) = size().unwrap_or((0, 0));
+            let rows_left = (rows as usize).saturating_sub(buffer.line_count() + 2);
+            if !processes_to_display.is_empty() && rows_left > 6 {
+                self.render_process_list(buffer, state, &processes_to_display, usize::MAX, 0);
+            }
         }
     }
 
@@ -674,6 +685,7 @@ impl UiLoop {
         &self,
         buffer: &mut BufferWriter,
         hostname: &str,
+        next_retry: Option<Instant>,
         width: usize,
     ) {
         use crate::ui::text::print_colored_text;
@@ -764,6 +776,30 @@ impl UiLoop {
         print_colored_text(buffer, " │", Color::Red, None, None);
         writeln!(buffer).unwrap();
 
+        // Retry line, while the node is backing off
+        if let Some(next_retry) = next_retry {
+            let wait = next_retry.saturating_duration_since(Instant::now());
+            let retry_text = if wait.is_zero() {
+                "Retrying now".to_string()
+            } else {
+                format!("Next retry in {}s", wait.as_secs() + 1)
+            };
+            let retry_padding = (box_width - 4 - retry_text.len()) / 2;
+            write!(buffer, "{}", " ".repeat(margin)).unwrap();
+            print_colored_text(buffer, "│ ", Color::Red, None, None);
+            print_colored_text(buffer, &" ".repeat(retry_padding), Color::White, None, None);
+            print_colored_text(buffer, &retry_text, Color::DarkGrey, None, None);
+            print_colored_text(
+                buffer,
+                &" ".repeat(box_width - 4 - retry_padding - retry_text.len()),
+                Color::White,
+                None,
+                None,
+            );
+            print_colored_text(buffer, " │", Color::Red, None, None);
+            writeln!(buffer).unwrap();
+        }
+
         // Empty line
         write!(buffer, "{}", " ".repeat(margin)).unwrap();
         print_colored_text(buffer, "│", Color::Red, None, None);
@@ -827,44 +863,61 @@ impl UiLoop {
         // Process information for local mode (if available)
         if !state.process_info.is_empty() {
             // The print_process_info function expects the full process list and handles slicing internally
-            let (cols, rows) = match crossterm::terminal::size() {
-                Ok((c, r)) => (c, r),
-                Err(_) => (
-                    AppConfig::DEFAULT_TERMINAL_WIDTH,
-                    AppConfig::DEFAULT_TERMINAL_HEIGHT,
-                ),
-            };
-
-            // Calculate how many lines have been used so far
-            // Use the efficient line counter from BufferWriter
-            let lines_used = buffer.line_count();
-
-            // Add a blank line before process list
-            queue!(buffer, Print("\r\n")).unwrap();
-
-            // Reserve 1 line for function keys at the bottom
-            let function_key_rows = 1;
-
-            // Calculate available rows for process list
-            // Use all remaining space from current position to the function keys
-            // Account for the blank line we just added
-            let available_rows = rows.saturating_sub(lines_used as u16 + 1 + function_key_rows);
-
-            // Get current user for process coloring
-            let current_user = whoami::username();
-
-            print_process_info(
+            self.render_process_list(
                 buffer,
+                state,
                 &state.process_info,
                 state.selected_process_index,
                 state.start_index,
-                available_rows,
-                cols,
-                state.process_horizontal_scroll_offset,
-                &current_user,
-                &state.sort_criteria,
-                &state.sort_direction,
             );
         }
     }
+
+    fn render_process_list(
+        &self,
+        buffer: &mut BufferWriter,
+        state: &AppState,
+        processes: &[ProcessInfo],
+        selected_index: usize,
+        start_index: usize,
+    ) {
+        let (cols, rows) = match crossterm::terminal::size() {
+            Ok((c, r)) => (c, r),
+            Err(_) => (
+                AppConfig::DEFAULT_TERMINAL_WIDTH,
+                AppConfig::DEFAULT_TERMINAL_HEIGHT,
+            ),
+        };
+
+        // Calculate how many lines have been used so far
+        // Use the efficient line counter from BufferWriter
+        let lines_used = buffer.line_count();
+
+        // Add a blank line before process list
+        queue!(buffer, Print("\r\n")).unwrap();
+
+        // Reserve 1 line for function keys at the bottom
+        let function_key_rows = 1;
+
+        // Calculate available rows for process list
+        // Use all remaining space from current position to the function keys
+        // Account for the blank line we just added
+        let available_rows = rows.saturating_sub(lines_used as u16 + 1 + function_key_rows);
+
+        // Get current user for process coloring
+        let current_user = whoami::username();
+
+        print_process_info(
+            buffer,
+            processes,
+            selected_index,
+            start_index,
+            available_rows,
+            cols,
+            state.process_horizontal_scroll_offset,
+            &current_user,
+            &state.sort_criteria,
+            &state.sort_direction,
+        );
+    }
 }
@@ -1,5 +1,5 @@
 use all_smi::device::CpuPlatformType;
-use all_smi::network::metrics_parser::MetricsParser;
+use all_smi::network::metrics_parser::{MetricsParser, ParsedMetrics};
 use regex::Regex;
 
 #[test]
@@ -17,7 +17,7 @@ all_smi_cpu_core_count{cpu_model="", instance="node-0001", index="0"} 128
 all_smi_cpu_frequency_mhz{instance="node-0001"} 2450
 "#;
 
-    let (_, cpu_info, _, _) = parser.parse_metrics(test_data, host, &re);
+    let ParsedMetrics { cpu_info, .. } = parser.parse_metrics(test_data, host, &re);
 
     assert_eq!(cpu_info.len(), 1);
     let cpu = &cpu_info[0];